@@ -0,0 +1,40 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::Error;
+
+const READ_ONLY_FLAG: &str = "--read-only";
+const READ_ONLY_ENV: &str = "GAME_ASSET_TRACKER_READ_ONLY";
+
+// Guest installs on shared review machines start read-only. The mode is fixed
+// for the lifetime of the process so the frontend cannot lift it.
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct AccessMode {
+    read_only: bool,
+}
+
+impl AccessMode {
+    pub fn from_launch() -> Self {
+        let flag = std::env::args().skip(1).any(|arg| arg == READ_ONLY_FLAG);
+        let env = std::env::var(READ_ONLY_ENV)
+            .map(|value| matches!(value.trim(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        AccessMode {
+            read_only: flag || env,
+        }
+    }
+}
+
+// Every command that writes files, spawns ingestion or changes persisted
+// state calls this before doing any work.
+pub fn ensure_writable(app: &AppHandle) -> Result<(), Error> {
+    if app.state::<AccessMode>().read_only {
+        return Err(Error::ReadOnly);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_access_mode(mode: State<'_, AccessMode>) -> AccessMode {
+    *mode
+}
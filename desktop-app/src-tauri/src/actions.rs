@@ -0,0 +1,571 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    access, asset_types, batch, catalog, check_source_available, documents, environment, inference,
+    jobs, marketplace, metrics, preflight, profiles, queue, review, run_ingestion_job, search,
+    sync_cache, tags, textures, uv_binary, validate_ingestion_path, IngestionConfig,
+};
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct ArgSpec {
+    name: &'static str,
+    kind: &'static str,
+    required: bool,
+}
+
+const fn arg(name: &'static str, kind: &'static str) -> ArgSpec {
+    ArgSpec {
+        name,
+        kind,
+        required: true,
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct ActionDescriptor {
+    id: &'static str,
+    title: &'static str,
+    args: &'static [ArgSpec],
+    permission: Permission,
+}
+
+// Single list of everything the command palette, scripting and any future
+// remote API may invoke. Argument names use the same camelCase keys as
+// direct Tauri invokes.
+const ACTIONS: &[ActionDescriptor] = &[
+    ActionDescriptor {
+        id: "ingestion.run",
+        title: "Run ingestion",
+        args: &[arg("config", "object"), arg("ingestionPath", "path")],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "ingestion.run_profile",
+        title: "Run ingestion from profile",
+        args: &[
+            arg("profileId", "string"),
+            arg("overrides", "object"),
+            arg("ingestionPath", "path"),
+        ],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "ingestion.validate_path",
+        title: "Validate ingestion path",
+        args: &[arg("path", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "ingestion.check_source",
+        title: "Check source availability",
+        args: &[arg("source", "string"), arg("ingestionPath", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "ingestion.preflight",
+        title: "Run pre-flight checks",
+        args: &[
+            arg("source", "string"),
+            arg("ingestionPath", "path"),
+            ArgSpec {
+                name: "outputDir",
+                kind: "path",
+                required: false,
+            },
+        ],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "batch.discover",
+        title: "Discover packs in folder",
+        args: &[arg("root", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "batch.run",
+        title: "Ingest all packs in folder",
+        args: &[
+            arg("root", "path"),
+            arg("config", "object"),
+            arg("ingestionPath", "path"),
+        ],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "batch.infer_details",
+        title: "Infer pack name, author and version",
+        args: &[arg("path", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "catalog.list_assets",
+        title: "List catalog assets",
+        args: &[
+            ArgSpec {
+                name: "packId",
+                kind: "string",
+                required: false,
+            },
+            ArgSpec {
+                name: "limit",
+                kind: "integer",
+                required: false,
+            },
+            ArgSpec {
+                name: "offset",
+                kind: "integer",
+                required: false,
+            },
+            ArgSpec {
+                name: "reviewStatus",
+                kind: "string",
+                required: false,
+            },
+        ],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "catalog.get_asset",
+        title: "Show catalog asset",
+        args: &[arg("id", "integer")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "catalog.delete_asset",
+        title: "Delete catalog asset",
+        args: &[arg("id", "integer")],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "catalog.approve_asset",
+        title: "Approve staged asset",
+        args: &[
+            arg("id", "integer"),
+            ArgSpec {
+                name: "reason",
+                kind: "string",
+                required: false,
+            },
+        ],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "catalog.reject_asset",
+        title: "Reject staged asset",
+        args: &[arg("id", "integer"), arg("reason", "string")],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "catalog.search",
+        title: "Search assets",
+        args: &[
+            arg("query", "string"),
+            ArgSpec {
+                name: "limit",
+                kind: "integer",
+                required: false,
+            },
+        ],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "marketplace.resolve_url",
+        title: "Look up marketplace listing",
+        args: &[arg("url", "string")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "documents.harvest",
+        title: "Read pack README and LICENSE",
+        args: &[arg("path", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "textures.validate",
+        title: "Validate texture sets",
+        args: &[arg("path", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "manifest.summarize_asset_types",
+        title: "Summarize asset types",
+        args: &[arg("manifestJson", "string")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "tags.tree",
+        title: "Show tag tree",
+        args: &[arg("manifestJson", "string")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "tags.find_assets",
+        title: "Find assets by tag",
+        args: &[arg("manifestJson", "string"), arg("tag", "string")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "jobs.status",
+        title: "Show job status",
+        args: &[arg("id", "string")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "jobs.enqueue",
+        title: "Queue ingestion",
+        args: &[arg("config", "object"), arg("ingestionPath", "path")],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "jobs.list",
+        title: "List jobs",
+        args: &[],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "jobs.cancel",
+        title: "Cancel ingestion",
+        args: &[arg("jobId", "string")],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "metrics.performance_history",
+        title: "Show performance history",
+        args: &[],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "environment.info",
+        title: "Show ingestion environment",
+        args: &[arg("ingestionPath", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "environment.detect_uv",
+        title: "Locate uv",
+        args: &[],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "environment.install_uv",
+        title: "Install uv",
+        args: &[],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "environment.verify_lockfile",
+        title: "Verify lockfile",
+        args: &[arg("ingestionPath", "path")],
+        permission: Permission::Read,
+    },
+    ActionDescriptor {
+        id: "environment.pin",
+        title: "Pin ingestion environment",
+        args: &[arg("ingestionPath", "path")],
+        permission: Permission::Write,
+    },
+    ActionDescriptor {
+        id: "environment.clear_sync_cache",
+        title: "Clear dependency sync cache",
+        args: &[],
+        permission: Permission::Write,
+    },
+];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IngestArgs {
+    config: IngestionConfig,
+    ingestion_path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileArgs {
+    profile_id: String,
+    #[serde(default)]
+    overrides: Value,
+    ingestion_path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchArgs {
+    root: String,
+    config: IngestionConfig,
+    ingestion_path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SourceArgs {
+    source: String,
+    ingestion_path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PreflightArgs {
+    source: String,
+    ingestion_path: String,
+    output_dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IngestionPathArgs {
+    ingestion_path: String,
+}
+
+#[derive(Deserialize)]
+struct PathArgs {
+    path: String,
+}
+
+#[derive(Deserialize)]
+struct RootArgs {
+    root: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestArgs {
+    manifest_json: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TagArgs {
+    manifest_json: String,
+    tag: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListAssetsArgs {
+    pack_id: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    review_status: Option<review::ReviewStatus>,
+}
+
+#[derive(Deserialize)]
+struct ReviewArgs {
+    id: i64,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AssetIdArgs {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+struct SearchArgs {
+    query: String,
+    limit: Option<u32>,
+    group_variants: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct UrlArgs {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct IdArgs {
+    id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobArgs {
+    job_id: String,
+}
+
+fn parse<T: DeserializeOwned>(id: &str, args: Value) -> Result<T, String> {
+    // Actions without arguments may be invoked with null.
+    let args = if args.is_null() {
+        Value::Object(Default::default())
+    } else {
+        args
+    };
+    serde_json::from_value(args).map_err(|e| format!("Invalid arguments for {}: {}", id, e))
+}
+
+fn respond<T: Serialize>(result: Result<T, String>) -> Result<Value, String> {
+    serde_json::to_value(result?).map_err(|e| format!("Failed to serialize result: {}", e))
+}
+
+pub fn is_registered(id: &str) -> bool {
+    ACTIONS.iter().any(|action| action.id == id)
+}
+
+#[tauri::command]
+pub fn list_actions() -> Vec<ActionDescriptor> {
+    ACTIONS.to_vec()
+}
+
+#[tauri::command]
+pub async fn invoke_action(app: AppHandle, id: String, args: Value) -> Result<Value, String> {
+    let action = ACTIONS
+        .iter()
+        .find(|action| action.id == id)
+        .ok_or(format!("Unknown action: {}", id))?;
+    if action.permission == Permission::Write {
+        access::ensure_writable(&app)?;
+    }
+
+    match action.id {
+        "ingestion.run" => {
+            let a: IngestArgs = parse(&id, args)?;
+            respond(
+                run_ingestion_job(app, a.config, a.ingestion_path)
+                    .await
+                    .map_err(String::from),
+            )
+        }
+        "ingestion.run_profile" => {
+            let a: ProfileArgs = parse(&id, args)?;
+            respond(
+                profiles::run_ingestion_with_profile(
+                    app,
+                    a.profile_id,
+                    a.overrides,
+                    a.ingestion_path,
+                )
+                .await
+                .map_err(String::from),
+            )
+        }
+        "ingestion.validate_path" => {
+            let a: PathArgs = parse(&id, args)?;
+            respond(validate_ingestion_path(a.path).map_err(String::from))
+        }
+        "ingestion.check_source" => {
+            let a: SourceArgs = parse(&id, args)?;
+            respond(check_source_available(a.source, a.ingestion_path).map_err(String::from))
+        }
+        "ingestion.preflight" => {
+            let a: PreflightArgs = parse(&id, args)?;
+            respond(preflight::preflight_check(app, a.source, a.ingestion_path, a.output_dir).await)
+        }
+        "batch.discover" => {
+            let a: RootArgs = parse(&id, args)?;
+            respond(batch::discover_packs(a.root))
+        }
+        "batch.run" => {
+            let a: BatchArgs = parse(&id, args)?;
+            respond(batch::run_batch_ingestion(app, a.root, a.config, a.ingestion_path).await)
+        }
+        "batch.infer_details" => {
+            let a: PathArgs = parse(&id, args)?;
+            respond(Ok(inference::infer_pack_details(a.path)))
+        }
+        "catalog.list_assets" => {
+            let a: ListAssetsArgs = parse(&id, args)?;
+            respond(catalog::list_assets(
+                app,
+                a.pack_id,
+                a.limit,
+                a.offset,
+                a.review_status,
+            ))
+        }
+        "catalog.get_asset" => {
+            let a: AssetIdArgs = parse(&id, args)?;
+            respond(catalog::get_asset(app, a.id))
+        }
+        "catalog.delete_asset" => {
+            let a: AssetIdArgs = parse(&id, args)?;
+            respond(catalog::delete_asset(app, a.id))
+        }
+        "catalog.approve_asset" => {
+            let a: ReviewArgs = parse(&id, args)?;
+            respond(review::approve_asset(app, a.id, a.reason))
+        }
+        "catalog.reject_asset" => {
+            let a: ReviewArgs = parse(&id, args)?;
+            respond(review::reject_asset(
+                app,
+                a.id,
+                a.reason.unwrap_or_default(),
+            ))
+        }
+        "catalog.search" => {
+            let a: SearchArgs = parse(&id, args)?;
+            respond(search::search_assets(
+                app,
+                a.query,
+                a.limit,
+                a.group_variants,
+            ))
+        }
+        "marketplace.resolve_url" => {
+            let a: UrlArgs = parse(&id, args)?;
+            respond(marketplace::resolve_marketplace_url(a.url).await)
+        }
+        "documents.harvest" => {
+            let a: PathArgs = parse(&id, args)?;
+            respond(documents::harvest_pack_documents(a.path))
+        }
+        "textures.validate" => {
+            let a: PathArgs = parse(&id, args)?;
+            respond(textures::validate_texture_sets(a.path))
+        }
+        "manifest.summarize_asset_types" => {
+            let a: ManifestArgs = parse(&id, args)?;
+            respond(asset_types::summarize_asset_types(a.manifest_json))
+        }
+        "tags.tree" => {
+            let a: ManifestArgs = parse(&id, args)?;
+            respond(tags::get_tag_tree(a.manifest_json))
+        }
+        "tags.find_assets" => {
+            let a: TagArgs = parse(&id, args)?;
+            respond(tags::find_assets_by_tag(a.manifest_json, a.tag))
+        }
+        "jobs.status" => {
+            let a: IdArgs = parse(&id, args)?;
+            respond(jobs::get_job_status(app.state(), a.id))
+        }
+        "jobs.enqueue" => {
+            let a: IngestArgs = parse(&id, args)?;
+            respond(queue::enqueue_ingestion(app, a.config, a.ingestion_path))
+        }
+        "jobs.list" => respond(Ok(queue::list_jobs(app.state()))),
+        "jobs.cancel" => {
+            let a: JobArgs = parse(&id, args)?;
+            respond(queue::cancel_job(app, a.job_id))
+        }
+        "metrics.performance_history" => {
+            respond(metrics::get_performance_history(app.clone(), app.state()))
+        }
+        "environment.info" => {
+            let a: IngestionPathArgs = parse(&id, args)?;
+            respond(environment::get_environment_info(app, a.ingestion_path).await)
+        }
+        "environment.detect_uv" => respond(uv_binary::detect_uv(app).await),
+        "environment.install_uv" => respond(uv_binary::install_uv(app).await),
+        "environment.verify_lockfile" => {
+            let a: IngestionPathArgs = parse(&id, args)?;
+            respond(environment::verify_lockfile(app, a.ingestion_path).await)
+        }
+        "environment.pin" => {
+            let a: IngestionPathArgs = parse(&id, args)?;
+            respond(environment::pin_environment(app, a.ingestion_path))
+        }
+        "environment.clear_sync_cache" => respond(sync_cache::clear_uv_sync_cache(app)),
+        _ => Err(format!("Action {} has no handler", id)),
+    }
+}
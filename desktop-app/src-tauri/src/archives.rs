@@ -0,0 +1,893 @@
+use serde::Serialize;
+use sevenz_rust::{Archive, Password, SevenZReader};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::{access, hashing, storage};
+
+const EXTRACT_DIR: &str = "extracted";
+const PROGRESS_BYTES: u64 = 64 * 1024 * 1024;
+const PROGRESS_FILES: u64 = 200;
+const CHUNK: usize = 1024 * 1024;
+const MAX_COMPRESSION_RATIO: u64 = 200;
+const MIN_SIZE_LIMIT: u64 = 1024 * 1024 * 1024;
+const MAX_EXTRACTED_BYTES: u64 = 256 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "7z")]
+    SevenZ,
+    #[serde(rename = "rar")]
+    Rar,
+}
+
+// Detected from the file signature, since downloaded packs are often
+// misnamed.
+pub fn detect(path: &Path) -> Option<ArchiveFormat> {
+    if !path.is_file() {
+        return None;
+    }
+    let mut magic = [0u8; 6];
+    File::open(path).ok()?.read_exact(&mut magic).ok()?;
+    match magic {
+        [b'P', b'K', 3, 4, ..] | [b'P', b'K', 5, 6, ..] => Some(ArchiveFormat::Zip),
+        [b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C] => Some(ArchiveFormat::SevenZ),
+        [b'R', b'a', b'r', b'!', 0x1A, 0x07] => Some(ArchiveFormat::Rar),
+        _ => None,
+    }
+}
+
+// RAR has no pure-Rust decoder, so it is recognised only to explain why it
+// is refused.
+fn rar_unsupported(path: &Path) -> String {
+    format!(
+        "{} is a RAR archive, which cannot be read here; extract it first",
+        path.display()
+    )
+}
+
+fn format_of(path: &Path) -> Result<ArchiveFormat, String> {
+    detect(path).ok_or_else(|| format!("{} is not a zip or 7z archive", path.display()))
+}
+
+// Entry names come from the archive, so anything that could land outside
+// the destination is rejected.
+fn enclosed(name: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(name.replace('\\', "/"));
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!out.as_os_str().is_empty()).then_some(out)
+}
+
+// Paths already written, keyed in lowercase. Archives built on a
+// case-sensitive system can hold `Foo.png` and `foo.png`, which would
+// overwrite each other on Windows and macOS, so such entries are refused
+// everywhere, as are repeated file entries.
+#[derive(Default)]
+struct Claims {
+    files: HashSet<String>,
+    // Lowercase path to the spelling first seen.
+    dirs: HashMap<String, String>,
+}
+
+impl Claims {
+    fn claim(&mut self, relative: &Path, is_dir: bool) -> bool {
+        let parts: Vec<String> = relative
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy().to_string())
+            .collect();
+        let dir_count = if is_dir { parts.len() } else { parts.len() - 1 };
+        let mut dirs = Vec::new();
+        let mut prefix = String::new();
+        for part in &parts[..dir_count] {
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            prefix.push_str(part);
+            let lower = prefix.to_lowercase();
+            if self.files.contains(&lower)
+                || self.dirs.get(&lower).is_some_and(|seen| *seen != prefix)
+            {
+                return false;
+            }
+            dirs.push((lower, prefix.clone()));
+        }
+        let file = (!is_dir).then(|| parts.join("/").to_lowercase());
+        if let Some(file) = &file {
+            if self.files.contains(file) || self.dirs.contains_key(file) {
+                return false;
+            }
+        }
+        self.dirs.extend(dirs);
+        self.files.extend(file);
+        true
+    }
+}
+
+// Refuses to follow a symlink already in the destination, whether it is the
+// entry itself or one of its folders.
+fn check_no_symlinks(destination: &Path, relative: &Path) -> io::Result<()> {
+    let mut path = destination.to_path_buf();
+    for part in relative.components() {
+        path.push(part);
+        if fs::symlink_metadata(&path).is_ok_and(|meta| meta.file_type().is_symlink()) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} is a symlink", path.display()),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn create_dir(destination: &Path, relative: &Path) -> io::Result<PathBuf> {
+    check_no_symlinks(destination, relative)?;
+    let target = destination.join(relative);
+    fs::create_dir_all(&target)?;
+    Ok(target)
+}
+
+// Never opens an existing file, so nothing already on disk is overwritten.
+fn create_file(destination: &Path, relative: &Path) -> io::Result<File> {
+    if let Some(parent) = relative.parent() {
+        create_dir(destination, parent)?;
+    }
+    check_no_symlinks(destination, relative)?;
+    File::options()
+        .write(true)
+        .create_new(true)
+        .open(destination.join(relative))
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ArchiveEntry {
+    path: String,
+    size_bytes: u64,
+    compressed_bytes: Option<u64>,
+    is_dir: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ArchiveListing {
+    format: ArchiveFormat,
+    entries: Vec<ArchiveEntry>,
+    file_count: u64,
+    total_bytes: u64,
+}
+
+fn zip_reader(path: &Path) -> Result<zip::ZipArchive<File>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    zip::ZipArchive::new(file).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+// Reads only the archive's directory, without decompressing anything.
+pub fn list(path: &Path) -> Result<ArchiveListing, String> {
+    let format = format_of(path)?;
+    let read_err = |e: String| format!("Failed to read {}: {}", path.display(), e);
+    let entries: Vec<ArchiveEntry> = match format {
+        ArchiveFormat::Zip => {
+            let mut archive = zip_reader(path)?;
+            let mut entries = Vec::with_capacity(archive.len());
+            for index in 0..archive.len() {
+                let entry = archive
+                    .by_index_raw(index)
+                    .map_err(|e| read_err(e.to_string()))?;
+                entries.push(ArchiveEntry {
+                    path: entry.name().to_string(),
+                    size_bytes: entry.size(),
+                    compressed_bytes: Some(entry.compressed_size()),
+                    is_dir: entry.is_dir(),
+                });
+            }
+            entries
+        }
+        ArchiveFormat::SevenZ => Archive::open(path)
+            .map_err(|e| read_err(e.to_string()))?
+            .files
+            .iter()
+            .filter(|entry| !entry.is_anti_item)
+            .map(|entry| ArchiveEntry {
+                path: entry.name.clone(),
+                size_bytes: entry.size,
+                // Solid blocks are shared between files, so a per-file size
+                // is not meaningful.
+                compressed_bytes: None,
+                is_dir: entry.is_directory,
+            })
+            .collect(),
+        ArchiveFormat::Rar => return Err(rar_unsupported(path)),
+    };
+    let files = entries.iter().filter(|entry| !entry.is_dir);
+    Ok(ArchiveListing {
+        format,
+        file_count: files.clone().count() as u64,
+        total_bytes: files.map(|entry| entry.size_bytes).sum(),
+        entries,
+    })
+}
+
+// Emitted as `archive-progress` every 64 MiB or 200 files, and at the end.
+#[derive(Debug, Serialize, Clone)]
+pub struct ExtractProgress {
+    archive: String,
+    bytes_extracted: u64,
+    total_bytes: u64,
+    files_extracted: u64,
+    total_files: u64,
+    file: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ExtractFailure {
+    path: String,
+    reason: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ExtractSummary {
+    destination: String,
+    files: u64,
+    bytes: u64,
+    // Entries whose paths pointed outside the destination, repeated an
+    // earlier entry or differed from one only by case.
+    skipped: Vec<String>,
+    // Entries that could not be written; the rest of the archive still is.
+    failed: Vec<ExtractFailure>,
+}
+
+// The most an archive may expand to: a multiple of its own size, since
+// asset packs rarely compress past 20:1 and far beyond that is a zip bomb,
+// with a fixed allowance for small archives and a cap for huge ones.
+fn size_limit(archive_bytes: u64) -> u64 {
+    archive_bytes
+        .saturating_mul(MAX_COMPRESSION_RATIO)
+        .clamp(MIN_SIZE_LIMIT, MAX_EXTRACTED_BYTES)
+}
+
+// Unix symlinks are stored as entries whose content is the link target.
+fn is_symlink_mode(mode: u32) -> bool {
+    mode & 0o170000 == 0o120000
+}
+
+// 7z keeps the Unix mode in the upper half of the attributes when this
+// flag is set.
+fn is_7z_symlink(entry: &sevenz_rust::SevenZArchiveEntry) -> bool {
+    entry.has_windows_attributes
+        && entry.windows_attributes & 0x8000 != 0
+        && is_symlink_mode(entry.windows_attributes >> 16)
+}
+
+enum EntryError {
+    Io(io::Error),
+    TooLarge,
+}
+
+impl From<io::Error> for EntryError {
+    fn from(e: io::Error) -> Self {
+        EntryError::Io(e)
+    }
+}
+
+struct Extraction<'a> {
+    on_progress: &'a dyn Fn(&ExtractProgress),
+    destination: &'a Path,
+    progress: ExtractProgress,
+    reported_bytes: u64,
+    reported_files: u64,
+    skipped: Vec<String>,
+    failed: Vec<ExtractFailure>,
+    claims: Claims,
+    // Bytes decompressed so far, including entries that were discarded.
+    decompressed: u64,
+    limit: u64,
+}
+
+impl Extraction<'_> {
+    fn report(&mut self, file: Option<String>) {
+        self.progress.file = file;
+        self.reported_bytes = self.progress.bytes_extracted;
+        self.reported_files = self.progress.files_extracted;
+        (self.on_progress)(&self.progress);
+    }
+
+    fn fail(&mut self, name: &str, reason: impl ToString) {
+        self.failed.push(ExtractFailure {
+            path: name.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    fn too_large(&self) -> String {
+        format!(
+            "the archive expands to more than {} bytes, the limit for its size",
+            self.limit
+        )
+    }
+
+    // A failed entry is recorded and the extraction carries on; only passing
+    // the size limit stops it.
+    fn write(
+        &mut self,
+        name: &str,
+        is_dir: bool,
+        is_symlink: bool,
+        reader: &mut dyn Read,
+    ) -> Result<(), String> {
+        let Some(relative) = enclosed(name).filter(|path| self.claims.claim(path, is_dir)) else {
+            self.skipped.push(name.to_string());
+            return Ok(());
+        };
+        if is_symlink {
+            self.fail(name, "symbolic links are not extracted");
+            return Ok(());
+        }
+        if is_dir {
+            if let Err(e) = create_dir(self.destination, &relative) {
+                self.fail(name, e);
+            }
+            return Ok(());
+        }
+        match self.write_file(name, &relative, reader) {
+            Ok(()) => {}
+            Err(EntryError::Io(e)) => self.fail(name, e),
+            Err(EntryError::TooLarge) => return Err(self.too_large()),
+        }
+        Ok(())
+    }
+
+    fn write_file(
+        &mut self,
+        name: &str,
+        relative: &Path,
+        reader: &mut dyn Read,
+    ) -> Result<(), EntryError> {
+        let mut out = create_file(self.destination, relative)?;
+        if let Err(e) = self.copy(Some(name), reader, &mut out) {
+            // The file was created by this entry, so nothing else is lost.
+            drop(out);
+            let _ = fs::remove_file(self.destination.join(relative));
+            return Err(e);
+        }
+        self.progress.files_extracted += 1;
+        if self.progress.files_extracted - self.reported_files >= PROGRESS_FILES {
+            self.report(Some(name.to_string()));
+        }
+        Ok(())
+    }
+
+    // Progress is reported only for bytes written under `name`.
+    fn copy(
+        &mut self,
+        name: Option<&str>,
+        reader: &mut dyn Read,
+        out: &mut dyn Write,
+    ) -> Result<(), EntryError> {
+        let mut buffer = vec![0u8; CHUNK];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+            self.decompressed += read as u64;
+            if self.decompressed > self.limit {
+                return Err(EntryError::TooLarge);
+            }
+            out.write_all(&buffer[..read])?;
+            let Some(name) = name else {
+                continue;
+            };
+            self.progress.bytes_extracted += read as u64;
+            if self.progress.bytes_extracted - self.reported_bytes >= PROGRESS_BYTES {
+                self.report(Some(name.to_string()));
+            }
+        }
+    }
+
+    // Entries of a solid 7z block are decoded back to back, so whatever an
+    // entry left unread is consumed before the next one starts.
+    fn discard(&mut self, name: &str, reader: &mut dyn Read) -> Result<(), String> {
+        match self.copy(None, reader, &mut io::sink()) {
+            Ok(()) => Ok(()),
+            Err(EntryError::Io(e)) => Err(format!("{}: {}", name, e)),
+            Err(EntryError::TooLarge) => Err(self.too_large()),
+        }
+    }
+}
+
+// Unpacks `archive` into `destination`, checking `stop` between entries.
+// Returns `None` when stopped, leaving what was already written in place.
+pub fn extract(
+    app: &AppHandle,
+    archive: &Path,
+    destination: &Path,
+    stop: impl Fn() -> bool,
+) -> Result<Option<ExtractSummary>, String> {
+    extract_with(archive, destination, stop, &|progress| {
+        let _ = app.emit("archive-progress", progress.clone());
+    })
+}
+
+fn extract_with(
+    archive: &Path,
+    destination: &Path,
+    stop: impl Fn() -> bool,
+    on_progress: &dyn Fn(&ExtractProgress),
+) -> Result<Option<ExtractSummary>, String> {
+    let listing = list(archive)?;
+    let extract_err = |e: String| format!("Failed to extract {}: {}", archive.display(), e);
+    let archive_bytes = fs::metadata(archive)
+        .map_err(|e| extract_err(e.to_string()))?
+        .len();
+    let limit = size_limit(archive_bytes);
+    // Declared sizes can lie, so the limit is also enforced while writing.
+    if listing.total_bytes > limit {
+        return Err(extract_err(format!(
+            "it declares {} bytes, more than the {} allowed for an archive of its size",
+            listing.total_bytes, limit
+        )));
+    }
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create {}: {}", destination.display(), e))?;
+    let mut extraction = Extraction {
+        on_progress,
+        destination,
+        progress: ExtractProgress {
+            archive: archive.to_string_lossy().to_string(),
+            bytes_extracted: 0,
+            total_bytes: listing.total_bytes,
+            files_extracted: 0,
+            total_files: listing.file_count,
+            file: None,
+        },
+        reported_bytes: 0,
+        reported_files: 0,
+        skipped: Vec::new(),
+        failed: Vec::new(),
+        claims: Claims::default(),
+        decompressed: 0,
+        limit,
+    };
+
+    let mut stopped = false;
+    match listing.format {
+        ArchiveFormat::Zip => {
+            let mut zip = zip_reader(archive)?;
+            for index in 0..zip.len() {
+                if stop() {
+                    stopped = true;
+                    break;
+                }
+                let name = zip
+                    .name_for_index(index)
+                    .map_or_else(|| format!("#{}", index), str::to_string);
+                let mut entry = match zip.by_index(index) {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        extraction.fail(&name, e);
+                        continue;
+                    }
+                };
+                let is_dir = entry.is_dir();
+                let is_symlink = entry.is_symlink();
+                extraction
+                    .write(&name, is_dir, is_symlink, &mut entry)
+                    .map_err(extract_err)?;
+            }
+        }
+        ArchiveFormat::SevenZ => {
+            let mut reader = SevenZReader::open(archive, Password::empty())
+                .map_err(|e| extract_err(e.to_string()))?;
+            let mut failure = None;
+            reader
+                .for_each_entries(|entry, data| {
+                    if stop() {
+                        stopped = true;
+                        return Ok(false);
+                    }
+                    if entry.is_anti_item {
+                        return Ok(true);
+                    }
+                    let written = extraction
+                        .write(&entry.name, entry.is_directory, is_7z_symlink(entry), data)
+                        .and_then(|()| extraction.discard(&entry.name, data));
+                    if let Err(e) = written {
+                        failure = Some(e);
+                        return Ok(false);
+                    }
+                    Ok(true)
+                })
+                .map_err(|e| extract_err(e.to_string()))?;
+            if let Some(failure) = failure {
+                return Err(extract_err(failure));
+            }
+        }
+        ArchiveFormat::Rar => return Err(rar_unsupported(archive)),
+    }
+    extraction.report(None);
+    if stopped {
+        return Ok(None);
+    }
+    Ok(Some(ExtractSummary {
+        destination: destination.to_string_lossy().to_string(),
+        files: extraction.progress.files_extracted,
+        bytes: extraction.progress.bytes_extracted,
+        skipped: extraction.skipped,
+        failed: extraction.failed,
+    }))
+}
+
+fn is_occupied(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
+}
+
+// Unpacks into a folder of the job's own beside `destination` and renames it
+// into place once complete, so concurrent jobs and stopped extractions never
+// leave a half-written pack where a finished one is expected. `unpack`
+// returns false when it was stopped. With `reuse`, an existing destination
+// is an earlier extraction of the same archive and is returned as is;
+// otherwise it is an error. An existing destination is never deleted.
+fn stage_into(
+    destination: &Path,
+    reuse: bool,
+    job_id: &str,
+    unpack: impl FnOnce(&Path) -> Result<bool, String>,
+) -> Result<Option<PathBuf>, String> {
+    let occupied = || -> Result<Option<PathBuf>, String> {
+        if reuse {
+            return Ok(Some(destination.to_path_buf()));
+        }
+        Err(format!(
+            "{} already exists and is not empty; remove it or choose another output directory",
+            destination.display()
+        ))
+    };
+    if is_occupied(destination) {
+        return occupied();
+    }
+    let name = destination
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let partial = destination.with_file_name(format!(".{}.{}.partial", name, job_id));
+    let _ = fs::remove_dir_all(&partial);
+
+    let unpacked = unpack(&partial);
+    if !matches!(unpacked, Ok(true)) {
+        let _ = fs::remove_dir_all(&partial);
+        return unpacked.map(|_| None);
+    }
+    // An empty folder left at the destination would make the rename fail on
+    // Windows; `remove_dir` only removes it while it is empty.
+    let _ = fs::remove_dir(destination);
+    match fs::rename(&partial, destination) {
+        Ok(()) => Ok(Some(destination.to_path_buf())),
+        Err(e) => {
+            let _ = fs::remove_dir_all(&partial);
+            if is_occupied(destination) {
+                return occupied();
+            }
+            Err(format!(
+                "Failed to move {} into place: {}",
+                destination.display(),
+                e
+            ))
+        }
+    }
+}
+
+// Unpacks an archive ingested as a pack and returns the folder to scan, or
+// `None` when `stop` interrupted it. With `output_dir` the pack lands beside
+// other downloads and an occupied folder is refused. Otherwise it goes under
+// the app data directory in a folder named after the archive's contents, so
+// different archives with one file name never share a folder and
+// re-ingesting the same archive reuses the earlier extraction.
+pub fn stage(
+    app: &AppHandle,
+    archive: &Path,
+    output_dir: Option<&str>,
+    job_id: &str,
+    stop: impl Fn() -> bool,
+) -> Result<Option<PathBuf>, String> {
+    let stem = archive
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| "archive".to_string());
+    let (destination, reuse) = match output_dir.filter(|dir| !dir.trim().is_empty()) {
+        Some(dir) => (Path::new(dir).join(&stem), false),
+        None => {
+            let digest = File::open(archive)
+                .and_then(|mut file| hashing::sha256_reader(&mut file, |_| {}))
+                .map_err(|e| format!("Failed to read {}: {}", archive.display(), e))?;
+            let dir = storage::data_path(app, EXTRACT_DIR)?;
+            (dir.join(format!("{}-{}", stem, &digest[..16])), true)
+        }
+    };
+    stage_into(&destination, reuse, job_id, |partial| {
+        Ok(extract(app, archive, partial, &stop)?.is_some())
+    })
+}
+
+#[tauri::command]
+pub async fn list_archive(path: String) -> Result<ArchiveListing, String> {
+    tauri::async_runtime::spawn_blocking(move || list(Path::new(&path)))
+        .await
+        .map_err(|e| format!("Failed to list archive: {}", e))?
+}
+
+#[tauri::command]
+pub async fn extract_archive(
+    app: AppHandle,
+    path: String,
+    destination: String,
+) -> Result<ExtractSummary, String> {
+    access::ensure_writable(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        extract(&app, Path::new(&path), Path::new(&destination), || false)?
+            .ok_or_else(|| "Extraction was stopped".to_string())
+    })
+    .await
+    .map_err(|e| format!("Failed to extract archive: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("archives-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn enclosed_rejects_parent_components() {
+        assert_eq!(enclosed("../evil.txt"), None);
+        assert_eq!(enclosed("a/../../evil.txt"), None);
+        assert_eq!(enclosed("a\\..\\..\\evil.txt"), None);
+    }
+
+    #[test]
+    fn enclosed_rejects_absolute_paths() {
+        assert_eq!(enclosed("/etc/passwd"), None);
+        assert_eq!(enclosed("\\etc\\passwd"), None);
+    }
+
+    #[test]
+    fn enclosed_keeps_normal_paths() {
+        assert_eq!(
+            enclosed("./Textures\\Rock.png"),
+            Some(PathBuf::from("Textures/Rock.png"))
+        );
+        assert_eq!(enclosed("./"), None);
+    }
+
+    #[test]
+    fn claims_reject_duplicate_files() {
+        let mut claims = Claims::default();
+        assert!(claims.claim(Path::new("a/b.txt"), false));
+        assert!(!claims.claim(Path::new("a/b.txt"), false));
+    }
+
+    #[test]
+    fn claims_reject_case_collisions() {
+        let mut claims = Claims::default();
+        assert!(claims.claim(Path::new("Textures/Rock.png"), false));
+        assert!(!claims.claim(Path::new("textures/rock.png"), false));
+        assert!(!claims.claim(Path::new("TEXTURES/Other.png"), false));
+        assert!(!claims.claim(Path::new("Textures/ROCK.PNG"), false));
+    }
+
+    #[test]
+    fn claims_reject_file_and_folder_with_one_name() {
+        let mut claims = Claims::default();
+        assert!(claims.claim(Path::new("a/b"), false));
+        assert!(!claims.claim(Path::new("a/B"), true));
+        assert!(!claims.claim(Path::new("a/b/c.txt"), false));
+    }
+
+    #[test]
+    fn claims_allow_repeated_folders() {
+        let mut claims = Claims::default();
+        assert!(claims.claim(Path::new("a"), true));
+        assert!(claims.claim(Path::new("a"), true));
+        assert!(claims.claim(Path::new("a/b.txt"), false));
+    }
+
+    #[test]
+    fn create_file_does_not_overwrite() {
+        let dir = scratch("overwrite");
+        create_file(&dir, Path::new("a/b.txt")).unwrap();
+        assert!(create_file(&dir, Path::new("a/b.txt")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_file_refuses_symlinks() {
+        let dir = scratch("symlink");
+        let outside = scratch("symlink-outside");
+        std::os::unix::fs::symlink(&outside, dir.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("target"), dir.join("file")).unwrap();
+        assert!(create_file(&dir, Path::new("link/evil.txt")).is_err());
+        assert!(create_file(&dir, Path::new("file")).is_err());
+        assert!(!outside.join("evil.txt").exists());
+        assert!(!outside.join("target").exists());
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[test]
+    fn staging_moves_a_finished_extraction_into_place() {
+        let dir = scratch("stage");
+        let destination = dir.join("Textures");
+        let staged = stage_into(&destination, false, "1", |partial| {
+            create_file(partial, Path::new("a.png")).map_err(|e| e.to_string())?;
+            Ok(true)
+        })
+        .unwrap();
+        assert_eq!(staged, Some(destination.clone()));
+        assert!(destination.join("a.png").is_file());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn staging_never_deletes_an_existing_folder() {
+        let dir = scratch("stage-existing");
+        let destination = dir.join("Textures");
+        create_file(&destination, Path::new("kept.png")).unwrap();
+
+        let reused = stage_into(&destination, true, "1", |_| panic!("extracted again"));
+        assert_eq!(reused.unwrap(), Some(destination.clone()));
+        assert!(stage_into(&destination, false, "1", |_| panic!("extracted again")).is_err());
+        assert!(destination.join("kept.png").is_file());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn staging_discards_only_its_own_stopped_extraction() {
+        let dir = scratch("stage-stopped");
+        let destination = dir.join("Textures");
+        let other = dir.join(".Textures.2.partial");
+        create_file(&other, Path::new("b.png")).unwrap();
+        let staged = stage_into(&destination, false, "1", |partial| {
+            create_file(partial, Path::new("a.png")).map_err(|e| e.to_string())?;
+            Ok(false)
+        })
+        .unwrap();
+        assert_eq!(staged, None);
+        assert!(!destination.exists());
+        assert!(!dir.join(".Textures.1.partial").exists());
+        assert!(other.join("b.png").is_file());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn staging_keeps_the_first_of_two_concurrent_extractions() {
+        let dir = scratch("stage-race");
+        let destination = dir.join("Textures");
+        let staged = stage_into(&destination, true, "1", |partial| {
+            create_file(partial, Path::new("mine.png")).map_err(|e| e.to_string())?;
+            // Another job finishes first.
+            create_file(&destination, Path::new("theirs.png")).map_err(|e| e.to_string())?;
+            Ok(true)
+        })
+        .unwrap();
+        assert_eq!(staged, Some(destination.clone()));
+        assert!(destination.join("theirs.png").is_file());
+        assert!(!destination.join("mine.png").exists());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn extraction<'a>(destination: &'a Path, limit: u64) -> Extraction<'a> {
+        Extraction {
+            on_progress: &|_| {},
+            destination,
+            progress: ExtractProgress {
+                archive: String::new(),
+                bytes_extracted: 0,
+                total_bytes: 0,
+                files_extracted: 0,
+                total_files: 0,
+                file: None,
+            },
+            reported_bytes: 0,
+            reported_files: 0,
+            skipped: Vec::new(),
+            failed: Vec::new(),
+            claims: Claims::default(),
+            decompressed: 0,
+            limit,
+        }
+    }
+
+    #[test]
+    fn size_limit_scales_with_the_archive() {
+        assert_eq!(size_limit(0), MIN_SIZE_LIMIT);
+        let archive = 100 * 1024 * 1024;
+        assert_eq!(size_limit(archive), archive * MAX_COMPRESSION_RATIO);
+        assert_eq!(size_limit(u64::MAX), MAX_EXTRACTED_BYTES);
+    }
+
+    #[test]
+    fn extraction_records_failed_entries_and_continues() {
+        let dir = scratch("failures");
+        let archive = dir.join("pack.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        zip.add_symlink("link", "/etc/passwd", options).unwrap();
+        for name in ["a.txt", "taken.txt", "c.txt"] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(name.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let destination = dir.join("out");
+        create_file(&destination, Path::new("taken.txt")).unwrap();
+        let summary = extract_with(&archive, &destination, || false, &|_| {})
+            .unwrap()
+            .unwrap();
+        let failed: Vec<&str> = summary.failed.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(failed, ["link", "taken.txt"]);
+        assert_eq!(summary.files, 2);
+        assert!(!destination.join("link").exists());
+        assert_eq!(fs::read(destination.join("c.txt")).unwrap(), b"c.txt");
+        assert_eq!(fs::read(destination.join("taken.txt")).unwrap(), b"");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn extraction_stops_past_the_size_limit() {
+        let dir = scratch("limit");
+        let mut extraction = extraction(&dir, 1000);
+        extraction
+            .write("small.bin", false, false, &mut &[0u8; 600][..])
+            .unwrap();
+        let result = extraction.write("large.bin", false, false, &mut &[0u8; 600][..]);
+        assert!(result.is_err());
+        assert!(dir.join("small.bin").is_file());
+        // The partly written file is removed.
+        assert!(!dir.join("large.bin").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn discarded_bytes_count_toward_the_limit() {
+        let dir = scratch("discard");
+        let mut extraction = extraction(&dir, 1000);
+        extraction
+            .write("../outside.bin", false, false, &mut &[][..])
+            .unwrap();
+        assert!(extraction
+            .discard("../outside.bin", &mut &[0u8; 1001][..])
+            .is_err());
+        assert_eq!(extraction.skipped, ["../outside.bin"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn symlink_modes_are_recognised() {
+        assert!(is_symlink_mode(0o120777));
+        assert!(!is_symlink_mode(0o100644));
+        assert!(!is_symlink_mode(0o040755));
+    }
+}
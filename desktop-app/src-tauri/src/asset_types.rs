@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::manifest::AssetManifest;
+
+pub const METADATA_KEY: &str = "asset_type";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetType {
+    Texture,
+    Material,
+    Mesh,
+    Animation,
+    Audio,
+    Vfx,
+    Script,
+    Font,
+    Shader,
+    Scene,
+    Other,
+}
+
+impl AssetType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AssetType::Texture => "texture",
+            AssetType::Material => "material",
+            AssetType::Mesh => "mesh",
+            AssetType::Animation => "animation",
+            AssetType::Audio => "audio",
+            AssetType::Vfx => "vfx",
+            AssetType::Script => "script",
+            AssetType::Font => "font",
+            AssetType::Shader => "shader",
+            AssetType::Scene => "scene",
+            AssetType::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        serde_json::from_value(Value::String(value.to_string())).ok()
+    }
+}
+
+const EXTENSIONS: &[(AssetType, &[&str])] = &[
+    (
+        AssetType::Texture,
+        &[
+            "png", "jpg", "jpeg", "tga", "tif", "tiff", "bmp", "exr", "hdr", "dds", "psd", "ktx",
+            "ktx2", "webp", "gif",
+        ],
+    ),
+    (
+        AssetType::Material,
+        &["mat", "mtl", "sbs", "sbsar", "vmat", "physicmaterial"],
+    ),
+    (
+        AssetType::Mesh,
+        &[
+            "fbx", "obj", "gltf", "glb", "dae", "3ds", "blend", "max", "ma", "mb", "stl", "ply",
+            "usd", "usda", "usdc", "usdz", "abc",
+        ],
+    ),
+    (
+        AssetType::Animation,
+        &["anim", "bvh", "controller", "overridecontroller"],
+    ),
+    (
+        AssetType::Audio,
+        &[
+            "wav", "mp3", "ogg", "flac", "aif", "aiff", "m4a", "opus", "wem", "bank",
+        ],
+    ),
+    (AssetType::Vfx, &["vfx", "pkfx", "efk", "efkefc"]),
+    (
+        AssetType::Script,
+        &[
+            "cs", "js", "ts", "gd", "lua", "py", "cpp", "c", "h", "hpp", "uc",
+        ],
+    ),
+    (AssetType::Font, &["ttf", "otf", "woff", "woff2", "fnt"]),
+    (
+        AssetType::Shader,
+        &[
+            "shader",
+            "hlsl",
+            "glsl",
+            "cginc",
+            "compute",
+            "shadergraph",
+            "vert",
+            "frag",
+            "usf",
+            "ush",
+            "gdshader",
+            "wgsl",
+        ],
+    ),
+    (
+        AssetType::Scene,
+        &["unity", "umap", "tscn", "escn", "scene"],
+    ),
+];
+
+// Unreal packages share one extension, so fall back to the community naming
+// prefixes to tell a texture from a static mesh.
+const UNREAL_PREFIXES: &[(AssetType, &[&str])] = &[
+    (AssetType::Texture, &["t_"]),
+    (AssetType::Material, &["m_", "mi_", "mf_"]),
+    (AssetType::Mesh, &["sm_", "sk_"]),
+    (AssetType::Animation, &["a_", "as_", "am_", "abp_", "bs_"]),
+    (AssetType::Audio, &["sw_", "sc_", "s_"]),
+    (AssetType::Vfx, &["ns_", "p_", "fx_"]),
+    (AssetType::Script, &["bp_", "wbp_"]),
+    (AssetType::Font, &["font_"]),
+];
+
+pub fn classify(relative_path: &str, file_type: &str) -> AssetType {
+    let extension = file_type.to_lowercase();
+    let normalized = relative_path.replace('\\', "/").to_lowercase();
+    let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+
+    if extension == "uasset" {
+        return UNREAL_PREFIXES
+            .iter()
+            .find(|(_, prefixes)| prefixes.iter().any(|p| file_name.starts_with(p)))
+            .map(|(asset_type, _)| *asset_type)
+            .unwrap_or(AssetType::Other);
+    }
+
+    let by_extension = EXTENSIONS
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+        .map(|(asset_type, _)| *asset_type)
+        .unwrap_or(AssetType::Other);
+
+    // Animation clips usually ship as FBX, distinguishable only by location or name.
+    if by_extension == AssetType::Mesh && looks_like_animation(&normalized, file_name) {
+        return AssetType::Animation;
+    }
+    by_extension
+}
+
+fn looks_like_animation(path: &str, file_name: &str) -> bool {
+    path.split('/')
+        .any(|segment| segment == "animations" || segment == "animation" || segment == "anims")
+        || file_name.starts_with("anim_")
+        || file_name.contains("@")
+}
+
+// Fills in `metadata.asset_type` for every asset in each manifest printed by the
+// ingestion tool, keeping any value the tool already set.
+pub fn annotate(manifests: &mut [AssetManifest]) {
+    for asset in manifests
+        .iter_mut()
+        .flat_map(|manifest| manifest.assets.iter_mut())
+    {
+        let asset_type = classify(&asset.relative_path, &asset.file_type);
+        asset
+            .metadata
+            .entry(METADATA_KEY.to_string())
+            .or_insert_with(|| asset_type.as_str().to_string());
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AssetTypeSummary {
+    asset_type: AssetType,
+    count: u64,
+    size_bytes: u64,
+}
+
+#[tauri::command]
+pub fn summarize_asset_types(manifest_json: String) -> Result<Vec<AssetTypeSummary>, String> {
+    let mut totals: BTreeMap<AssetType, (u64, u64)> = BTreeMap::new();
+
+    for manifest in serde_json::Deserializer::from_str(&manifest_json).into_iter::<Value>() {
+        let manifest = manifest.map_err(|e| format!("Invalid manifest JSON: {}", e))?;
+        let assets = manifest
+            .get("assets")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        for asset in assets {
+            let asset_type = asset
+                .pointer("/metadata/asset_type")
+                .and_then(Value::as_str)
+                .and_then(AssetType::parse)
+                .unwrap_or_else(|| {
+                    classify(
+                        asset["relative_path"].as_str().unwrap_or_default(),
+                        asset["file_type"].as_str().unwrap_or_default(),
+                    )
+                });
+            let entry = totals.entry(asset_type).or_default();
+            entry.0 += 1;
+            entry.1 += asset["size_bytes"].as_u64().unwrap_or(0);
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(|(asset_type, (count, size_bytes))| AssetTypeSummary {
+            asset_type,
+            count,
+            size_bytes,
+        })
+        .collect())
+}
@@ -0,0 +1,770 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::asset_types::{self, AssetType};
+use crate::catalog::{self, AssetRecord};
+use crate::manifest::{AssetFile, AssetManifest};
+use crate::memory::{self, Pool};
+use crate::{access, preview, runtime, storage};
+
+const AUDIO_CHECKS_FILE: &str = "audio-checks.json";
+const LOUDNESS_KEY: &str = "loudness_lufs";
+const TRUE_PEAK_KEY: &str = "true_peak_dbtp";
+const CLIPPED_KEY: &str = "clipped_samples";
+const WARNINGS_KEY: &str = "audio_warnings";
+const ANALYSIS_KEY: &str = "audio_analysis";
+// Normalized previews are built in memory, so larger files play unchanged.
+const MAX_PREVIEW_BYTES: u64 = 512 * 1024 * 1024;
+// Raw sample data read per block during analysis.
+const BLOCK_BYTES: usize = 1024 * 1024;
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+// A sample this close to full scale, held for `CLIP_RUN` samples in a row,
+// counts as clipped rather than a legitimate peak.
+const CLIP_LEVEL: f32 = 0.999;
+const CLIP_RUN: usize = 3;
+// Half-width, in input samples, of the interpolation kernel used to
+// estimate inter-sample peaks at four times the sample rate.
+const OVERSAMPLE_TAPS: i64 = 6;
+const OVERSAMPLE: usize = 4;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AudioChecks {
+    // Off by default: decoding every sample slows ingestion of large packs.
+    enabled: bool,
+    min_lufs: f64,
+    max_lufs: f64,
+    max_true_peak_dbtp: f64,
+    // Level that normalized previews are played at.
+    preview_lufs: f64,
+}
+
+impl Default for AudioChecks {
+    fn default() -> Self {
+        AudioChecks {
+            enabled: false,
+            min_lufs: -30.0,
+            max_lufs: -10.0,
+            max_true_peak_dbtp: -1.0,
+            preview_lufs: -20.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AudioWarning {
+    Silent,
+    TooQuiet,
+    TooLoud,
+    TruePeak,
+    Clipping,
+}
+
+impl AudioWarning {
+    fn as_str(self) -> &'static str {
+        match self {
+            AudioWarning::Silent => "silent",
+            AudioWarning::TooQuiet => "too_quiet",
+            AudioWarning::TooLoud => "too_loud",
+            AudioWarning::TruePeak => "true_peak",
+            AudioWarning::Clipping => "clipping",
+        }
+    }
+}
+
+struct WaveFormat {
+    tag: u16,
+    channels: usize,
+    sample_rate: u32,
+    bits: u16,
+}
+
+struct Analysis {
+    // `None` when every block falls below the absolute gate.
+    loudness: Option<f64>,
+    true_peak: f64,
+    clipped: usize,
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn decode_sample(bytes: &[u8], format: u16, bits: u16) -> Option<f32> {
+    let sample = match (format, bits) {
+        (WAVE_FORMAT_PCM, 8) => (f32::from(bytes[0]) - 128.0) / 128.0,
+        (WAVE_FORMAT_PCM, 16) => f32::from(i16::from_le_bytes([bytes[0], bytes[1]])) / 32768.0,
+        (WAVE_FORMAT_PCM, 24) => {
+            let value = i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8;
+            value as f32 / 8_388_608.0
+        }
+        (WAVE_FORMAT_PCM, 32) => {
+            i32::from_le_bytes(bytes[..4].try_into().ok()?) as f32 / 2_147_483_648.0
+        }
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => f32::from_le_bytes(bytes[..4].try_into().ok()?),
+        _ => return None,
+    };
+    Some(sample)
+}
+
+// Reads the samples of a RIFF WAVE file a block at a time, so files of any
+// length are analysed in constant memory. Compressed formats have no decoder
+// here, so only WAVE is supported.
+struct WaveReader {
+    file: BufReader<File>,
+    format: WaveFormat,
+    // Bytes of the data chunk not read yet; a truncated file simply ends
+    // early.
+    remaining: u64,
+    raw: Vec<u8>,
+}
+
+impl WaveReader {
+    fn open(path: &Path) -> Option<Self> {
+        let mut file = BufReader::new(File::open(path).ok()?);
+        let mut header = [0u8; 12];
+        file.read_exact(&mut header).ok()?;
+        if &header[..4] != b"RIFF" || &header[8..] != b"WAVE" {
+            return None;
+        }
+
+        let mut format = None;
+        loop {
+            let mut chunk = [0u8; 8];
+            file.read_exact(&mut chunk).ok()?;
+            let len = u64::from(le_u32(&chunk, 4)?);
+            if &chunk[..4] == b"data" {
+                return Some(WaveReader {
+                    file,
+                    format: format?,
+                    remaining: len,
+                    raw: Vec::new(),
+                });
+            }
+            let mut skip = len + (len & 1);
+            if &chunk[..4] == b"fmt " {
+                let mut body = vec![0; len.min(40) as usize];
+                file.read_exact(&mut body).ok()?;
+                skip -= body.len() as u64;
+                format = Some(parse_format(&body)?);
+            }
+            // Chunks are padded to an even length.
+            file.seek_relative(i64::try_from(skip).ok()?).ok()?;
+        }
+    }
+
+    // Decodes the next block of interleaved samples into `samples`, leaving
+    // it empty at the end of the data. `None` means the data is corrupt.
+    fn next_block(&mut self, samples: &mut Vec<f32>) -> Option<()> {
+        samples.clear();
+        let width = usize::from(self.format.bits / 8);
+        let frame = width * self.format.channels;
+        let want = self.remaining.min((BLOCK_BYTES / frame * frame) as u64);
+        self.raw.clear();
+        (&mut self.file)
+            .take(want)
+            .read_to_end(&mut self.raw)
+            .ok()?;
+        self.remaining -= self.raw.len() as u64;
+        if self.raw.len() < want as usize {
+            self.remaining = 0;
+        }
+        // A partial trailing frame is dropped.
+        for sample in self
+            .raw
+            .chunks_exact(frame)
+            .flat_map(|f| f.chunks_exact(width))
+        {
+            samples.push(decode_sample(sample, self.format.tag, self.format.bits)?);
+        }
+        Some(())
+    }
+}
+
+fn parse_format(body: &[u8]) -> Option<WaveFormat> {
+    let mut tag = le_u16(body, 0)?;
+    if tag == WAVE_FORMAT_EXTENSIBLE {
+        // The sub-format GUID starts with the real format tag.
+        tag = le_u16(body, 24)?;
+    }
+    let format = WaveFormat {
+        tag,
+        channels: usize::from(le_u16(body, 2)?),
+        sample_rate: le_u32(body, 4)?,
+        bits: le_u16(body, 14)?,
+    };
+    // Decoding a silent sample checks the tag and bit depth are supported.
+    let supported = decode_sample(&[0; 4], format.tag, format.bits).is_some();
+    (supported && format.channels > 0 && format.sample_rate > 0).then_some(format)
+}
+
+#[derive(Clone)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    // Previous two inputs and outputs.
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl Biquad {
+    fn new(b: [f64; 3], a: [f64; 2]) -> Self {
+        Biquad {
+            b,
+            a,
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn next(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+        y
+    }
+}
+
+// The two K-weighting stages of ITU-R BS.1770, derived for any sample rate
+// rather than the tabulated 48 kHz coefficients.
+fn k_weighting(sample_rate: u32) -> [Biquad; 2] {
+    let rate = f64::from(sample_rate);
+
+    let k = (PI * 1_681.974_450_955_533 / rate).tan();
+    let q = 0.707_175_236_955_419_6;
+    let vh = 10f64.powf(3.999_843_853_973_347 / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad::new(
+        [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+
+    let k = (PI * 38.135_470_876_024_44 / rate).tan();
+    let q = 0.500_327_037_323_877_3;
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad::new(
+        [1.0, -2.0, 1.0],
+        [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    );
+    [shelf, high_pass]
+}
+
+// Surround channels are weighted up and the LFE channel of a 5.1 file is
+// ignored, as BS.1770 specifies.
+fn channel_weight(index: usize, count: usize) -> f64 {
+    match (count, index) {
+        (6, 3) => 0.0,
+        (6, 4 | 5) => 1.41,
+        _ => 1.0,
+    }
+}
+
+fn to_lufs(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+// Integrated loudness over 400 ms blocks overlapping by 75%, built from the
+// power of each 100 ms segment, with the absolute (-70 LUFS) and relative
+// (-10 LU) gates. Clips shorter than one block are measured as a single
+// block.
+fn integrated_loudness(segment_power: &[f64], step: usize, len: usize) -> Option<f64> {
+    let blocks: Vec<f64> = if segment_power.len() < 4 {
+        vec![segment_power.iter().sum::<f64>() / len.max(1) as f64]
+    } else {
+        segment_power
+            .windows(4)
+            .map(|window| window.iter().sum::<f64>() / (4 * step) as f64)
+            .collect()
+    };
+
+    let audible: Vec<f64> = blocks
+        .into_iter()
+        .filter(|&power| power > 0.0 && to_lufs(power) > -70.0)
+        .collect();
+    if audible.is_empty() {
+        return None;
+    }
+    let mean = |powers: &[f64]| powers.iter().sum::<f64>() / powers.len() as f64;
+    let threshold = to_lufs(mean(&audible)) - 10.0;
+    let gated: Vec<f64> = audible
+        .iter()
+        .copied()
+        .filter(|&power| to_lufs(power) > threshold)
+        .collect();
+    Some(to_lufs(mean(&gated)))
+}
+
+fn interpolation_kernel() -> Vec<Vec<f64>> {
+    (1..OVERSAMPLE)
+        .map(|phase| {
+            let offset = phase as f64 / OVERSAMPLE as f64;
+            (1 - OVERSAMPLE_TAPS..=OVERSAMPLE_TAPS)
+                .map(|tap| {
+                    let t = offset - tap as f64;
+                    let sinc = if t == 0.0 {
+                        1.0
+                    } else {
+                        (PI * t).sin() / (PI * t)
+                    };
+                    let window = 0.5 + 0.5 * (PI * t / OVERSAMPLE_TAPS as f64).cos();
+                    sinc * window
+                })
+                .collect()
+        })
+        .collect()
+}
+
+struct ChannelMeter {
+    weight: f64,
+    filters: [Biquad; 2],
+    // The samples around the one whose inter-sample peaks are estimated
+    // next, which lags `OVERSAMPLE_TAPS` samples behind the input.
+    window: VecDeque<f64>,
+    clip_run: usize,
+}
+
+// Accumulates loudness, true peak and clipping one frame at a time.
+struct Meter {
+    channels: Vec<ChannelMeter>,
+    kernel: Vec<Vec<f64>>,
+    // Samples per 100 ms loudness segment.
+    step: usize,
+    segment_power: Vec<f64>,
+    segment_frames: usize,
+    frames: usize,
+    peak: f64,
+    clipped: usize,
+}
+
+impl Meter {
+    fn new(format: &WaveFormat) -> Self {
+        let taps = 2 * OVERSAMPLE_TAPS as usize;
+        Meter {
+            channels: (0..format.channels)
+                .map(|index| ChannelMeter {
+                    weight: channel_weight(index, format.channels),
+                    filters: k_weighting(format.sample_rate),
+                    window: VecDeque::from(vec![0.0; taps / 2 - 1]),
+                    clip_run: 0,
+                })
+                .collect(),
+            kernel: interpolation_kernel(),
+            step: (format.sample_rate / 10).max(1) as usize,
+            segment_power: vec![0.0],
+            segment_frames: 0,
+            frames: 0,
+            peak: 0.0,
+            clipped: 0,
+        }
+    }
+
+    fn push(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(self.channels.len()) {
+            if self.segment_frames == self.step {
+                self.segment_power.push(0.0);
+                self.segment_frames = 0;
+            }
+            let mut power = 0.0;
+            for (channel, &sample) in self.channels.iter_mut().zip(frame) {
+                let [shelf, high_pass] = &mut channel.filters;
+                let y = high_pass.next(shelf.next(f64::from(sample)));
+                power += channel.weight * y * y;
+
+                if sample.abs() >= CLIP_LEVEL {
+                    channel.clip_run += 1;
+                } else {
+                    if channel.clip_run >= CLIP_RUN {
+                        self.clipped += channel.clip_run;
+                    }
+                    channel.clip_run = 0;
+                }
+
+                self.peak = self.peak.max(f64::from(sample.abs()));
+                self.peak =
+                    self.peak
+                        .max(inter_sample_peak(channel, &self.kernel, f64::from(sample)));
+            }
+            *self.segment_power.last_mut().unwrap() += power;
+            self.segment_frames += 1;
+            self.frames += 1;
+        }
+    }
+
+    fn finish(mut self) -> Option<Analysis> {
+        if self.frames == 0 {
+            return None;
+        }
+        for channel in &mut self.channels {
+            // Trailing zeros let the last samples' peaks be estimated.
+            for _ in 0..OVERSAMPLE_TAPS {
+                self.peak = self.peak.max(inter_sample_peak(channel, &self.kernel, 0.0));
+            }
+            if channel.clip_run >= CLIP_RUN {
+                self.clipped += channel.clip_run;
+            }
+        }
+        Some(Analysis {
+            loudness: integrated_loudness(&self.segment_power, self.step, self.frames),
+            true_peak: 20.0 * self.peak.max(f64::MIN_POSITIVE).log10(),
+            clipped: self.clipped,
+        })
+    }
+}
+
+// Adds `sample` to the channel's window and returns the largest value
+// interpolated between the window's middle sample and the next, or zero
+// while the window is still filling.
+fn inter_sample_peak(channel: &mut ChannelMeter, kernel: &[Vec<f64>], sample: f64) -> f64 {
+    channel.window.push_back(sample);
+    if channel.window.len() < 2 * OVERSAMPLE_TAPS as usize {
+        return 0.0;
+    }
+    let peak = kernel
+        .iter()
+        .map(|phase| {
+            phase
+                .iter()
+                .zip(&channel.window)
+                .map(|(weight, sample)| weight * sample)
+                .sum::<f64>()
+                .abs()
+        })
+        .fold(0.0, f64::max);
+    channel.window.pop_front();
+    peak
+}
+
+fn analyze(path: &Path) -> Option<Analysis> {
+    let mut reader = WaveReader::open(path)?;
+    let mut meter = Meter::new(&reader.format);
+    let mut samples = Vec::new();
+    loop {
+        reader.next_block(&mut samples)?;
+        if samples.is_empty() {
+            return meter.finish();
+        }
+        meter.push(&samples);
+    }
+}
+
+// The raw file plus 32-bit samples decoded from the common 16-bit PCM.
+fn decoded_size(path: &Path) -> u64 {
+    fs::metadata(path).map_or(0, |metadata| metadata.len() * 3)
+}
+
+// Streams the file a second time, writing every sample as a 32-bit float
+// scaled by `gain`.
+fn encode_float_wave(path: &Path, gain: f32) -> Option<Vec<u8>> {
+    let mut reader = WaveReader::open(path)?;
+    let channels = reader.format.channels as u16;
+    let sample_rate = reader.format.sample_rate;
+    let mut out = Vec::new();
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&WAVE_FORMAT_IEEE_FLOAT.to_le_bytes());
+    out.extend_from_slice(&channels.to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&(sample_rate * u32::from(channels) * 4).to_le_bytes());
+    out.extend_from_slice(&(channels * 4).to_le_bytes());
+    out.extend_from_slice(&32u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&0u32.to_le_bytes());
+
+    let mut samples = Vec::new();
+    loop {
+        reader.next_block(&mut samples)?;
+        if samples.is_empty() {
+            break;
+        }
+        for sample in &samples {
+            out.extend_from_slice(&(sample * gain).to_le_bytes());
+        }
+    }
+    let data_len = u32::try_from(out.len() - 44).ok()?;
+    out[4..8].copy_from_slice(&(36 + data_len).to_le_bytes());
+    out[40..44].copy_from_slice(&data_len.to_le_bytes());
+    Some(out)
+}
+
+// Re-encodes a WAV file with its loudness moved to `target_lufs` (or the
+// configured preview level), reducing the gain if that would push the true
+// peak above the configured limit. Returns `None` for unsupported or silent
+// files, which are played unchanged.
+pub fn normalized_wave(app: &AppHandle, path: &Path, target_lufs: Option<f64>) -> Option<Vec<u8>> {
+    if fs::metadata(path).ok()?.len() > MAX_PREVIEW_BYTES {
+        return None;
+    }
+    let settings: AudioChecks = storage::load_json(app, AUDIO_CHECKS_FILE);
+    let target_lufs = target_lufs.unwrap_or(settings.preview_lufs);
+    let ceiling_dbtp = settings.max_true_peak_dbtp;
+    let _held = memory::reserve(app, Pool::PreviewBuffers, decoded_size(path));
+    let analysis = analyze(path)?;
+    let gain_db = (target_lufs - analysis.loudness?).min(ceiling_dbtp - analysis.true_peak);
+    encode_float_wave(path, 10f64.powf(gain_db / 20.0) as f32)
+}
+
+fn warnings(analysis: &Analysis, settings: &AudioChecks) -> Vec<AudioWarning> {
+    let mut warnings = Vec::new();
+    match analysis.loudness {
+        None => warnings.push(AudioWarning::Silent),
+        Some(lufs) if lufs < settings.min_lufs => warnings.push(AudioWarning::TooQuiet),
+        Some(lufs) if lufs > settings.max_lufs => warnings.push(AudioWarning::TooLoud),
+        Some(_) => {}
+    }
+    if analysis.true_peak > settings.max_true_peak_dbtp {
+        warnings.push(AudioWarning::TruePeak);
+    }
+    if analysis.clipped > 0 {
+        warnings.push(AudioWarning::Clipping);
+    }
+    warnings
+}
+
+fn is_wave(asset: &AssetFile) -> bool {
+    asset.file_type.eq_ignore_ascii_case("wav")
+}
+
+fn is_audio(asset: &AssetFile) -> bool {
+    asset_types::classify(&asset.relative_path, &asset.file_type) == AssetType::Audio
+}
+
+// Stores the measurements in each WAV asset's metadata, plus a
+// comma-separated `audio_warnings` entry for files outside the configured
+// ranges. Audio that could not be measured gets an `audio_analysis` entry
+// saying why, so it is not mistaken for a file that passed.
+pub fn annotate(app: &AppHandle, manifests: &mut [AssetManifest]) {
+    let settings: AudioChecks = storage::load_json(app, AUDIO_CHECKS_FILE);
+    if !settings.enabled {
+        return;
+    }
+    let paths: Vec<PathBuf> = manifests
+        .iter()
+        .flat_map(|manifest| {
+            let root = Path::new(&manifest.root_path);
+            manifest
+                .assets
+                .iter()
+                .filter(|asset| is_wave(asset))
+                .map(move |asset| root.join(&asset.relative_path))
+        })
+        .collect();
+    let results = runtime::parallel_map(app, &paths, |path| {
+        // The raw block plus its samples decoded to 32-bit floats.
+        let _held = memory::reserve(app, Pool::AudioAnalysis, BLOCK_BYTES as u64 * 5);
+        analyze(path)
+    });
+
+    let mut results = results.into_iter();
+    let assets = manifests
+        .iter_mut()
+        .flat_map(|manifest| manifest.assets.iter_mut())
+        .filter(|asset| is_audio(asset));
+    for asset in assets {
+        if !is_wave(asset) {
+            asset
+                .metadata
+                .insert(ANALYSIS_KEY.to_string(), "unsupported_format".to_string());
+            continue;
+        }
+        match results.next().flatten() {
+            Some(analysis) => record(asset, &analysis, &settings),
+            None => {
+                asset
+                    .metadata
+                    .insert(ANALYSIS_KEY.to_string(), "unreadable".to_string());
+            }
+        }
+    }
+}
+
+fn record(asset: &mut AssetFile, analysis: &Analysis, settings: &AudioChecks) {
+    if let Some(lufs) = analysis.loudness {
+        asset
+            .metadata
+            .insert(LOUDNESS_KEY.to_string(), format!("{:.1}", lufs));
+    }
+    asset.metadata.insert(
+        TRUE_PEAK_KEY.to_string(),
+        format!("{:.1}", analysis.true_peak),
+    );
+    asset
+        .metadata
+        .insert(CLIPPED_KEY.to_string(), analysis.clipped.to_string());
+
+    let warnings = warnings(analysis, settings);
+    if !warnings.is_empty() {
+        let joined = warnings
+            .iter()
+            .map(|warning| warning.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        asset.metadata.insert(WARNINGS_KEY.to_string(), joined);
+    }
+}
+
+#[tauri::command]
+pub fn get_audio_checks(app: AppHandle) -> AudioChecks {
+    storage::load_json(&app, AUDIO_CHECKS_FILE)
+}
+
+#[tauri::command]
+pub fn set_audio_checks(app: AppHandle, settings: AudioChecks) -> Result<AudioChecks, String> {
+    access::ensure_writable(&app)?;
+    if settings.min_lufs >= settings.max_lufs {
+        return Err("The minimum loudness must be below the maximum".to_string());
+    }
+    if !(-70.0..0.0).contains(&settings.preview_lufs) {
+        return Err("The preview loudness must be between -70 and 0 LUFS".to_string());
+    }
+    storage::save_json(&app, AUDIO_CHECKS_FILE, &settings)?;
+    // Cached previews were normalized to the old level.
+    preview::shrink_cache(&app, u64::MAX);
+    Ok(settings)
+}
+
+#[tauri::command]
+pub fn list_flagged_audio(
+    app: AppHandle,
+    warning: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<AssetRecord>, String> {
+    catalog::list_flagged(&app, WARNINGS_KEY, warning.as_deref(), limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wave_file(name: &str, channels: u16, frames: &[Vec<i16>]) -> PathBuf {
+        let data: Vec<u8> = frames
+            .iter()
+            .flatten()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&48_000u32.to_le_bytes());
+        bytes.extend_from_slice(&(48_000 * u32::from(channels) * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&data);
+
+        let path = std::env::temp_dir().join(format!("audio-{}-{}.wav", name, std::process::id()));
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    fn sine(channels: usize, seconds: f64, amplitude: f64) -> Vec<Vec<i16>> {
+        (0..(48_000.0 * seconds) as usize)
+            .map(|i| {
+                let value = amplitude * (2.0 * PI * 997.0 * i as f64 / 48_000.0).sin();
+                vec![(value * 32767.0) as i16; channels]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn measures_a_sine_tone() {
+        let path = wave_file("sine", 1, &sine(1, 3.0, 0.1));
+        let analysis = analyze(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        // A 997 Hz tone at -20 dBFS reads about -23 LUFS on one channel.
+        assert!((analysis.loudness.unwrap() + 23.0).abs() < 0.2);
+        assert!((analysis.true_peak + 20.0).abs() < 0.2);
+        assert_eq!(analysis.clipped, 0);
+    }
+
+    #[test]
+    fn streams_files_larger_than_one_block() {
+        // Stereo 16-bit at 48 kHz is 192 kB per second.
+        let path = wave_file("long", 2, &sine(2, 8.0, 0.1));
+        assert!(fs::metadata(&path).unwrap().len() > BLOCK_BYTES as u64);
+        let analysis = analyze(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        // Two identical channels add 3 dB over the mono tone.
+        assert!((analysis.loudness.unwrap() + 20.0).abs() < 0.2);
+        assert!((analysis.true_peak + 20.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn counts_clipped_runs_and_silence() {
+        let mut frames = vec![vec![0i16]; 4_800];
+        frames.extend(vec![vec![i16::MAX]; 10]);
+        frames.push(vec![0]);
+        frames.extend(vec![vec![i16::MIN]; 2]);
+        let path = wave_file("clipped", 1, &frames);
+        let analysis = analyze(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(analysis.clipped, 10);
+        assert!(analysis.true_peak > -0.1);
+
+        let path = wave_file("silent", 1, &vec![vec![0i16]; 48_000]);
+        let analysis = analyze(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(analysis.loudness, None);
+    }
+
+    #[test]
+    fn rejects_unsupported_and_truncated_files() {
+        let path = wave_file("empty", 1, &[]);
+        assert!(analyze(&path).is_none());
+
+        let truncated = wave_file("truncated", 2, &sine(2, 1.0, 0.5));
+        let mut bytes = fs::read(&truncated).unwrap();
+        fs::remove_file(&truncated).unwrap();
+        bytes.truncate(bytes.len() / 2 + 1);
+        fs::write(&path, &bytes).unwrap();
+        assert!(analyze(&path).unwrap().loudness.is_some());
+
+        bytes.truncate(30);
+        fs::write(&path, &bytes).unwrap();
+        assert!(analyze(&path).is_none());
+
+        fs::write(&path, b"ID3\x03\x00\x00\x00\x00\x00\x00").unwrap();
+        assert!(analyze(&path).is_none());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn normalizes_preview_gain() {
+        let path = wave_file("preview", 1, &sine(1, 1.0, 0.1));
+        let encoded = encode_float_wave(&path, 2.0).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(&encoded[..4], b"RIFF");
+        assert_eq!(le_u32(&encoded, 40).unwrap() as usize, 48_000 * 4);
+        let peak = encoded[44..]
+            .chunks_exact(4)
+            .map(|sample| f32::from_le_bytes(sample.try_into().unwrap()).abs())
+            .fold(0.0, f32::max);
+        assert!((peak - 0.2).abs() < 0.001);
+    }
+}
@@ -0,0 +1,831 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+const HEAD_BYTES: u64 = 64 * 1024;
+// Largest MP4 `moov` or ASF header read into memory.
+const MAX_HEADER_BYTES: u64 = 16 * 1024 * 1024;
+// Top-level MP4 boxes walked while looking for `moov`.
+const MAX_TOP_LEVEL_BOXES: u32 = 1024;
+// ADTS frames sampled to estimate the length of an AAC stream.
+const ADTS_SAMPLE_FRAMES: u64 = 200;
+
+const ASF_HEADER: [u8; 16] = [
+    0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11, 0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C,
+];
+const ASF_FILE_PROPERTIES: [u8; 16] = [
+    0xA1, 0xDC, 0xAB, 0x8C, 0x47, 0xA9, 0xCF, 0x11, 0x8E, 0xE4, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65,
+];
+const ASF_STREAM_PROPERTIES: [u8; 16] = [
+    0x91, 0x07, 0xDC, 0xB7, 0xB7, 0xA9, 0xCF, 0x11, 0x8E, 0xE6, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65,
+];
+const ASF_AUDIO_MEDIA: [u8; 16] = [
+    0x40, 0x9E, 0x69, 0xF8, 0x4D, 0x5B, 0xCF, 0x11, 0xA8, 0xFD, 0x00, 0x80, 0x5F, 0x5C, 0x44, 0x2B,
+];
+
+struct AudioInfo {
+    seconds: f64,
+    sample_rate: u32,
+    channels: u16,
+    // Formats without a stated bitrate get the file's average.
+    bitrate: Option<u64>,
+}
+
+impl AudioInfo {
+    fn into_metadata(self, size_bytes: u64) -> BTreeMap<String, String> {
+        let bitrate = self.bitrate.unwrap_or_else(|| {
+            if self.seconds > 0.0 {
+                (size_bytes as f64 * 8.0 / self.seconds) as u64
+            } else {
+                0
+            }
+        });
+        let mut metadata = BTreeMap::new();
+        metadata.insert("duration".to_string(), format!("{:.2}s", self.seconds));
+        metadata.insert("sample_rate".to_string(), self.sample_rate.to_string());
+        metadata.insert("bitrate".to_string(), bitrate.to_string());
+        metadata.insert("channels".to_string(), self.channels.to_string());
+        metadata
+    }
+}
+
+// Duration, sample rate, bitrate and channels for the audio formats the
+// Python scanner read through mutagen. Only headers are read, plus the last
+// page of an Ogg file, so long recordings stay cheap.
+pub fn read(path: &Path, file_type: &str) -> Option<BTreeMap<String, String>> {
+    let mut file = File::open(path).ok()?;
+    let size_bytes = file.metadata().ok()?.len();
+    let info = match file_type {
+        "wav" => wave(&mut file)?,
+        "flac" => flac(&mut file)?,
+        "ogg" => ogg(&mut file, size_bytes)?,
+        "mp3" => mp3(&mut file, size_bytes)?,
+        "m4a" => mp4(&mut file, size_bytes)?,
+        // Raw ADTS streams and MP4 containers both use the extension.
+        "aac" => mp4(&mut file, size_bytes).or_else(|| adts(&mut file, size_bytes))?,
+        "wma" => asf(&mut file)?,
+        _ => return None,
+    };
+    Some(info.into_metadata(size_bytes))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u64(bytes: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(bytes.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(bytes: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(bytes.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn read_at(file: &mut File, offset: u64, len: u64) -> Option<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    let mut bytes = Vec::new();
+    file.take(len).read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+// Length of a leading ID3v2 tag, which MP3, AAC and FLAC files may carry.
+fn id3_len(head: &[u8]) -> usize {
+    if head.len() < 10 || &head[..3] != b"ID3" {
+        return 0;
+    }
+    let size = head[6..10]
+        .iter()
+        .fold(0usize, |size, byte| (size << 7) | usize::from(byte & 0x7f));
+    let footer = if head[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + size + footer
+}
+
+// Reads only the RIFF chunk headers.
+fn wave(file: &mut File) -> Option<AudioInfo> {
+    let mut header = [0u8; 12];
+    file.read_exact(&mut header).ok()?;
+    if &header[..4] != b"RIFF" || &header[8..] != b"WAVE" {
+        return None;
+    }
+    let mut format = None;
+    let mut data_len = None;
+    let mut chunk = [0u8; 8];
+    while file.read_exact(&mut chunk).is_ok() {
+        let len = u32::from_le_bytes(chunk[4..].try_into().ok()?);
+        match &chunk[..4] {
+            b"fmt " if len >= 16 => {
+                let mut fmt = [0u8; 16];
+                file.read_exact(&mut fmt).ok()?;
+                format = Some((
+                    u16::from_le_bytes([fmt[2], fmt[3]]),
+                    u32::from_le_bytes(fmt[4..8].try_into().ok()?),
+                    u16::from_le_bytes([fmt[14], fmt[15]]),
+                ));
+                file.seek(SeekFrom::Current(i64::from(len) - 16 + i64::from(len & 1)))
+                    .ok()?;
+            }
+            b"data" => {
+                data_len = Some(len);
+                break;
+            }
+            _ => {
+                // Chunks are padded to an even length.
+                file.seek(SeekFrom::Current(i64::from(len) + i64::from(len & 1)))
+                    .ok()?;
+            }
+        }
+    }
+    let (channels, sample_rate, bits) = format?;
+    let frame_bytes = u64::from(channels) * u64::from(bits).div_ceil(8);
+    if frame_bytes == 0 || sample_rate == 0 {
+        return None;
+    }
+    let frames = u64::from(data_len?) / frame_bytes;
+    Some(AudioInfo {
+        seconds: frames as f64 / f64::from(sample_rate),
+        sample_rate,
+        channels,
+        bitrate: Some(u64::from(sample_rate) * u64::from(channels) * u64::from(bits)),
+    })
+}
+
+// The STREAMINFO block always comes first and holds the total sample count.
+fn flac(file: &mut File) -> Option<AudioInfo> {
+    let head = read_at(file, 0, HEAD_BYTES)?;
+    let bytes = read_at(file, id3_len(&head) as u64, 42)?;
+    if bytes.get(..4)? != b"fLaC" || bytes.get(4)? & 0x7f != 0 {
+        return None;
+    }
+    let info = bytes.get(8..42)?;
+    let sample_rate =
+        (u32::from(info[10]) << 12) | (u32::from(info[11]) << 4) | (u32::from(info[12]) >> 4);
+    let channels = u16::from((info[12] >> 1) & 0x07) + 1;
+    let samples = (u64::from(info[13] & 0x0f) << 32) | u64::from(be_u32(info, 14)?);
+    if sample_rate == 0 {
+        return None;
+    }
+    Some(AudioInfo {
+        seconds: samples as f64 / f64::from(sample_rate),
+        sample_rate,
+        channels,
+        bitrate: None,
+    })
+}
+
+// The first page identifies the codec; the granule position of the last page
+// is the stream's length in samples.
+fn ogg(file: &mut File, size_bytes: u64) -> Option<AudioInfo> {
+    let head = read_at(file, 0, HEAD_BYTES)?;
+    if head.get(..4)? != b"OggS" {
+        return None;
+    }
+    let packet = head.get(27 + usize::from(*head.get(26)?)..)?;
+
+    let tail_start = size_bytes.saturating_sub(HEAD_BYTES);
+    let tail = read_at(file, tail_start, HEAD_BYTES)?;
+    let last_page = (0..tail.len().saturating_sub(14))
+        .rev()
+        .find(|&at| &tail[at..at + 4] == b"OggS" && tail[at + 4] == 0)?;
+    let granule = le_u64(&tail, last_page + 6)?;
+
+    if packet.starts_with(b"\x01vorbis") {
+        let sample_rate = le_u32(packet, 12)?;
+        let nominal = le_u32(packet, 20)? as i32;
+        if sample_rate == 0 {
+            return None;
+        }
+        Some(AudioInfo {
+            seconds: granule as f64 / f64::from(sample_rate),
+            sample_rate,
+            channels: u16::from(*packet.get(11)?),
+            bitrate: (nominal > 0).then_some(nominal as u64),
+        })
+    } else if packet.starts_with(b"OpusHead") {
+        // Opus always decodes at 48 kHz, whatever rate the source had.
+        let pre_skip = u64::from(le_u16(packet, 10)?);
+        Some(AudioInfo {
+            seconds: granule.saturating_sub(pre_skip) as f64 / 48_000.0,
+            sample_rate: 48_000,
+            channels: u16::from(*packet.get(9)?),
+            bitrate: None,
+        })
+    } else {
+        None
+    }
+}
+
+const MP3_BITRATES: [[u32; 15]; 5] = [
+    // MPEG-1 layers I, II and III.
+    [
+        0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+    ],
+    [
+        0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+    ],
+    [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ],
+    // MPEG-2 and 2.5 layer I, then layers II and III.
+    [
+        0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+    ],
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+];
+
+struct Mp3Frame {
+    mpeg1: bool,
+    layer: u8,
+    bitrate: u32,
+    sample_rate: u32,
+    channels: u16,
+}
+
+fn mp3_frame(header: &[u8]) -> Option<Mp3Frame> {
+    let [sync, flags, rates, mode] = header.get(..4)?.try_into().ok()?;
+    if sync != 0xff || flags & 0xe0 != 0xe0 {
+        return None;
+    }
+    let version = (flags >> 3) & 0x03;
+    let layer = 4 - ((flags >> 1) & 0x03);
+    let bitrate_index = usize::from(rates >> 4);
+    let rate_index = usize::from((rates >> 2) & 0x03);
+    if version == 1 || layer == 4 || bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+        return None;
+    }
+    let mpeg1 = version == 3;
+    let table = match (mpeg1, layer) {
+        (true, layer) => usize::from(layer - 1),
+        (false, 1) => 3,
+        (false, _) => 4,
+    };
+    let divisor = match version {
+        3 => 1,
+        2 => 2,
+        _ => 4,
+    };
+    Some(Mp3Frame {
+        mpeg1,
+        layer,
+        bitrate: MP3_BITRATES[table][bitrate_index] * 1000,
+        sample_rate: [44_100, 48_000, 32_000][rate_index] / divisor,
+        channels: if mode >> 6 == 3 { 1 } else { 2 },
+    })
+}
+
+// Uses the frame count from a Xing, Info or VBRI header when there is one,
+// and otherwise treats the stream as constant bitrate.
+fn mp3(file: &mut File, size_bytes: u64) -> Option<AudioInfo> {
+    let head = read_at(file, 0, HEAD_BYTES)?;
+    let start = id3_len(&head);
+    let head = if start + 4 > head.len() {
+        read_at(file, start as u64, HEAD_BYTES)?
+    } else {
+        head[start..].to_vec()
+    };
+    let (at, frame) = (0..head.len().saturating_sub(4))
+        .find_map(|at| mp3_frame(&head[at..]).map(|frame| (at, frame)))?;
+    let audio_start = (start + at) as u64;
+
+    let samples_per_frame: u64 = match (frame.layer, frame.mpeg1) {
+        (1, _) => 384,
+        (3, false) => 576,
+        _ => 1152,
+    };
+    let side_info = match (frame.mpeg1, frame.channels) {
+        (true, 1) | (false, 2) => 17,
+        (true, _) => 32,
+        (false, _) => 9,
+    };
+    let xing = at + 4 + side_info;
+    let frames = match head.get(xing..xing + 4) {
+        Some(b"Xing") | Some(b"Info") if be_u32(&head, xing + 4)? & 0x01 != 0 => {
+            Some(be_u32(&head, xing + 8)?)
+        }
+        _ if head.get(at + 36..at + 40) == Some(&b"VBRI"[..]) => Some(be_u32(&head, at + 36 + 14)?),
+        _ => None,
+    };
+
+    let audio_bytes = size_bytes.saturating_sub(audio_start);
+    let (seconds, bitrate) = match frames {
+        Some(frames) => {
+            let seconds =
+                u64::from(frames) as f64 * samples_per_frame as f64 / f64::from(frame.sample_rate);
+            let bitrate = if seconds > 0.0 {
+                (audio_bytes as f64 * 8.0 / seconds) as u64
+            } else {
+                u64::from(frame.bitrate)
+            };
+            (seconds, bitrate)
+        }
+        None => (
+            audio_bytes as f64 * 8.0 / f64::from(frame.bitrate),
+            u64::from(frame.bitrate),
+        ),
+    };
+    Some(AudioInfo {
+        seconds,
+        sample_rate: frame.sample_rate,
+        channels: frame.channels,
+        bitrate: Some(bitrate),
+    })
+}
+
+// A raw AAC stream has no length field, so it is estimated from the average
+// size of the first frames, as mutagen does.
+fn adts(file: &mut File, size_bytes: u64) -> Option<AudioInfo> {
+    const RATES: [u32; 13] = [
+        96_000, 88_200, 64_000, 48_000, 44_100, 32_000, 24_000, 22_050, 16_000, 12_000, 11_025,
+        8_000, 7_350,
+    ];
+    let head = read_at(file, 0, HEAD_BYTES)?;
+    let start = id3_len(&head) as u64;
+    let mut offset = start;
+    let mut frames = 0u64;
+    let mut samples = 0u64;
+    let mut format = None;
+    while frames < ADTS_SAMPLE_FRAMES {
+        let Some(header) = read_at(file, offset, 7).filter(|header| header.len() == 7) else {
+            break;
+        };
+        if header[0] != 0xff || header[1] & 0xf6 != 0xf0 {
+            break;
+        }
+        let sample_rate = *RATES.get(usize::from((header[2] >> 2) & 0x0f))?;
+        let channels = u16::from(((header[2] & 0x01) << 2) | (header[3] >> 6));
+        let length = (u64::from(header[3] & 0x03) << 11)
+            | (u64::from(header[4]) << 3)
+            | (u64::from(header[5]) >> 5);
+        if length < 7 {
+            break;
+        }
+        format.get_or_insert((sample_rate, channels));
+        samples += 1024 * (u64::from(header[6] & 0x03) + 1);
+        frames += 1;
+        offset += length;
+    }
+    let (sample_rate, channels) = format?;
+    let sampled_bytes = offset - start;
+    let audio_bytes = size_bytes.saturating_sub(start);
+    let seconds =
+        samples as f64 / f64::from(sample_rate) * audio_bytes as f64 / sampled_bytes as f64;
+    Some(AudioInfo {
+        seconds,
+        sample_rate,
+        channels,
+        bitrate: None,
+    })
+}
+
+// Box bodies directly inside `data`, as (type, body) pairs.
+fn mp4_boxes(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut at = 0;
+    std::iter::from_fn(move || {
+        let kind = data.get(at + 4..at + 8)?;
+        let (header, size) = match be_u32(data, at)? {
+            0 => (8, data.len() - at),
+            1 => (16, usize::try_from(be_u64(data, at + 8)?).ok()?),
+            size => (8, usize::try_from(size).ok()?),
+        };
+        if size < header {
+            return None;
+        }
+        let end = at.checked_add(size)?;
+        let body = data.get(at + header..end)?;
+        at = end;
+        Some((kind, body))
+    })
+}
+
+fn mp4_child<'a>(data: &'a [u8], path: &[&[u8; 4]]) -> Option<&'a [u8]> {
+    path.iter().try_fold(data, |data, name| {
+        mp4_boxes(data)
+            .find(|(kind, _)| *kind == *name)
+            .map(|(_, body)| body)
+    })
+}
+
+// Reads the `moov` box, wherever it sits among the top-level boxes, and
+// takes the format of the first sound track.
+fn mp4(file: &mut File, size_bytes: u64) -> Option<AudioInfo> {
+    let mut offset = 0;
+    let mut boxes = 0;
+    let moov = loop {
+        let header = read_at(file, offset, 16)?;
+        let (header_len, size) = match be_u32(&header, 0)? {
+            0 => (8, size_bytes.checked_sub(offset)?),
+            1 => (16, be_u64(&header, 8)?),
+            size => (8, u64::from(size)),
+        };
+        if size < header_len || (offset == 0 && header.get(4..8)? != b"ftyp") {
+            return None;
+        }
+        if header.get(4..8)? == b"moov" {
+            if size > MAX_HEADER_BYTES {
+                return None;
+            }
+            break read_at(file, offset, size)?;
+        }
+        // Sizes come from the file, so the walk must move forward, stay
+        // inside it and give up after a bounded number of boxes.
+        offset = offset.checked_add(size).filter(|&next| next < size_bytes)?;
+        boxes += 1;
+        if boxes >= MAX_TOP_LEVEL_BOXES {
+            return None;
+        }
+    };
+    let moov = mp4_child(&moov, &[b"moov"])?;
+
+    let mvhd = mp4_child(moov, &[b"mvhd"])?;
+    let (timescale, duration) = match mvhd.first()? {
+        1 => (be_u32(mvhd, 20)?, be_u64(mvhd, 24)?),
+        _ => (be_u32(mvhd, 12)?, u64::from(be_u32(mvhd, 16)?)),
+    };
+    if timescale == 0 {
+        return None;
+    }
+    let (sample_rate, channels) = mp4_boxes(moov)
+        .filter(|(kind, _)| *kind == b"trak")
+        .find_map(|(_, trak)| {
+            let mdia = mp4_child(trak, &[b"mdia"])?;
+            if mp4_child(mdia, &[b"hdlr"])?.get(8..12)? != b"soun" {
+                return None;
+            }
+            let stsd = mp4_child(mdia, &[b"minf", b"stbl", b"stsd"])?;
+            // Version and entry count come before the first sample entry.
+            let entry = stsd.get(16..)?;
+            let channels = u16::from_be_bytes(entry.get(16..18)?.try_into().ok()?);
+            Some((be_u32(entry, 24)? >> 16, channels))
+        })?;
+    Some(AudioInfo {
+        seconds: duration as f64 / f64::from(timescale),
+        sample_rate,
+        channels,
+        bitrate: None,
+    })
+}
+
+// The File Properties object holds the play duration in 100ns units,
+// including a preroll given in milliseconds.
+fn asf(file: &mut File) -> Option<AudioInfo> {
+    let header = read_at(file, 0, 30)?;
+    if header.get(..16)? != ASF_HEADER {
+        return None;
+    }
+    let size = le_u64(&header, 16)?;
+    if size > MAX_HEADER_BYTES {
+        return None;
+    }
+    let header = read_at(file, 0, size)?;
+    let mut at = 30;
+    let mut seconds = None;
+    let mut format = None;
+    while let (Some(guid), Some(size)) = (header.get(at..at + 16), le_u64(&header, at + 16)) {
+        // Every object is at least its own GUID and size.
+        if size < 24 {
+            return None;
+        }
+        let end = at.checked_add(usize::try_from(size).ok()?)?;
+        let object = header.get(at + 24..end)?;
+        if guid == ASF_FILE_PROPERTIES {
+            let play = le_u64(object, 40)? as f64 / 10_000_000.0;
+            let preroll = le_u64(object, 56)? as f64 / 1000.0;
+            seconds = Some((play - preroll).max(0.0));
+        } else if guid == ASF_STREAM_PROPERTIES && object.get(..16)? == ASF_AUDIO_MEDIA {
+            let wave = object.get(54..)?;
+            format.get_or_insert((le_u16(wave, 2)?, le_u32(wave, 4)?, le_u32(wave, 8)?));
+        }
+        at = end;
+    }
+    let (channels, sample_rate, bytes_per_second) = format?;
+    Some(AudioInfo {
+        seconds: seconds?,
+        sample_rate,
+        channels,
+        bitrate: Some(u64::from(bytes_per_second) * 8),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn metadata(name: &str, file_type: &str, bytes: &[u8]) -> Option<BTreeMap<String, String>> {
+        let path = std::env::temp_dir().join(format!(
+            "audio-metadata-{}-{}.{}",
+            name,
+            std::process::id(),
+            file_type
+        ));
+        fs::write(&path, bytes).unwrap();
+        let metadata = read(&path, file_type);
+        fs::remove_file(&path).unwrap();
+        metadata
+    }
+
+    fn expect(
+        metadata: Option<BTreeMap<String, String>>,
+        duration: &str,
+        rate: &str,
+        channels: &str,
+    ) -> BTreeMap<String, String> {
+        let metadata = metadata.expect("metadata");
+        assert_eq!(metadata["duration"], duration);
+        assert_eq!(metadata["sample_rate"], rate);
+        assert_eq!(metadata["channels"], channels);
+        metadata
+    }
+
+    fn mp4_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut bytes = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(kind);
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    fn asf_object(guid: &[u8; 16], body: &[u8]) -> Vec<u8> {
+        let mut bytes = guid.to_vec();
+        bytes.extend_from_slice(&((body.len() + 24) as u64).to_le_bytes());
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    fn asf_file(objects: &[u8]) -> Vec<u8> {
+        let mut bytes = ASF_HEADER.to_vec();
+        bytes.extend_from_slice(&((objects.len() + 30) as u64).to_le_bytes());
+        bytes.extend_from_slice(&[2, 0, 0, 0, 1, 2]);
+        bytes.extend_from_slice(objects);
+        bytes
+    }
+
+    // 22050 Hz, stereo, 16 bit, one second.
+    fn wave_file() -> Vec<u8> {
+        let mut fmt = vec![1, 0, 2, 0];
+        fmt.extend_from_slice(&22_050u32.to_le_bytes());
+        fmt.extend_from_slice(&88_200u32.to_le_bytes());
+        fmt.extend_from_slice(&[4, 0, 16, 0]);
+        let mut bytes = b"RIFF\x00\x00\x00\x00WAVEfmt \x10\x00\x00\x00".to_vec();
+        bytes.extend_from_slice(&fmt);
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&88_200u32.to_le_bytes());
+        bytes.resize(bytes.len() + 64, 0);
+        bytes
+    }
+
+    // 44100 Hz, stereo, 16 bit, 88200 samples.
+    fn flac_file() -> Vec<u8> {
+        let mut bytes = b"fLaC\x80\x00\x00\x22".to_vec();
+        let mut info = [0u8; 34];
+        info[10..14].copy_from_slice(&[0x0a, 0xc4, 0x42, 0xf0]);
+        info[14..18].copy_from_slice(&88_200u32.to_be_bytes());
+        bytes.extend_from_slice(&info);
+        bytes
+    }
+
+    // 48000 Hz mono Vorbis, 96 kbps nominal, 144000 samples.
+    fn vorbis_file() -> Vec<u8> {
+        let mut packet = b"\x01vorbis".to_vec();
+        packet.extend_from_slice(&0u32.to_le_bytes());
+        packet.push(1);
+        packet.extend_from_slice(&48_000u32.to_le_bytes());
+        packet.extend_from_slice(&0u32.to_le_bytes());
+        packet.extend_from_slice(&96_000u32.to_le_bytes());
+        packet.extend_from_slice(&[0u8; 6]);
+        let page = |granule: u64, body: &[u8]| {
+            let mut page = b"OggS\x00\x02".to_vec();
+            page.extend_from_slice(&granule.to_le_bytes());
+            page.extend_from_slice(&[0u8; 12]);
+            page.extend_from_slice(&[1, body.len() as u8]);
+            page.extend_from_slice(body);
+            page
+        };
+        let mut bytes = page(0, &packet);
+        bytes.extend_from_slice(&page(144_000, &[0u8; 16]));
+        bytes
+    }
+
+    // MPEG-1 layer III, 128 kbps, 44100 Hz, joint stereo, no VBR header.
+    fn cbr_mp3() -> Vec<u8> {
+        let mut bytes = vec![0xff, 0xfb, 0x90, 0x44];
+        bytes.resize(32_000, 0);
+        bytes
+    }
+
+    // MPEG-1 layer III, 44100 Hz, mono, behind an ID3 tag; the Xing tag
+    // follows 17 bytes of side information and counts 1000 frames.
+    fn xing_mp3() -> Vec<u8> {
+        let mut bytes = b"ID3\x03\x00\x00\x00\x00\x00\x0a".to_vec();
+        bytes.extend_from_slice(&[0u8; 10]);
+        bytes.extend_from_slice(&[0xff, 0xfb, 0x90, 0xc4]);
+        bytes.extend_from_slice(&[0u8; 17]);
+        bytes.extend_from_slice(b"Xing");
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend_from_slice(&1_000u32.to_be_bytes());
+        bytes.resize(4_000, 0);
+        bytes
+    }
+
+    // 44100 Hz stereo sound track, 4.5 seconds, `moov` after `mdat`.
+    fn m4a_file() -> Vec<u8> {
+        let mut mvhd = vec![0u8; 20];
+        mvhd[12..16].copy_from_slice(&1_000u32.to_be_bytes());
+        mvhd[16..20].copy_from_slice(&4_500u32.to_be_bytes());
+        let mut hdlr = vec![0u8; 12];
+        hdlr[8..12].copy_from_slice(b"soun");
+        let mut entry = vec![0u8; 28];
+        entry[16..18].copy_from_slice(&2u16.to_be_bytes());
+        entry[24..28].copy_from_slice(&(44_100u32 << 16).to_be_bytes());
+        let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stsd.extend_from_slice(&mp4_box(b"mp4a", &entry));
+        let stbl = mp4_box(b"stbl", &mp4_box(b"stsd", &stsd));
+        let mut mdia = mp4_box(b"hdlr", &hdlr);
+        mdia.extend_from_slice(&mp4_box(b"minf", &stbl));
+        let mut moov = mp4_box(b"mvhd", &mvhd);
+        moov.extend_from_slice(&mp4_box(b"trak", &mp4_box(b"mdia", &mdia)));
+
+        let mut bytes = mp4_box(b"ftyp", b"M4A \x00\x00\x00\x00");
+        bytes.extend_from_slice(&mp4_box(b"mdat", &[0u8; 64]));
+        bytes.extend_from_slice(&mp4_box(b"moov", &moov));
+        bytes
+    }
+
+    // 44100 Hz stereo, 43 frames of 100 bytes and 1024 samples.
+    fn adts_file() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for _ in 0..43 {
+            bytes.extend_from_slice(&[0xff, 0xf1, 0x50, 0x80, 0x0c, 0x80, 0x00]);
+            bytes.extend_from_slice(&[0u8; 93]);
+        }
+        bytes
+    }
+
+    // 44100 Hz stereo at 16000 bytes a second; 6.5 seconds less 1.5 of
+    // preroll.
+    fn wma_file() -> Vec<u8> {
+        let mut file_properties = vec![0u8; 80];
+        file_properties[40..48].copy_from_slice(&65_000_000u64.to_le_bytes());
+        file_properties[56..64].copy_from_slice(&1_500u64.to_le_bytes());
+        let mut stream = ASF_AUDIO_MEDIA.to_vec();
+        stream.resize(54, 0);
+        let mut wave = vec![0u8; 18];
+        wave[2..4].copy_from_slice(&2u16.to_le_bytes());
+        wave[4..8].copy_from_slice(&44_100u32.to_le_bytes());
+        wave[8..12].copy_from_slice(&16_000u32.to_le_bytes());
+        stream.extend_from_slice(&wave);
+
+        let mut objects = asf_object(&ASF_FILE_PROPERTIES, &file_properties);
+        objects.extend_from_slice(&asf_object(&ASF_STREAM_PROPERTIES, &stream));
+        asf_file(&objects)
+    }
+
+    fn samples() -> Vec<(&'static str, Vec<u8>)> {
+        vec![
+            ("wav", wave_file()),
+            ("flac", flac_file()),
+            ("ogg", vorbis_file()),
+            ("mp3", cbr_mp3()),
+            ("mp3", xing_mp3()),
+            ("m4a", m4a_file()),
+            ("aac", adts_file()),
+            ("wma", wma_file()),
+        ]
+    }
+
+    #[test]
+    fn wave_reads_format_and_data_chunks() {
+        let metadata = expect(metadata("wav", "wav", &wave_file()), "1.00s", "22050", "2");
+        assert_eq!(metadata["bitrate"], "705600");
+    }
+
+    #[test]
+    fn flac_reads_streaminfo() {
+        expect(
+            metadata("flac", "flac", &flac_file()),
+            "2.00s",
+            "44100",
+            "2",
+        );
+    }
+
+    #[test]
+    fn ogg_vorbis_uses_last_granule() {
+        let metadata = expect(
+            metadata("vorbis", "ogg", &vorbis_file()),
+            "3.00s",
+            "48000",
+            "1",
+        );
+        assert_eq!(metadata["bitrate"], "96000");
+    }
+
+    #[test]
+    fn mp3_without_vbr_header_is_constant_bitrate() {
+        let metadata = expect(metadata("cbr", "mp3", &cbr_mp3()), "2.00s", "44100", "2");
+        assert_eq!(metadata["bitrate"], "128000");
+    }
+
+    #[test]
+    fn mp3_counts_frames_from_xing_header() {
+        expect(metadata("xing", "mp3", &xing_mp3()), "26.12s", "44100", "1");
+    }
+
+    #[test]
+    fn m4a_reads_movie_header_and_sound_track() {
+        expect(metadata("m4a", "m4a", &m4a_file()), "4.50s", "44100", "2");
+    }
+
+    #[test]
+    fn m4a_rejects_box_sizes_that_overflow() {
+        // A 64-bit size that would wrap the offset back to the start.
+        let mut bytes = mp4_box(b"ftyp", b"M4A \x00\x00\x00\x00");
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend_from_slice(b"free");
+        bytes.extend_from_slice(&(u64::MAX - 15).to_be_bytes());
+        assert_eq!(metadata("wrap", "m4a", &bytes), None);
+
+        // Sizes smaller than their own header, or zero-length boxes that
+        // would not move the walk forward.
+        for size in [0u32, 4, 7] {
+            let mut bytes = mp4_box(b"ftyp", b"M4A \x00\x00\x00\x00");
+            bytes.extend_from_slice(&size.to_be_bytes());
+            bytes.extend_from_slice(b"free");
+            bytes.resize(bytes.len() + 32, 0);
+            assert_eq!(metadata("small", "m4a", &bytes), None);
+        }
+    }
+
+    #[test]
+    fn m4a_gives_up_after_too_many_boxes() {
+        let mut bytes = mp4_box(b"ftyp", b"M4A \x00\x00\x00\x00");
+        for _ in 0..MAX_TOP_LEVEL_BOXES {
+            bytes.extend_from_slice(&mp4_box(b"free", &[]));
+        }
+        bytes.extend_from_slice(&m4a_file()[16..]);
+        assert_eq!(metadata("boxes", "m4a", &bytes), None);
+    }
+
+    #[test]
+    fn aac_estimates_adts_length() {
+        expect(metadata("adts", "aac", &adts_file()), "1.00s", "44100", "2");
+    }
+
+    #[test]
+    fn wma_reads_asf_properties() {
+        let metadata = expect(metadata("wma", "wma", &wma_file()), "5.00s", "44100", "2");
+        assert_eq!(metadata["bitrate"], "128000");
+    }
+
+    #[test]
+    fn wma_rejects_bad_object_sizes() {
+        for size in [0u64, 23, u64::MAX, u64::MAX - 8] {
+            let mut object = ASF_FILE_PROPERTIES.to_vec();
+            object.extend_from_slice(&size.to_le_bytes());
+            object.resize(object.len() + 80, 0);
+            assert_eq!(metadata("asf-size", "wma", &asf_file(&object)), None);
+        }
+    }
+
+    #[test]
+    fn truncated_files_do_not_panic() {
+        for (file_type, bytes) in samples() {
+            for len in 0..bytes.len().min(200) {
+                let _ = metadata("truncated", file_type, &bytes[..len]);
+            }
+        }
+    }
+
+    // Corrupts a few bytes of each sample many times over; the readers must
+    // return rather than panic or loop.
+    #[test]
+    fn corrupted_files_do_not_panic() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for (file_type, bytes) in samples() {
+            for _ in 0..300 {
+                let mut corrupted = bytes.clone();
+                for _ in 0..1 + next() % 4 {
+                    let at = (next() % corrupted.len() as u64) as usize;
+                    corrupted[at] = next() as u8;
+                }
+                if next() % 4 == 0 {
+                    corrupted.truncate((next() % corrupted.len() as u64) as usize);
+                }
+                let _ = metadata("fuzz", file_type, &corrupted);
+            }
+        }
+    }
+}
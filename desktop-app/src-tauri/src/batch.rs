@@ -0,0 +1,148 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::Error;
+use crate::{access, inference};
+use crate::{run_ingestion_job, IngestionConfig, IngestionResult};
+
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z", "rar", "unitypackage"];
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PackKind {
+    Directory,
+    Archive,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiscoveredPack {
+    path: String,
+    name: String,
+    author: Option<String>,
+    version: Option<String>,
+    kind: PackKind,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BatchProgress {
+    index: usize,
+    total: usize,
+    name: String,
+    path: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BatchItemResult {
+    name: String,
+    path: String,
+    result: Option<IngestionResult>,
+    error: Option<Error>,
+}
+
+pub fn discover(root: &Path) -> Result<Vec<DiscoveredPack>, String> {
+    let entries =
+        fs::read_dir(root).map_err(|e| format!("Failed to read {}: {}", root.display(), e))?;
+
+    let mut packs = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+
+        let kind = if file_type.is_dir() {
+            PackKind::Directory
+        } else if file_type.is_file() && is_archive(&path) {
+            PackKind::Archive
+        } else {
+            continue;
+        };
+
+        let inferred = inference::infer(&inference::stem_for(&path));
+        packs.push(DiscoveredPack {
+            path: path.to_string_lossy().to_string(),
+            name: inferred.name().to_string(),
+            author: inferred.author().map(str::to_string),
+            version: inferred.version().map(str::to_string),
+            kind,
+        });
+    }
+
+    packs.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(packs)
+}
+
+fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ARCHIVE_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+pub fn discover_packs(root: String) -> Result<Vec<DiscoveredPack>, String> {
+    discover(Path::new(&root))
+}
+
+#[tauri::command]
+pub async fn run_batch_ingestion(
+    app: AppHandle,
+    root: String,
+    config: IngestionConfig,
+    ingestion_path: String,
+) -> Result<Vec<BatchItemResult>, String> {
+    access::ensure_writable(&app)?;
+    let packs = discover(Path::new(&root))?;
+    let total = packs.len();
+    let mut results = Vec::with_capacity(total);
+
+    for (index, pack) in packs.into_iter().enumerate() {
+        let _ = app.emit(
+            "batch-progress",
+            BatchProgress {
+                index,
+                total,
+                name: pack.name.clone(),
+                path: pack.path.clone(),
+            },
+        );
+
+        if pack.kind == PackKind::Archive {
+            results.push(BatchItemResult {
+                name: pack.name,
+                path: pack.path,
+                result: None,
+                error: Some(Error::InvalidConfig(
+                    "Archives must be extracted before ingestion".to_string(),
+                )),
+            });
+            continue;
+        }
+
+        let pack_config = IngestionConfig {
+            path: Some(pack.path.clone()),
+            name: Some(pack.name.clone()),
+            source: "filesystem".to_string(),
+            ..config.clone()
+        };
+
+        let outcome = run_ingestion_job(app.clone(), pack_config, ingestion_path.clone()).await;
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        results.push(BatchItemResult {
+            name: pack.name,
+            path: pack.path,
+            result,
+            error,
+        });
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,193 @@
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::hashing;
+use crate::runtime::RuntimeLimits;
+
+const MAX_SCAN_DEPTH: usize = 8;
+const MAX_SAMPLE_FILES: usize = 2000;
+const SEQUENTIAL_BYTES: u64 = 256 * 1024 * 1024;
+const CHUNK: usize = 1024 * 1024;
+const RANDOM_BLOCK: usize = 4096;
+const RANDOM_READS: usize = 2000;
+const HASH_BYTES: usize = 64 * 1024 * 1024;
+const TIME_BUDGET: Duration = Duration::from_secs(5);
+// Below these the library is treated as network or spinning storage.
+const SLOW_SEQUENTIAL_MB_S: f64 = 150.0;
+const SLOW_RANDOM_IOPS: f64 = 500.0;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BenchmarkReport {
+    files_sampled: usize,
+    sequential_mb_per_s: Option<f64>,
+    random_read_iops: Option<f64>,
+    hash_mb_per_s: f64,
+    hash_mb_per_s_all_cores: f64,
+    cores: usize,
+    // Values to pass to `set_runtime_limits`.
+    suggested: RuntimeLimits,
+    notes: Vec<String>,
+}
+
+fn collect_files(dir: &Path, depth: usize, out: &mut Vec<(PathBuf, u64)>) {
+    if depth > MAX_SCAN_DEPTH || out.len() >= MAX_SAMPLE_FILES {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if out.len() >= MAX_SAMPLE_FILES {
+            return;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_files(&entry.path(), depth + 1, out);
+        } else if let Ok(metadata) = entry.metadata() {
+            if metadata.len() > 0 {
+                out.push((entry.path(), metadata.len()));
+            }
+        }
+    }
+}
+
+fn mb_per_s(bytes: u64, elapsed: Duration) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64().max(1e-9)
+}
+
+// Reads the largest files front to back, which is what scans and hashing do.
+fn sequential(files: &[(PathBuf, u64)]) -> Option<f64> {
+    let mut largest: Vec<&(PathBuf, u64)> = files.iter().collect();
+    largest.sort_by_key(|(_, len)| Reverse(*len));
+    let mut buffer = vec![0u8; CHUNK];
+    let mut total = 0u64;
+    let start = Instant::now();
+    'files: for (path, _) in largest {
+        let Ok(mut file) = File::open(path) else {
+            continue;
+        };
+        loop {
+            match file.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => total += read as u64,
+            }
+            if total >= SEQUENTIAL_BYTES || start.elapsed() > TIME_BUDGET {
+                break 'files;
+            }
+        }
+    }
+    (total > 0).then(|| mb_per_s(total, start.elapsed()))
+}
+
+// Small reads at scattered offsets, as when previews and metadata are read
+// from many files.
+fn random(files: &[(PathBuf, u64)]) -> Option<f64> {
+    let mut seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(1, |d| d.as_nanos() as u64)
+        | 1;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    let mut buffer = [0u8; RANDOM_BLOCK];
+    let mut reads = 0usize;
+    let start = Instant::now();
+    for _ in 0..RANDOM_READS {
+        if start.elapsed() > TIME_BUDGET {
+            break;
+        }
+        let (path, len) = &files[next() as usize % files.len()];
+        let Ok(mut file) = File::open(path) else {
+            continue;
+        };
+        let offset = next() % len.saturating_sub(RANDOM_BLOCK as u64).max(1);
+        if file.seek(SeekFrom::Start(offset)).is_ok() && file.read(&mut buffer).is_ok() {
+            reads += 1;
+        }
+    }
+    (reads > 0).then(|| reads as f64 / start.elapsed().as_secs_f64().max(1e-9))
+}
+
+// Uses the same streaming path as ingestion, so the figure matches what a
+// scan can reach once the disk keeps up.
+fn hash_throughput(threads: usize) -> f64 {
+    let data: Vec<u8> = (0..HASH_BYTES).map(|i| (i % 251) as u8).collect();
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| hashing::sha256_reader(&mut data.as_slice(), |_| {}));
+        }
+    });
+    mb_per_s((HASH_BYTES * threads) as u64, start.elapsed())
+}
+
+fn suggest(
+    cores: usize,
+    sequential: Option<f64>,
+    random: Option<f64>,
+    notes: &mut Vec<String>,
+) -> RuntimeLimits {
+    let slow = sequential.is_some_and(|mb| mb < SLOW_SEQUENTIAL_MB_S)
+        || random.is_some_and(|iops| iops < SLOW_RANDOM_IOPS);
+    if slow {
+        notes.push(
+            "The library is on slow or network storage; fewer parallel readers avoid \
+             thrashing it"
+                .to_string(),
+        );
+        // Larger batches mean fewer commits while the disk is busy.
+        RuntimeLimits::new(cores.clamp(1, 4), 2, 1, 20_000)
+    } else {
+        // Autodetection already assumes fast local storage.
+        RuntimeLimits::default()
+    }
+}
+
+fn run(root: &Path) -> BenchmarkReport {
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut files = Vec::new();
+    collect_files(root, 0, &mut files);
+    let mut notes = Vec::new();
+    if files.is_empty() {
+        notes.push("No files were found to read; only hashing was measured".to_string());
+    } else {
+        notes.push(
+            "Files read recently may be served from the OS cache and appear faster".to_string(),
+        );
+    }
+
+    let sequential = (!files.is_empty()).then(|| sequential(&files)).flatten();
+    let random = (!files.is_empty()).then(|| random(&files)).flatten();
+    BenchmarkReport {
+        files_sampled: files.len(),
+        sequential_mb_per_s: sequential,
+        random_read_iops: random,
+        hash_mb_per_s: hash_throughput(1),
+        hash_mb_per_s_all_cores: hash_throughput(cores),
+        cores,
+        suggested: suggest(cores, sequential, random, &mut notes),
+        notes,
+    }
+}
+
+// Takes several seconds on slow storage, so it runs off the async runtime.
+#[tauri::command]
+pub async fn run_benchmark(path: String) -> Result<BenchmarkReport, String> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    tauri::async_runtime::spawn_blocking(move || run(&root))
+        .await
+        .map_err(|e| format!("Benchmark failed: {}", e))
+}
@@ -0,0 +1,382 @@
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::access;
+use crate::asset_types::{self, AssetType};
+use crate::catalog::with_connection;
+use crate::variant_groups;
+
+const MANIFEST_NAME: &str = "bundle.json";
+const DEFAULT_NAME: &str = "AssetBundle";
+// Map names that end texture file stems; files that differ only by these
+// belong to the same surface.
+const TEXTURE_MAPS: &[&str] = &[
+    "albedo",
+    "basecolor",
+    "diffuse",
+    "color",
+    "normal",
+    "nrm",
+    "roughness",
+    "metallic",
+    "metalness",
+    "ao",
+    "occlusion",
+    "height",
+    "displacement",
+    "opacity",
+    "mask",
+    "specular",
+    "gloss",
+    "glossiness",
+    "emissive",
+    "cavity",
+    "bump",
+    "translucency",
+];
+
+// Folder layouts other tools expect:
+//   quixel: `<Bundle>/<Asset>/<file>`, one folder per asset with its own
+//           metadata file, as Megascans downloads are laid out
+//   unreal: `Content/<Bundle>/<Meshes|Textures|...>/<file>`, except that
+//           packages keep their pack folders (see `unreal_package_path`)
+//   godot:  `addons/<bundle>/<models|textures|...>/<file>`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleFormat {
+    Quixel,
+    Unreal,
+    Godot,
+}
+
+struct SourceAsset {
+    id: i64,
+    path: PathBuf,
+    pack_name: String,
+    license: Option<String>,
+    relative_path: String,
+    file_type: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BundledAsset {
+    asset_id: i64,
+    pack_name: String,
+    source_path: String,
+    bundle_path: String,
+    license: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BundleSummary {
+    path: String,
+    format: BundleFormat,
+    assets: Vec<BundledAsset>,
+    size_bytes: u64,
+}
+
+fn string_list(text: String) -> Vec<String> {
+    serde_json::from_str(&text).unwrap_or_default()
+}
+
+fn load(app: &AppHandle, ids: &[i64]) -> Result<Vec<SourceAsset>, String> {
+    with_connection(app, |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT p.root_path, p.pack_name, p.license_link, a.relative_path, a.file_type,
+                        a.local_tags, p.global_tags
+                 FROM assets a JOIN packs p ON p.pack_id = a.pack_id
+                 WHERE a.id = ?1 AND a.review_status = 'approved'",
+            )
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        ids.iter()
+            .map(|&id| {
+                stmt.query_row(params![id], |row| {
+                    let root: String = row.get(0)?;
+                    let relative_path: String = row.get(3)?;
+                    let mut tags = string_list(row.get(5)?);
+                    tags.extend(string_list(row.get(6)?));
+                    Ok(SourceAsset {
+                        id,
+                        path: Path::new(&root).join(&relative_path),
+                        pack_name: row.get(1)?,
+                        license: row.get(2)?,
+                        relative_path,
+                        file_type: row.get(4)?,
+                        tags,
+                    })
+                })
+                .optional()
+                .map_err(|e| format!("Failed to read asset {}: {}", id, e))?
+                .ok_or(format!("Asset {} is unknown or not approved", id))
+            })
+            .collect()
+    })
+}
+
+// Engines are strict about folder names, so anything but ASCII letters and
+// digits becomes an underscore.
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    let slug = slug.trim_matches('_');
+    if slug.is_empty() {
+        DEFAULT_NAME.to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+// Folder inside the archive that holds the bundle.
+fn root(format: BundleFormat, name: &str) -> String {
+    match format {
+        BundleFormat::Quixel => name.to_string(),
+        BundleFormat::Unreal => format!("Content/{}", name),
+        BundleFormat::Godot => format!("addons/{}", name.to_lowercase()),
+    }
+}
+
+fn type_folder(format: BundleFormat, asset_type: AssetType) -> &'static str {
+    match (format, asset_type) {
+        (BundleFormat::Godot, AssetType::Mesh) => "models",
+        (BundleFormat::Godot, AssetType::Scene) => "scenes",
+        (BundleFormat::Godot, AssetType::Texture) => "textures",
+        (BundleFormat::Godot, AssetType::Material) => "materials",
+        (BundleFormat::Godot, AssetType::Animation) => "animations",
+        (BundleFormat::Godot, AssetType::Audio) => "audio",
+        (BundleFormat::Godot, AssetType::Vfx) => "vfx",
+        (BundleFormat::Godot, AssetType::Script) => "scripts",
+        (BundleFormat::Godot, AssetType::Font) => "fonts",
+        (BundleFormat::Godot, AssetType::Shader) => "shaders",
+        (BundleFormat::Godot, AssetType::Other) => "misc",
+        (_, AssetType::Mesh) => "Meshes",
+        (_, AssetType::Scene) => "Maps",
+        (_, AssetType::Texture) => "Textures",
+        (_, AssetType::Material) => "Materials",
+        (_, AssetType::Animation) => "Animations",
+        (_, AssetType::Audio) => "Audio",
+        (_, AssetType::Vfx) => "FX",
+        (_, AssetType::Script) => "Scripts",
+        (_, AssetType::Font) => "Fonts",
+        (_, AssetType::Shader) => "Shaders",
+        (_, AssetType::Other) => "Misc",
+    }
+}
+
+// `Rock_Mossy_4K_Albedo.png` and `Rock_Mossy_LOD1.fbx` both belong to
+// `Rock_Mossy`.
+fn asset_name(relative_path: &str) -> String {
+    let name = variant_groups::identify(relative_path).name;
+    let tokens: Vec<&str> = name.split('_').collect();
+    let end = tokens
+        .iter()
+        .rposition(|token| !TEXTURE_MAPS.contains(&token.to_lowercase().as_str()))
+        .map_or(tokens.len(), |index| index + 1);
+    slug(&tokens[..end].join("_"))
+}
+
+// Unreal packages reference each other by path, so `.uasset` and `.umap`
+// files keep their folders below the pack's `Content` directory.
+fn unreal_package_path(relative_path: &str, file_type: &str) -> Option<String> {
+    if !matches!(file_type.to_lowercase().as_str(), "uasset" | "umap") {
+        return None;
+    }
+    let mut parts: Vec<&str> = relative_path
+        .split(['/', '\\'])
+        .filter(|part| !matches!(*part, "" | "." | ".."))
+        .collect();
+    if parts
+        .first()
+        .is_some_and(|part| part.eq_ignore_ascii_case("Content"))
+    {
+        parts.remove(0);
+    }
+    Some(parts.join("/"))
+}
+
+fn file_name(relative_path: &str) -> &str {
+    relative_path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(relative_path)
+}
+
+// Appends `_2`, `_3`, ... before the extension until the path is unused.
+fn unique(path: String, used: &mut HashSet<String>) -> String {
+    if used.insert(path.to_lowercase()) {
+        return path;
+    }
+    let (stem, extension) = match path.rfind('.') {
+        Some(index) if index > path.rfind('/').map_or(0, |slash| slash + 1) => path.split_at(index),
+        _ => (path.as_str(), ""),
+    };
+    (2..)
+        .map(|n| format!("{}_{}{}", stem, n, extension))
+        .find(|candidate| used.insert(candidate.to_lowercase()))
+        .unwrap_or_default()
+}
+
+fn layout(format: BundleFormat, name: &str, assets: &[SourceAsset]) -> Vec<String> {
+    let root = root(format, name);
+    let mut used = HashSet::new();
+    assets
+        .iter()
+        .map(|asset| {
+            let file = file_name(&asset.relative_path);
+            let path = match format {
+                BundleFormat::Quixel => {
+                    format!("{}/{}/{}", root, asset_name(&asset.relative_path), file)
+                }
+                BundleFormat::Unreal
+                    if let Some(package) =
+                        unreal_package_path(&asset.relative_path, &asset.file_type) =>
+                {
+                    format!("{}/{}", root, package)
+                }
+                BundleFormat::Unreal | BundleFormat::Godot => {
+                    let asset_type = asset_types::classify(&asset.relative_path, &asset.file_type);
+                    format!("{}/{}/{}", root, type_folder(format, asset_type), file)
+                }
+            };
+            unique(path, &mut used)
+        })
+        .collect()
+}
+
+fn write_bundle(
+    destination: &Path,
+    format: BundleFormat,
+    name: &str,
+    assets: &[SourceAsset],
+    paths: &[String],
+) -> Result<u64, String> {
+    let write_err = |e: String| format!("Failed to write {}: {}", destination.display(), e);
+    let out = File::create(destination).map_err(|e| write_err(e.to_string()))?;
+    let mut zip = ZipWriter::new(out);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut size_bytes = 0;
+    for (asset, path) in assets.iter().zip(paths) {
+        let mut source = File::open(&asset.path)
+            .map_err(|e| format!("Failed to read {}: {}", asset.path.display(), e))?;
+        let size = source.metadata().map_or(0, |meta| meta.len());
+        zip.start_file(
+            path.as_str(),
+            options.large_file(size > u64::from(u32::MAX)),
+        )
+        .map_err(|e| write_err(e.to_string()))?;
+        size_bytes += io::copy(&mut source, &mut zip).map_err(|e| write_err(e.to_string()))?;
+    }
+
+    let mut used: HashSet<String> = paths.iter().map(|path| path.to_lowercase()).collect();
+    let mut metadata: Vec<(String, serde_json::Value)> = Vec::new();
+    // Megascans keep a metadata file beside each asset's files.
+    if format == BundleFormat::Quixel {
+        let mut folders: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (index, path) in paths.iter().enumerate() {
+            let folder = path.rsplit_once('/').map_or("", |(folder, _)| folder);
+            folders.entry(folder).or_default().push(index);
+        }
+        for (folder, members) in folders {
+            let asset = folder.rsplit('/').next().unwrap_or(folder);
+            let mut tags: Vec<&String> = members.iter().flat_map(|&i| &assets[i].tags).collect();
+            tags.sort();
+            tags.dedup();
+            metadata.push((
+                unique(format!("{}/{}.json", folder, asset), &mut used),
+                json!({
+                    "name": asset,
+                    "tags": tags,
+                    "files": members.iter().map(|&i| file_name(&paths[i])).collect::<Vec<_>>(),
+                    "packs": members.iter().map(|&i| &assets[i].pack_name).collect::<BTreeSet<_>>(),
+                }),
+            ));
+        }
+    }
+    let root = root(format, name);
+    metadata.push((
+        unique(format!("{}/{}", root, MANIFEST_NAME), &mut used),
+        json!({
+            "name": name,
+            "format": format,
+            "assets": assets.iter().zip(paths).map(|(asset, path)| json!({
+                "path": path.strip_prefix(&format!("{}/", root)).unwrap_or(path),
+                "pack": asset.pack_name,
+                "source_path": asset.relative_path,
+                "license": asset.license,
+            })).collect::<Vec<_>>(),
+        }),
+    ));
+    for (path, value) in metadata {
+        let text = serde_json::to_string_pretty(&value)
+            .map_err(|e| format!("Failed to serialize {}: {}", path, e))?;
+        zip.start_file(path.as_str(), options)
+            .map_err(|e| write_err(e.to_string()))?;
+        zip.write_all(text.as_bytes())
+            .map_err(|e| write_err(e.to_string()))?;
+    }
+    zip.finish().map_err(|e| write_err(e.to_string()))?;
+    Ok(size_bytes)
+}
+
+// Packages catalog assets as a zip laid out for another tool. The same
+// selection can be bundled in each format.
+#[tauri::command]
+pub async fn create_bundle(
+    app: AppHandle,
+    mut asset_ids: Vec<i64>,
+    format: BundleFormat,
+    destination: String,
+    name: Option<String>,
+) -> Result<BundleSummary, String> {
+    access::ensure_writable(&app)?;
+    if asset_ids.is_empty() {
+        return Err("Select at least one asset to bundle".to_string());
+    }
+    let mut seen = HashSet::new();
+    asset_ids.retain(|id| seen.insert(*id));
+    let name = slug(name.as_deref().unwrap_or(DEFAULT_NAME));
+    let assets = load(&app, &asset_ids)?;
+    let paths = layout(format, &name, &assets);
+
+    let path = destination.clone();
+    let (assets, paths, size_bytes) = tauri::async_runtime::spawn_blocking(move || {
+        write_bundle(Path::new(&path), format, &name, &assets, &paths)
+            .map(|size_bytes| (assets, paths, size_bytes))
+    })
+    .await
+    .map_err(|e| format!("Failed to write bundle: {}", e))??;
+
+    Ok(BundleSummary {
+        path: destination,
+        format,
+        assets: assets
+            .into_iter()
+            .zip(paths)
+            .map(|(asset, bundle_path)| BundledAsset {
+                asset_id: asset.id,
+                pack_name: asset.pack_name,
+                source_path: asset.relative_path,
+                bundle_path,
+                license: asset.license,
+                tags: asset.tags,
+            })
+            .collect(),
+        size_bytes,
+    })
+}
@@ -0,0 +1,641 @@
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::hashing::HASH_KEY;
+use crate::jobs::now_millis;
+use crate::manifest::{self, AssetManifest, AssetSource};
+use crate::messages::Message;
+use crate::review::{self, ReviewStatus};
+use crate::{access, dedupe, documents, log_batch, runtime, storage, LogEntry};
+
+const CATALOG_FILE: &str = "catalog.sqlite3";
+const DEFAULT_PAGE_SIZE: u32 = 500;
+
+const SCHEMA: &str = "
+    PRAGMA foreign_keys = ON;
+    CREATE TABLE IF NOT EXISTS packs (
+        pack_id TEXT PRIMARY KEY,
+        pack_name TEXT NOT NULL,
+        root_path TEXT NOT NULL,
+        source TEXT,
+        license_link TEXT,
+        global_tags TEXT NOT NULL,
+        saved_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS assets (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        pack_id TEXT NOT NULL REFERENCES packs(pack_id) ON DELETE CASCADE,
+        relative_path TEXT NOT NULL,
+        file_type TEXT NOT NULL,
+        size_bytes INTEGER NOT NULL,
+        metadata TEXT NOT NULL,
+        local_tags TEXT NOT NULL,
+        review_status TEXT NOT NULL DEFAULT 'approved',
+        review_reason TEXT,
+        reviewed_at INTEGER,
+        content_hash TEXT,
+        UNIQUE (pack_id, relative_path)
+    );
+    CREATE INDEX IF NOT EXISTS assets_pack ON assets(pack_id);
+    CREATE TABLE IF NOT EXISTS external_assets (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        marketplace TEXT NOT NULL,
+        item_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        url TEXT,
+        author TEXT,
+        added_at INTEGER NOT NULL,
+        materialize_job_id TEXT,
+        UNIQUE (marketplace, item_id)
+    );
+    CREATE TABLE IF NOT EXISTS purchase_requests (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        url TEXT NOT NULL,
+        marketplace TEXT NOT NULL,
+        item_id TEXT NOT NULL,
+        justification TEXT NOT NULL,
+        estimated_price TEXT,
+        requested_by TEXT,
+        status TEXT NOT NULL,
+        decided_by TEXT,
+        decision_note TEXT,
+        external_asset_id INTEGER REFERENCES external_assets(id) ON DELETE SET NULL,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS wishlist (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        url TEXT NOT NULL,
+        marketplace TEXT NOT NULL,
+        item_id TEXT NOT NULL,
+        name TEXT,
+        added_by TEXT NOT NULL,
+        added_at INTEGER NOT NULL,
+        UNIQUE (marketplace, item_id)
+    );
+    CREATE TABLE IF NOT EXISTS wishlist_votes (
+        wishlist_id INTEGER NOT NULL REFERENCES wishlist(id) ON DELETE CASCADE,
+        voter TEXT NOT NULL,
+        voted_at INTEGER NOT NULL,
+        PRIMARY KEY (wishlist_id, voter)
+    );
+    CREATE TABLE IF NOT EXISTS pack_documents (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        pack_id TEXT NOT NULL REFERENCES packs(pack_id) ON DELETE CASCADE,
+        relative_path TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        size_bytes INTEGER NOT NULL,
+        text TEXT NOT NULL,
+        truncated INTEGER NOT NULL,
+        UNIQUE (pack_id, relative_path)
+    );
+    CREATE VIRTUAL TABLE IF NOT EXISTS document_search USING fts5(
+        path, text,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
+    CREATE TRIGGER IF NOT EXISTS document_search_insert AFTER INSERT ON pack_documents BEGIN
+        INSERT INTO document_search (rowid, path, text)
+        VALUES (new.id, new.relative_path, new.text);
+    END;
+    CREATE TRIGGER IF NOT EXISTS document_search_delete AFTER DELETE ON pack_documents BEGIN
+        DELETE FROM document_search WHERE rowid = old.id;
+    END;
+    CREATE VIRTUAL TABLE IF NOT EXISTS asset_search USING fts5(
+        pack_name, path, tags, source, license,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
+    CREATE TRIGGER IF NOT EXISTS asset_search_insert AFTER INSERT ON assets BEGIN
+        INSERT INTO asset_search (rowid, pack_name, path, tags, source, license)
+        SELECT new.id, p.pack_name, new.relative_path || ' ' || new.file_type,
+               new.local_tags || ' ' || p.global_tags,
+               COALESCE(p.source, ''), COALESCE(p.license_link, '')
+        FROM packs p WHERE p.pack_id = new.pack_id;
+    END;
+    CREATE TRIGGER IF NOT EXISTS asset_search_delete AFTER DELETE ON assets BEGIN
+        DELETE FROM asset_search WHERE rowid = old.id;
+    END;
+    CREATE TRIGGER IF NOT EXISTS asset_search_update AFTER UPDATE ON assets BEGIN
+        DELETE FROM asset_search WHERE rowid = old.id;
+        INSERT INTO asset_search (rowid, pack_name, path, tags, source, license)
+        SELECT new.id, p.pack_name, new.relative_path || ' ' || new.file_type,
+               new.local_tags || ' ' || p.global_tags,
+               COALESCE(p.source, ''), COALESCE(p.license_link, '')
+        FROM packs p WHERE p.pack_id = new.pack_id;
+    END;
+    -- Catalogs created before the search index existed are indexed once.
+    INSERT INTO asset_search (rowid, pack_name, path, tags, source, license)
+    SELECT a.id, p.pack_name, a.relative_path || ' ' || a.file_type,
+           a.local_tags || ' ' || p.global_tags,
+           COALESCE(p.source, ''), COALESCE(p.license_link, '')
+    FROM assets a JOIN packs p ON p.pack_id = a.pack_id
+    WHERE NOT EXISTS (SELECT 1 FROM asset_search);
+";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AssetRecord {
+    id: i64,
+    pack_id: String,
+    pack_name: String,
+    source: Option<String>,
+    relative_path: String,
+    file_type: String,
+    size_bytes: u64,
+    metadata: Value,
+    local_tags: Vec<String>,
+    global_tags: Vec<String>,
+    review_status: ReviewStatus,
+    review_reason: Option<String>,
+}
+
+impl AssetRecord {
+    pub fn pack_id(&self) -> &str {
+        &self.pack_id
+    }
+
+    pub fn relative_path(&self) -> &str {
+        &self.relative_path
+    }
+}
+
+// The connection is opened on first use because the app data directory is
+// only known once the app handle exists.
+#[derive(Default)]
+pub struct Catalog {
+    conn: Mutex<Option<Connection>>,
+}
+
+pub fn with_connection<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Connection) -> Result<T, String>,
+) -> Result<T, String> {
+    let catalog = app.state::<Catalog>();
+    let mut guard = catalog.conn.lock().unwrap();
+    let conn = match guard.as_mut() {
+        Some(conn) => conn,
+        None => {
+            let path = storage::data_path(app, CATALOG_FILE)?;
+            let opened = Connection::open(&path)
+                .map_err(|e| format!("Failed to open catalog {}: {}", path.display(), e))?;
+            initialise(&opened)?;
+            guard.insert(opened)
+        }
+    };
+    f(conn)
+}
+
+fn initialise(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(SCHEMA)
+        .map_err(|e| format!("Failed to initialise catalog: {}", e))?;
+    review::migrate(conn)?;
+    dedupe::migrate(conn)
+}
+
+fn string_list(text: String) -> Vec<String> {
+    serde_json::from_str(&text).unwrap_or_default()
+}
+
+type Review = (String, Option<String>, Option<i64>);
+
+// Re-saving a pack replaces its assets, so re-ingesting keeps the catalog in
+// step with the folder instead of accumulating stale rows. Review decisions
+// are carried over for files that are still present.
+fn save_pack(
+    tx: &Transaction,
+    manifest: &AssetManifest,
+    initial_status: ReviewStatus,
+) -> Result<String, String> {
+    let pack_id = manifest.pack_id.as_str();
+    let db_err = |e: rusqlite::Error| format!("Failed to save pack {}: {}", pack_id, e);
+    let json_err = |e: serde_json::Error| format!("Failed to save pack {}: {}", pack_id, e);
+
+    tx.execute(
+        "INSERT INTO packs (pack_id, pack_name, root_path, source, license_link, global_tags, saved_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT(pack_id) DO UPDATE SET
+             pack_name = excluded.pack_name,
+             root_path = excluded.root_path,
+             source = excluded.source,
+             license_link = excluded.license_link,
+             global_tags = excluded.global_tags,
+             saved_at = excluded.saved_at",
+        params![
+            pack_id,
+            manifest.pack_name,
+            manifest.root_path,
+            manifest.source.as_ref().map(AssetSource::as_str),
+            manifest.license_link,
+            serde_json::to_string(&manifest.global_tags).map_err(json_err)?,
+            now_millis() as i64,
+        ],
+    )
+    .map_err(db_err)?;
+    let reviews: HashMap<String, Review> = {
+        let mut stmt = tx
+            .prepare(
+                "SELECT relative_path, review_status, review_reason, reviewed_at
+                 FROM assets WHERE pack_id = ?1",
+            )
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map(params![pack_id], |row| {
+                Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?)))
+            })
+            .map_err(db_err)?;
+        rows.collect::<Result<_, _>>().map_err(db_err)?
+    };
+    tx.execute("DELETE FROM assets WHERE pack_id = ?1", params![pack_id])
+        .map_err(db_err)?;
+
+    {
+        let mut insert = tx
+            .prepare(
+                "INSERT OR REPLACE INTO assets
+                     (pack_id, relative_path, file_type, size_bytes, metadata, local_tags,
+                      review_status, review_reason, reviewed_at, content_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )
+            .map_err(db_err)?;
+        for asset in &manifest.assets {
+            let (status, reason, reviewed_at) = reviews
+                .get(&asset.relative_path)
+                .cloned()
+                .unwrap_or((initial_status.as_str().to_string(), None, None));
+            insert
+                .execute(params![
+                    pack_id,
+                    asset.relative_path,
+                    asset.file_type,
+                    asset.size_bytes as i64,
+                    serde_json::to_string(&asset.metadata).map_err(json_err)?,
+                    serde_json::to_string(&asset.local_tags).map_err(json_err)?,
+                    status,
+                    reason,
+                    reviewed_at,
+                    asset.metadata.get(HASH_KEY),
+                ])
+                .map_err(db_err)?;
+        }
+    }
+    documents::save(tx, pack_id, Path::new(&manifest.root_path)).map_err(db_err)?;
+    Ok(pack_id.to_string())
+}
+
+// Packs are written together until a transaction holds `db_batch_size`
+// asset rows, so batches of small packs do not pay for a commit each. A pack
+// is never split across transactions.
+fn save_all(app: &AppHandle, manifests: &[AssetManifest]) -> Result<Vec<String>, String> {
+    let initial_status = review::initial_status(app);
+    let batch_size = runtime::limits(app).db_batch_size;
+    let db_err = |e: rusqlite::Error| format!("Failed to save catalog: {}", e);
+    with_connection(app, |conn| {
+        let mut saved = Vec::with_capacity(manifests.len());
+        let mut remaining = manifests;
+        while !remaining.is_empty() {
+            let tx = conn.transaction().map_err(db_err)?;
+            let mut rows = 0;
+            while let Some((manifest, rest)) = remaining.split_first() {
+                if rows > 0 && rows + manifest.assets.len() > batch_size {
+                    break;
+                }
+                saved.push(save_pack(&tx, manifest, initial_status)?);
+                rows += manifest.assets.len();
+                remaining = rest;
+            }
+            tx.commit().map_err(db_err)?;
+        }
+        Ok(saved)
+    })
+}
+
+// Called after every successful ingestion. A catalog failure is reported but
+// does not fail the job, since the manifests are still returned.
+pub fn record_ingestion(app: &AppHandle, manifests: &[AssetManifest]) {
+    if let Err(reason) = save_all(app, manifests) {
+        log_batch::emit(
+            app,
+            LogEntry::new(
+                "warn",
+                Message::new("catalog.save_failed").param("reason", reason),
+            ),
+        );
+    }
+}
+
+pub const ASSET_COLUMNS: &str =
+    "a.id, a.pack_id, p.pack_name, p.source, a.relative_path, a.file_type,
+     a.size_bytes, a.metadata, a.local_tags, p.global_tags, a.review_status, a.review_reason";
+
+pub fn asset_from_row(row: &Row) -> rusqlite::Result<AssetRecord> {
+    Ok(AssetRecord {
+        id: row.get(0)?,
+        pack_id: row.get(1)?,
+        pack_name: row.get(2)?,
+        source: row.get(3)?,
+        relative_path: row.get(4)?,
+        file_type: row.get(5)?,
+        size_bytes: row.get::<_, i64>(6)?.max(0) as u64,
+        metadata: serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or(Value::Null),
+        local_tags: string_list(row.get(8)?),
+        global_tags: string_list(row.get(9)?),
+        review_status: ReviewStatus::parse(&row.get::<_, String>(10)?),
+        review_reason: row.get(11)?,
+    })
+}
+
+// Assets whose metadata `key` holds a comma-separated list of warnings,
+// optionally narrowed to those containing `flag`.
+pub fn list_flagged(
+    app: &AppHandle,
+    key: &str,
+    flag: Option<&str>,
+    limit: Option<u32>,
+) -> Result<Vec<AssetRecord>, String> {
+    let sql = format!(
+        "SELECT {} FROM assets a JOIN packs p ON p.pack_id = a.pack_id
+         WHERE json_extract(a.metadata, ?1) IS NOT NULL
+           AND (?2 IS NULL OR instr(',' || json_extract(a.metadata, ?1) || ',',
+                                    ',' || ?2 || ',') > 0)
+         ORDER BY p.pack_name, a.relative_path
+         LIMIT ?3",
+        ASSET_COLUMNS
+    );
+    with_connection(app, |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        let rows = stmt
+            .query_map(
+                params![
+                    format!("$.{}", key),
+                    flag,
+                    limit.unwrap_or(DEFAULT_PAGE_SIZE)
+                ],
+                asset_from_row,
+            )
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read assets: {}", e))
+    })
+}
+
+#[tauri::command]
+pub fn save_manifest(app: AppHandle, manifest_json: String) -> Result<Vec<String>, String> {
+    access::ensure_writable(&app)?;
+    let manifests = manifest::parse(manifest_json.as_bytes())
+        .map_err(|issues| format!("Invalid manifest: {}", manifest::summarize(&issues)))?;
+    save_all(&app, &manifests)
+}
+
+#[tauri::command]
+pub fn list_assets(
+    app: AppHandle,
+    pack_id: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    review_status: Option<ReviewStatus>,
+) -> Result<Vec<AssetRecord>, String> {
+    // Browsing shows the main library; the review queue asks for `pending`.
+    let review_status = review_status.unwrap_or(ReviewStatus::Approved);
+    let sql = format!(
+        "SELECT {} FROM assets a JOIN packs p ON p.pack_id = a.pack_id
+         WHERE (?1 IS NULL OR a.pack_id = ?1) AND a.review_status = ?4
+         ORDER BY p.pack_name, a.relative_path
+         LIMIT ?2 OFFSET ?3",
+        ASSET_COLUMNS
+    );
+    with_connection(&app, |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        let rows = stmt
+            .query_map(
+                params![
+                    pack_id,
+                    limit.unwrap_or(DEFAULT_PAGE_SIZE),
+                    offset.unwrap_or(0),
+                    review_status.as_str()
+                ],
+                asset_from_row,
+            )
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read assets: {}", e))
+    })
+}
+
+#[tauri::command]
+pub fn get_asset(app: AppHandle, id: i64) -> Result<AssetRecord, String> {
+    let sql = format!(
+        "SELECT {} FROM assets a JOIN packs p ON p.pack_id = a.pack_id WHERE a.id = ?1",
+        ASSET_COLUMNS
+    );
+    with_connection(&app, |conn| {
+        conn.query_row(&sql, params![id], asset_from_row)
+            .optional()
+            .map_err(|e| format!("Failed to read asset {}: {}", id, e))?
+            .ok_or(format!("Unknown asset: {}", id))
+    })
+}
+
+// Location of an asset on disk, with its file type.
+pub fn asset_file(app: &AppHandle, id: i64) -> Result<(PathBuf, String), String> {
+    with_connection(app, |conn| {
+        conn.query_row(
+            "SELECT p.root_path, a.relative_path, a.file_type
+             FROM assets a JOIN packs p ON p.pack_id = a.pack_id WHERE a.id = ?1",
+            params![id],
+            |row| {
+                let root: String = row.get(0)?;
+                let relative: String = row.get(1)?;
+                Ok((Path::new(&root).join(relative), row.get(2)?))
+            },
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read asset {}: {}", id, e))?
+        .ok_or(format!("Unknown asset: {}", id))
+    })
+}
+
+// An asset's relative path and the SHA-256 recorded at ingestion, if any.
+pub type RecordedHash = (String, Option<String>);
+
+// Pack root and the recorded hash of each asset.
+pub fn pack_hashes(app: &AppHandle, pack_id: &str) -> Result<(PathBuf, Vec<RecordedHash>), String> {
+    with_connection(app, |conn| {
+        let root: String = conn
+            .query_row(
+                "SELECT root_path FROM packs WHERE pack_id = ?1",
+                params![pack_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read pack {}: {}", pack_id, e))?
+            .ok_or(format!("Unknown pack: {}", pack_id))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT relative_path, json_extract(metadata, '$.sha256')
+                 FROM assets WHERE pack_id = ?1 ORDER BY relative_path",
+            )
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        let assets = stmt
+            .query_map(params![pack_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to query assets: {}", e))?
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read assets: {}", e))?;
+        Ok((PathBuf::from(root), assets))
+    })
+}
+
+#[tauri::command]
+pub fn delete_asset(app: AppHandle, id: i64) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    let deleted = with_connection(&app, |conn| {
+        conn.execute("DELETE FROM assets WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete asset {}: {}", id, e))
+    })?;
+    if deleted == 0 {
+        return Err(format!("Unknown asset: {}", id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::manifest::AssetFile;
+
+    pub fn manifest(pack_id: &str, pack_name: &str, paths: &[&str]) -> AssetManifest {
+        AssetManifest {
+            pack_id: pack_id.to_string(),
+            pack_name: pack_name.to_string(),
+            root_path: format!("/nonexistent/{}", pack_id),
+            source: Some(AssetSource::Filesystem),
+            license_link: None,
+            global_tags: Vec::new(),
+            assets: paths
+                .iter()
+                .map(|path| AssetFile {
+                    relative_path: path.to_string(),
+                    file_type: path.rsplit('.').next().unwrap_or_default().to_string(),
+                    size_bytes: 1,
+                    metadata: Default::default(),
+                    local_tags: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    // An in-memory catalog holding `manifests`.
+    pub fn catalog_with(manifests: &[AssetManifest]) -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        initialise(&conn).unwrap();
+        save(&mut conn, manifests, ReviewStatus::Approved);
+        conn
+    }
+
+    pub fn save(conn: &mut Connection, manifests: &[AssetManifest], status: ReviewStatus) {
+        let tx = conn.transaction().unwrap();
+        for manifest in manifests {
+            save_pack(&tx, manifest, status).unwrap();
+        }
+        tx.commit().unwrap();
+    }
+
+    fn paths(conn: &Connection, pack_id: &str) -> Vec<(String, String)> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT relative_path, review_status FROM assets
+                 WHERE pack_id = ?1 ORDER BY relative_path",
+            )
+            .unwrap();
+        let rows = stmt
+            .query_map(params![pack_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        rows.collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn resaving_a_pack_replaces_its_assets() {
+        let mut conn = catalog_with(&[
+            manifest("rocks", "Rocks", &["a.png", "b.png"]),
+            manifest("trees", "Trees", &["oak.fbx"]),
+        ]);
+        let mut renamed = manifest("rocks", "Rock Pack", &["b.png", "c.png"]);
+        renamed.global_tags = vec!["nature".to_string()];
+        save(&mut conn, &[renamed], ReviewStatus::Approved);
+
+        let rocks: Vec<String> = paths(&conn, "rocks").into_iter().map(|(p, _)| p).collect();
+        assert_eq!(rocks, ["b.png", "c.png"]);
+        assert_eq!(paths(&conn, "trees").len(), 1);
+        let (name, tags): (String, String) = conn
+            .query_row(
+                "SELECT pack_name, global_tags FROM packs WHERE pack_id = 'rocks'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(name, "Rock Pack");
+        assert_eq!(string_list(tags), ["nature"]);
+        // The search index follows the replaced rows.
+        let indexed: i64 = conn
+            .query_row("SELECT count(*) FROM asset_search", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(indexed, 3);
+    }
+
+    #[test]
+    fn resaving_keeps_review_decisions_for_files_still_present() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        initialise(&conn).unwrap();
+        save(
+            &mut conn,
+            &[manifest("rocks", "Rocks", &["a.png", "b.png"])],
+            ReviewStatus::Pending,
+        );
+        conn.execute(
+            "UPDATE assets SET review_status = 'rejected', review_reason = 'blurry',
+                 reviewed_at = 5 WHERE relative_path = 'a.png'",
+            [],
+        )
+        .unwrap();
+
+        save(
+            &mut conn,
+            &[manifest("rocks", "Rocks", &["a.png", "b.png", "c.png"])],
+            ReviewStatus::Approved,
+        );
+        assert_eq!(
+            paths(&conn, "rocks"),
+            [
+                ("a.png".to_string(), "rejected".to_string()),
+                ("b.png".to_string(), "pending".to_string()),
+                ("c.png".to_string(), "approved".to_string()),
+            ]
+        );
+        let (reason, reviewed_at): (Option<String>, Option<i64>) = conn
+            .query_row(
+                "SELECT review_reason, reviewed_at FROM assets WHERE relative_path = 'a.png'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(reason.as_deref(), Some("blurry"));
+        assert_eq!(reviewed_at, Some(5));
+    }
+
+    #[test]
+    fn initialising_twice_keeps_existing_rows() {
+        let conn = catalog_with(&[manifest("rocks", "Rocks", &["a.png"])]);
+        initialise(&conn).unwrap();
+        assert_eq!(paths(&conn, "rocks").len(), 1);
+        let indexed: i64 = conn
+            .query_row("SELECT count(*) FROM asset_search", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(indexed, 1);
+    }
+}
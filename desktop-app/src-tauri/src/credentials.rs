@@ -0,0 +1,94 @@
+use keyring::Entry;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::access;
+
+const SERVICE: &str = "game-asset-tracker";
+
+// Marketplace logins are kept in the OS keychain and handed to the ingestion
+// helper through these variables, so they never appear in argv or on disk.
+fn env_var(source: &str) -> Result<&'static str, String> {
+    match source {
+        "fab" => Ok("GAT_FAB_COOKIES"),
+        "uas" => Ok("GAT_UAS_TOKENS"),
+        _ => Err(format!("No credentials are used for source: {}", source)),
+    }
+}
+
+fn entry(source: &str) -> Result<Entry, String> {
+    env_var(source)?;
+    Entry::new(SERVICE, source).map_err(|e| format!("Failed to open the keychain: {}", e))
+}
+
+fn read(source: &str) -> Result<Option<String>, String> {
+    match entry(source)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read {} credentials: {}", source, e)),
+    }
+}
+
+// Variables to set on a marketplace run. A keychain that cannot be read
+// leaves the helper to capture a fresh login itself.
+pub fn environment(source: &str) -> Vec<(&'static str, String)> {
+    match (env_var(source), read(source)) {
+        (Ok(name), Ok(Some(secret))) => vec![(name, secret)],
+        _ => Vec::new(),
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CredentialStatus {
+    source: String,
+    stored: bool,
+}
+
+// FAB expects the captured cookies as a JSON object of name to value; UAS
+// expects the Unity Hub token object (accessToken, accessTokenExpiration,
+// refreshToken).
+#[tauri::command]
+pub fn store_credential(
+    app: AppHandle,
+    source: String,
+    secret: String,
+) -> Result<CredentialStatus, String> {
+    access::ensure_writable(&app)?;
+    let parsed: serde_json::Value =
+        serde_json::from_str(&secret).map_err(|e| format!("Credentials must be JSON: {}", e))?;
+    if !parsed.is_object() {
+        return Err("Credentials must be a JSON object".to_string());
+    }
+    if source == "uas" {
+        for key in ["accessToken", "accessTokenExpiration", "refreshToken"] {
+            if parsed.get(key).is_none() {
+                return Err(format!("UAS credentials are missing \"{}\"", key));
+            }
+        }
+    }
+    entry(&source)?
+        .set_password(&secret)
+        .map_err(|e| format!("Failed to store {} credentials: {}", source, e))?;
+    Ok(CredentialStatus {
+        source,
+        stored: true,
+    })
+}
+
+#[tauri::command]
+pub fn get_credential_status(source: String) -> Result<CredentialStatus, String> {
+    let stored = read(&source)?.is_some();
+    Ok(CredentialStatus { source, stored })
+}
+
+#[tauri::command]
+pub fn delete_credential(app: AppHandle, source: String) -> Result<CredentialStatus, String> {
+    access::ensure_writable(&app)?;
+    match entry(&source)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(CredentialStatus {
+            source,
+            stored: false,
+        }),
+        Err(e) => Err(format!("Failed to delete {} credentials: {}", source, e)),
+    }
+}
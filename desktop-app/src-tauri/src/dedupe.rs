@@ -0,0 +1,207 @@
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::catalog::{self, with_connection, AssetRecord, ASSET_COLUMNS};
+use crate::hashing::{self, HASH_KEY};
+use crate::{access, runtime};
+
+const DEFAULT_GROUP_LIMIT: u32 = 200;
+
+// Catalogs created before duplicate detection get the column added and
+// filled from hashes already recorded in asset metadata.
+pub fn migrate(conn: &Connection) -> Result<(), String> {
+    let has_column = conn
+        .prepare("SELECT 1 FROM pragma_table_info('assets') WHERE name = 'content_hash'")
+        .and_then(|mut stmt| stmt.exists([]))
+        .map_err(|e| format!("Failed to inspect catalog: {}", e))?;
+    if !has_column {
+        conn.execute_batch(
+            "ALTER TABLE assets ADD COLUMN content_hash TEXT;
+             UPDATE assets SET content_hash = json_extract(metadata, '$.sha256');",
+        )
+        .map_err(|e| format!("Failed to migrate catalog: {}", e))?;
+    }
+    conn.execute_batch("CREATE INDEX IF NOT EXISTS assets_content ON assets(content_hash);")
+        .map_err(|e| format!("Failed to migrate catalog: {}", e))
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DuplicateGroup {
+    content_hash: String,
+    size_bytes: u64,
+    // Space freed by keeping a single copy.
+    reclaimable_bytes: u64,
+    assets: Vec<AssetRecord>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DuplicateReport {
+    groups: Vec<DuplicateGroup>,
+    // Totals cover every group, including those past `limit`.
+    group_count: u64,
+    duplicate_files: u64,
+    total_reclaimable_bytes: u64,
+    // Assets without a content hash cannot be matched; see
+    // `hash_catalog_assets`.
+    unhashed_assets: u64,
+}
+
+// Groups catalog assets with identical content, largest savings first.
+// Files smaller than `min_size` are ignored.
+#[tauri::command]
+pub fn find_duplicates(
+    app: AppHandle,
+    min_size: Option<u64>,
+    limit: Option<u32>,
+) -> Result<DuplicateReport, String> {
+    let min_size = min_size.unwrap_or(0) as i64;
+    let db_err = |e: rusqlite::Error| format!("Failed to find duplicates: {}", e);
+    with_connection(&app, |conn| {
+        let (group_count, duplicate_files, total_reclaimable_bytes): (i64, i64, i64) = conn
+            .query_row(
+                "SELECT COUNT(*), COALESCE(SUM(copies - 1), 0),
+                        COALESCE(SUM(size * (copies - 1)), 0)
+                 FROM (SELECT COUNT(*) AS copies, MAX(size_bytes) AS size FROM assets
+                       WHERE content_hash IS NOT NULL AND size_bytes >= ?1
+                       GROUP BY content_hash HAVING COUNT(*) > 1)",
+                params![min_size],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(db_err)?;
+        let unhashed_assets: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM assets WHERE content_hash IS NULL",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(db_err)?;
+
+        let hashes: Vec<(String, i64, i64)> = {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT content_hash, MAX(size_bytes), COUNT(*) FROM assets
+                     WHERE content_hash IS NOT NULL AND size_bytes >= ?1
+                     GROUP BY content_hash HAVING COUNT(*) > 1
+                     ORDER BY MAX(size_bytes) * (COUNT(*) - 1) DESC, content_hash
+                     LIMIT ?2",
+                )
+                .map_err(db_err)?;
+            let rows = stmt
+                .query_map(
+                    params![min_size, limit.unwrap_or(DEFAULT_GROUP_LIMIT)],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .map_err(db_err)?;
+            rows.collect::<Result<_, _>>().map_err(db_err)?
+        };
+
+        let sql = format!(
+            "SELECT {} FROM assets a JOIN packs p ON p.pack_id = a.pack_id
+             WHERE a.content_hash = ?1
+             ORDER BY p.pack_name, a.relative_path",
+            ASSET_COLUMNS
+        );
+        let mut stmt = conn.prepare(&sql).map_err(db_err)?;
+        let mut groups = Vec::with_capacity(hashes.len());
+        for (content_hash, size, copies) in hashes {
+            let assets = stmt
+                .query_map(params![content_hash], catalog::asset_from_row)
+                .map_err(db_err)?
+                .collect::<Result<_, _>>()
+                .map_err(db_err)?;
+            let size_bytes = size.max(0) as u64;
+            groups.push(DuplicateGroup {
+                content_hash,
+                size_bytes,
+                reclaimable_bytes: size_bytes * (copies.max(1) as u64 - 1),
+                assets,
+            });
+        }
+        Ok(DuplicateReport {
+            groups,
+            group_count: group_count.max(0) as u64,
+            duplicate_files: duplicate_files.max(0) as u64,
+            total_reclaimable_bytes: total_reclaimable_bytes.max(0) as u64,
+            unhashed_assets: unhashed_assets.max(0) as u64,
+        })
+    })
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HashBackfill {
+    hashed: u64,
+    // Files that are missing or unreadable on disk.
+    failed: u64,
+}
+
+// Hashes assets that were ingested without a content hash, such as
+// marketplace packs, so they take part in duplicate detection.
+#[tauri::command]
+pub async fn hash_catalog_assets(
+    app: AppHandle,
+    pack_id: Option<String>,
+) -> Result<HashBackfill, String> {
+    access::ensure_writable(&app)?;
+    let pending: Vec<(i64, PathBuf)> = with_connection(&app, |conn| {
+        let db_err = |e: rusqlite::Error| format!("Failed to query assets: {}", e);
+        let mut stmt = conn
+            .prepare(
+                "SELECT a.id, p.root_path, a.relative_path
+                 FROM assets a JOIN packs p ON p.pack_id = a.pack_id
+                 WHERE a.content_hash IS NULL AND (?1 IS NULL OR a.pack_id = ?1)",
+            )
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map(params![pack_id], |row| {
+                let root: String = row.get(1)?;
+                let relative: String = row.get(2)?;
+                Ok((row.get(0)?, Path::new(&root).join(relative)))
+            })
+            .map_err(db_err)?;
+        rows.collect::<Result<_, _>>().map_err(db_err)
+    })?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let paths: Vec<PathBuf> = pending.iter().map(|(_, path)| path.clone()).collect();
+        let digests = hashing::hash_files(
+            runtime::limits(&app).analysis_threads,
+            &paths,
+            || false,
+            |progress| hashing::emit_progress(&app, None, pack_id.as_deref(), progress),
+        );
+        let hashed: Vec<(i64, String)> = pending
+            .iter()
+            .zip(digests)
+            .filter_map(|((id, _), digest)| Some((*id, digest?.ok()?)))
+            .collect();
+        let failed = (pending.len() - hashed.len()) as u64;
+
+        with_connection(&app, |conn| {
+            let db_err = |e: rusqlite::Error| format!("Failed to save hashes: {}", e);
+            let tx = conn.transaction().map_err(db_err)?;
+            {
+                let mut update = tx
+                    .prepare(
+                        "UPDATE assets SET content_hash = ?1,
+                             metadata = json_set(metadata, ?2, ?1)
+                         WHERE id = ?3",
+                    )
+                    .map_err(db_err)?;
+                for (id, digest) in &hashed {
+                    update
+                        .execute(params![digest, format!("$.{}", HASH_KEY), id])
+                        .map_err(db_err)?;
+                }
+            }
+            tx.commit().map_err(db_err)
+        })?;
+        Ok(HashBackfill {
+            hashed: hashed.len() as u64,
+            failed,
+        })
+    })
+    .await
+    .map_err(|e| format!("Hashing failed: {}", e))?
+}
@@ -0,0 +1,470 @@
+use rusqlite::{params, Transaction};
+use serde::Serialize;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::catalog::with_connection;
+use crate::search::fts_query;
+
+const MAX_DOCUMENT_BYTES: u64 = 64 * 1024;
+const MAX_DOCUMENTS: usize = 32;
+const MAX_SCAN_DEPTH: usize = 3;
+const TEXT_EXTENSIONS: &[&str] = &["", "txt", "md", "markdown", "rst"];
+const DOC_DIRECTORIES: &[&str] = &["doc", "docs", "documentation", "manual"];
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    Readme,
+    License,
+    Documentation,
+}
+
+impl DocumentKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            DocumentKind::Readme => "readme",
+            DocumentKind::License => "license",
+            DocumentKind::Documentation => "documentation",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "readme" => DocumentKind::Readme,
+            "license" => DocumentKind::License,
+            _ => DocumentKind::Documentation,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PackDocument {
+    kind: DocumentKind,
+    relative_path: String,
+    size_bytes: u64,
+    text: String,
+    truncated: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DetectedLicense {
+    pub id: &'static str,
+    pub link: &'static str,
+    pub source_path: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DocumentHit {
+    pack_id: String,
+    pack_name: String,
+    kind: DocumentKind,
+    relative_path: String,
+    // Matched terms are wrapped in `[` and `]`.
+    snippet: String,
+    // bm25 score, lower is better.
+    score: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HarvestReport {
+    pub documents: Vec<PackDocument>,
+    pub detected_license: Option<DetectedLicense>,
+}
+
+// Identifiers accepted from an explicit `SPDX-License-Identifier:` or
+// `License:` line, matched after lowercasing and joining words with `-`.
+const LICENSE_IDS: &[(&str, &str, &str)] = &[
+    ("cc0-1.0", "CC0-1.0", CC0_LINK),
+    ("cc0", "CC0-1.0", CC0_LINK),
+    ("cc-by-sa-4.0", "CC-BY-SA-4.0", CC_BY_SA_LINK),
+    ("cc-by-4.0", "CC-BY-4.0", CC_BY_LINK),
+    ("mit", "MIT", MIT_LINK),
+    ("apache-2.0", "Apache-2.0", APACHE_LINK),
+];
+
+// Phrases that only appear in the canonical license texts, so a match is a
+// confident identification rather than a guess from a file name.
+const LICENSE_SIGNATURES: &[(&str, &str, &str)] = &[
+    ("CC0-1.0", CC0_LINK, "cc0 1.0 universal"),
+    (
+        "CC0-1.0",
+        CC0_LINK,
+        "creativecommons.org/publicdomain/zero/1.0",
+    ),
+    (
+        "CC-BY-SA-4.0",
+        CC_BY_SA_LINK,
+        "attribution-sharealike 4.0 international",
+    ),
+    (
+        "CC-BY-SA-4.0",
+        CC_BY_SA_LINK,
+        "creativecommons.org/licenses/by-sa/4.0",
+    ),
+    ("CC-BY-4.0", CC_BY_LINK, "attribution 4.0 international"),
+    (
+        "CC-BY-4.0",
+        CC_BY_LINK,
+        "creativecommons.org/licenses/by/4.0",
+    ),
+    (
+        "MIT",
+        MIT_LINK,
+        "permission is hereby granted free of charge to any person obtaining a copy",
+    ),
+    ("Apache-2.0", APACHE_LINK, "apache license version 2.0"),
+    ("Apache-2.0", APACHE_LINK, "apache.org/licenses/license-2.0"),
+];
+
+const CC0_LINK: &str = "https://creativecommons.org/publicdomain/zero/1.0/";
+const CC_BY_SA_LINK: &str = "https://creativecommons.org/licenses/by-sa/4.0/";
+const CC_BY_LINK: &str = "https://creativecommons.org/licenses/by/4.0/";
+const MIT_LINK: &str = "https://opensource.org/licenses/MIT";
+const APACHE_LINK: &str = "https://www.apache.org/licenses/LICENSE-2.0";
+
+pub fn harvest(root: &Path) -> HarvestReport {
+    let mut documents = Vec::new();
+    collect_documents(root, root, 0, &mut documents);
+    documents.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    // A license file is trusted for its full text; a README only for an
+    // explicit declaration. Other documents are never used, since they often
+    // mention licenses they are not under.
+    let from_license_files = documents
+        .iter()
+        .filter(|doc| is_license_file(&doc.relative_path))
+        .find_map(|doc| detect_license(&doc.text).map(|found| (found, doc)));
+    let from_readmes = || {
+        documents
+            .iter()
+            .filter(|doc| doc.kind == DocumentKind::Readme)
+            .find_map(|doc| declared_license(&doc.text).map(|found| (found, doc)))
+    };
+    let detected_license = from_license_files
+        .or_else(from_readmes)
+        .map(|((id, link), doc)| DetectedLicense {
+            id,
+            link,
+            source_path: doc.relative_path.clone(),
+        });
+
+    HarvestReport {
+        documents,
+        detected_license,
+    }
+}
+
+fn is_license_file(relative_path: &str) -> bool {
+    let name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+    let lower = name.to_lowercase();
+    ["license", "licence", "copying"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+}
+
+pub fn detect_license(text: &str) -> Option<(&'static str, &'static str)> {
+    let normalized = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(',', "")
+        .to_lowercase();
+
+    LICENSE_SIGNATURES
+        .iter()
+        .find(|(_, _, phrase)| normalized.contains(phrase))
+        .map(|(id, link, _)| (*id, *link))
+}
+
+// Looks for `SPDX-License-Identifier: <id>` or `License: <id>` on a line of
+// its own, ignoring Markdown emphasis and list markers around it.
+fn declared_license(text: &str) -> Option<(&'static str, &'static str)> {
+    text.lines().find_map(|line| {
+        let line = line
+            .trim()
+            .trim_start_matches(['#', '*', '-', '>', '_', ' '])
+            .replace(['*', '_', '`'], "");
+        let (label, value) = line.split_once(':')?;
+        let label = label.trim().to_lowercase();
+        if !["spdx-license-identifier", "license", "licence"].contains(&label.as_str()) {
+            return None;
+        }
+        let value = value
+            .trim()
+            .trim_end_matches('.')
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+            .to_lowercase();
+        LICENSE_IDS
+            .iter()
+            .find(|(key, _, _)| *key == value)
+            .map(|(_, id, link)| (*id, *link))
+    })
+}
+
+fn collect_documents(root: &Path, dir: &Path, depth: usize, out: &mut Vec<PackDocument>) {
+    if depth > MAX_SCAN_DEPTH || out.len() >= MAX_DOCUMENTS {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        if out.len() >= MAX_DOCUMENTS {
+            return;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+
+        if file_type.is_dir() {
+            collect_documents(root, &path, depth + 1, out);
+            continue;
+        }
+        if !file_type.is_file() {
+            continue;
+        }
+
+        let in_doc_dir = path
+            .parent()
+            .and_then(|p| p.file_name())
+            .map(|p| DOC_DIRECTORIES.contains(&p.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false);
+        let Some(kind) = classify(&name, in_doc_dir) else {
+            continue;
+        };
+
+        if let Some(doc) = read_document(root, &path, kind) {
+            out.push(doc);
+        }
+    }
+}
+
+fn classify(file_name: &str, in_doc_dir: bool) -> Option<DocumentKind> {
+    let lower = file_name.to_lowercase();
+    let (stem, extension) = match lower.rsplit_once('.') {
+        Some((stem, ext)) => (stem, ext),
+        None => (lower.as_str(), ""),
+    };
+    if !TEXT_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+
+    if ["license", "licence", "copying", "eula"]
+        .iter()
+        .any(|prefix| stem.starts_with(prefix))
+    {
+        Some(DocumentKind::License)
+    } else if stem.starts_with("readme") {
+        Some(DocumentKind::Readme)
+    } else if in_doc_dir || stem.contains("documentation") || stem.contains("manual") {
+        Some(DocumentKind::Documentation)
+    } else {
+        None
+    }
+}
+
+fn read_document(root: &Path, path: &Path, kind: DocumentKind) -> Option<PackDocument> {
+    let size_bytes = fs::metadata(path).ok()?.len();
+    let mut bytes = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(MAX_DOCUMENT_BYTES)
+        .read_to_end(&mut bytes)
+        .ok()?;
+
+    let relative_path = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    Some(PackDocument {
+        kind,
+        relative_path,
+        size_bytes,
+        text: String::from_utf8_lossy(&bytes).to_string(),
+        truncated: size_bytes > MAX_DOCUMENT_BYTES,
+    })
+}
+
+#[tauri::command]
+pub fn harvest_pack_documents(path: String) -> Result<HarvestReport, String> {
+    let root = Path::new(&path);
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    Ok(harvest(root))
+}
+
+// Replaces a pack's stored documents with those now in its folder. A pack
+// saved from a manifest whose folder is not on this machine keeps the
+// documents it already had.
+pub fn save(tx: &Transaction, pack_id: &str, root: &Path) -> rusqlite::Result<()> {
+    if !root.is_dir() {
+        return Ok(());
+    }
+    let report = harvest(root);
+    tx.execute(
+        "DELETE FROM pack_documents WHERE pack_id = ?1",
+        params![pack_id],
+    )?;
+    let mut insert = tx.prepare(
+        "INSERT INTO pack_documents (pack_id, relative_path, kind, size_bytes, text, truncated)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for doc in &report.documents {
+        insert.execute(params![
+            pack_id,
+            doc.relative_path,
+            doc.kind.as_str(),
+            doc.size_bytes as i64,
+            doc.text,
+            doc.truncated,
+        ])?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn search_pack_documents(
+    app: AppHandle,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<DocumentHit>, String> {
+    let Some(fts) = fts_query(&query) else {
+        return Ok(Vec::new());
+    };
+    with_connection(&app, |conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT d.pack_id, p.pack_name, d.kind, d.relative_path,
+                        snippet(document_search, 1, '[', ']', '...', 16),
+                        bm25(document_search, 2.0, 1.0) AS score
+                 FROM document_search
+                 JOIN pack_documents d ON d.id = document_search.rowid
+                 JOIN packs p ON p.pack_id = d.pack_id
+                 WHERE document_search MATCH ?1
+                 ORDER BY score
+                 LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to search documents: {}", e))?;
+        let rows = stmt
+            .query_map(params![fts, limit.unwrap_or(DEFAULT_SEARCH_LIMIT)], |row| {
+                Ok(DocumentHit {
+                    pack_id: row.get(0)?,
+                    pack_name: row.get(1)?,
+                    kind: DocumentKind::parse(&row.get::<_, String>(2)?),
+                    relative_path: row.get(3)?,
+                    snippet: row.get(4)?,
+                    score: row.get(5)?,
+                })
+            })
+            .map_err(|e| format!("Failed to search documents: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read document results: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pack(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("documents-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (path, text) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+        dir
+    }
+
+    fn detected(name: &str, files: &[(&str, &str)]) -> Option<(&'static str, String)> {
+        let dir = pack(name, files);
+        let report = harvest(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        report
+            .detected_license
+            .map(|license| (license.id, license.source_path))
+    }
+
+    #[test]
+    fn license_file_text_is_recognized() {
+        assert_eq!(
+            detected(
+                "file",
+                &[("LICENSE.txt", "CC0 1.0 Universal\n\nStatement of Purpose")]
+            ),
+            Some(("CC0-1.0", "LICENSE.txt".to_string()))
+        );
+        assert_eq!(
+            detected(
+                "copying",
+                &[("COPYING", "Apache License\nVersion 2.0, January 2004")]
+            ),
+            Some(("Apache-2.0", "COPYING".to_string()))
+        );
+    }
+
+    #[test]
+    fn readme_needs_an_explicit_declaration() {
+        assert_eq!(
+            detected(
+                "readme-mention",
+                &[(
+                    "README.md",
+                    "Textures in the style of CC0 1.0 Universal packs."
+                )]
+            ),
+            None
+        );
+        assert_eq!(
+            detected(
+                "readme-spdx",
+                &[("README.md", "# Rocks\nSPDX-License-Identifier: MIT\n")]
+            ),
+            Some(("MIT", "README.md".to_string()))
+        );
+        assert_eq!(
+            detected(
+                "readme-line",
+                &[("README.md", "Rocks\n\n**License:** CC-BY 4.0\n")]
+            ),
+            Some(("CC-BY-4.0", "README.md".to_string()))
+        );
+        assert_eq!(
+            detected("readme-unknown", &[("README.md", "License: see website\n")]),
+            None
+        );
+    }
+
+    #[test]
+    fn other_documents_are_ignored() {
+        assert_eq!(
+            detected(
+                "docs",
+                &[
+                    ("docs/credits.txt", "Fonts under Attribution 4.0 International"),
+                    ("EULA.txt", "Permission is hereby granted, free of charge, to any person obtaining a copy"),
+                ]
+            ),
+            None
+        );
+    }
+}
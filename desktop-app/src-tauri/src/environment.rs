@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::access;
+use crate::log_batch;
+use crate::messages::Message;
+use crate::storage;
+use crate::sync_cache::to_hex;
+use crate::uv_binary;
+use crate::uv_command::UvCommand;
+use crate::LogEntry;
+
+const PINS_FILE: &str = "environment-pins.json";
+
+const REPORT_SCRIPT: &str = "import json, sys, importlib.metadata as m; \
+print(json.dumps({'python': sys.version.split()[0], \
+'packages': {d.metadata['Name']: d.version for d in m.distributions()}}))";
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct EnvironmentPins {
+    lock_hashes: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InterpreterReport {
+    python: String,
+    packages: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EnvironmentInfo {
+    uv_version: Option<String>,
+    python_version: String,
+    packages: BTreeMap<String, String>,
+    lock_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LockfileStatus {
+    lockfile_present: bool,
+    lock_hash: Option<String>,
+    pinned_hash: Option<String>,
+    matches_pin: Option<bool>,
+    up_to_date: bool,
+    details: Option<String>,
+}
+
+fn pin_key(ingestion_path: &str) -> String {
+    fs::canonicalize(ingestion_path)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| ingestion_path.to_string())
+}
+
+pub fn lock_hash(ingestion_path: &str) -> Option<String> {
+    let bytes = fs::read(Path::new(ingestion_path).join("uv.lock")).ok()?;
+    Some(to_hex(&Sha256::digest(&bytes)))
+}
+
+fn pinned_hash(app: &AppHandle, ingestion_path: &str) -> Option<String> {
+    let pins: EnvironmentPins = storage::load_json(app, PINS_FILE);
+    pins.lock_hashes.get(&pin_key(ingestion_path)).cloned()
+}
+
+// Warns, without blocking the job, when the lockfile no longer matches the
+// pinned environment.
+pub fn warn_on_drift(app: &AppHandle, ingestion_path: &str) {
+    let Some(pinned) = pinned_hash(app, ingestion_path) else {
+        return;
+    };
+    if lock_hash(ingestion_path).as_deref() != Some(pinned.as_str()) {
+        log_batch::emit(
+            app,
+            LogEntry::new("warn", Message::new("environment.drift")),
+        );
+    }
+}
+
+#[tauri::command]
+pub async fn get_environment_info(
+    app: AppHandle,
+    ingestion_path: String,
+) -> Result<EnvironmentInfo, String> {
+    let uv_version = uv_binary::command(&app)
+        .args(["--version"])
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    let (args, working_dir) =
+        UvCommand::python_script(&ingestion_path, REPORT_SCRIPT)?.into_parts();
+    let output = uv_binary::command(&app)
+        .args(&args)
+        .current_dir(working_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to inspect environment: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to inspect environment: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let report: InterpreterReport = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Unexpected environment report: {}", e))?;
+
+    Ok(EnvironmentInfo {
+        uv_version,
+        python_version: report.python,
+        packages: report.packages,
+        lock_hash: lock_hash(&ingestion_path),
+    })
+}
+
+#[tauri::command]
+pub async fn verify_lockfile(
+    app: AppHandle,
+    ingestion_path: String,
+) -> Result<LockfileStatus, String> {
+    let lock_hash = lock_hash(&ingestion_path);
+    let pinned_hash = pinned_hash(&app, &ingestion_path);
+    let matches_pin = pinned_hash
+        .as_ref()
+        .map(|pinned| lock_hash.as_ref() == Some(pinned));
+
+    let (args, working_dir) = UvCommand::lock_check(&ingestion_path)?.into_parts();
+    let output = uv_binary::command(&app)
+        .args(&args)
+        .current_dir(working_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run uv lock --check: {}", e))?;
+    let details = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    Ok(LockfileStatus {
+        lockfile_present: lock_hash.is_some(),
+        lock_hash,
+        pinned_hash,
+        matches_pin,
+        up_to_date: output.status.success(),
+        details: (!details.is_empty()).then_some(details),
+    })
+}
+
+#[tauri::command]
+pub fn pin_environment(app: AppHandle, ingestion_path: String) -> Result<String, String> {
+    access::ensure_writable(&app)?;
+    let hash =
+        lock_hash(&ingestion_path).ok_or(format!("No uv.lock found in {}", ingestion_path))?;
+    let mut pins: EnvironmentPins = storage::load_json(&app, PINS_FILE);
+    pins.lock_hashes
+        .insert(pin_key(&ingestion_path), hash.clone());
+    storage::save_json(&app, PINS_FILE, &pins)?;
+    Ok(hash)
+}
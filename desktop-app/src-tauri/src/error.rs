@@ -0,0 +1,84 @@
+use serde::ser::{SerializeStruct, Serializer};
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    // Blocked by the app's own mode rather than by anything that failed.
+    Access,
+    // Something missing on this machine, such as uv or project dependencies.
+    Environment,
+    // A path or setting the user can correct.
+    Input,
+    // Marketplace credentials that need to be captured again.
+    Auth,
+    // The ingestion process could not be run or stopped unexpectedly.
+    Process,
+    Internal,
+}
+
+// Failures of the ingestion commands. They serialize as
+// `{ code, category, message }` so the frontend can react to the kind of
+// failure instead of matching on text.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+    #[error("This action is disabled in read-only guest mode")]
+    ReadOnly,
+    #[error("uv could not be started: {0}")]
+    UvUnavailable(String),
+    #[error("{0}")]
+    InvalidPath(String),
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error("The stored {marketplace} login has expired; sign in again")]
+    AuthExpired { marketplace: String },
+    #[error("Dependency sync failed: {0}")]
+    SyncFailed(String),
+    #[error("{0}")]
+    Process(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl Error {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::ReadOnly => "error.read_only",
+            Error::UvUnavailable(_) => "error.uv_unavailable",
+            Error::InvalidPath(_) => "error.invalid_path",
+            Error::InvalidConfig(_) => "error.invalid_config",
+            Error::AuthExpired { .. } => "error.auth_expired",
+            Error::SyncFailed(_) => "error.sync_failed",
+            Error::Process(_) => "error.process",
+            Error::Internal(_) => "error.internal",
+        }
+    }
+
+    pub fn category(&self) -> Category {
+        match self {
+            Error::ReadOnly => Category::Access,
+            Error::UvUnavailable(_) | Error::SyncFailed(_) => Category::Environment,
+            Error::InvalidPath(_) | Error::InvalidConfig(_) => Category::Input,
+            Error::AuthExpired { .. } => Category::Auth,
+            Error::Process(_) => Category::Process,
+            Error::Internal(_) => Category::Internal,
+        }
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Error", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("category", &self.category())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+// Commands not yet migrated keep returning the message.
+impl From<Error> for String {
+    fn from(error: Error) -> Self {
+        error.to_string()
+    }
+}
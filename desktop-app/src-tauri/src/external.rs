@@ -0,0 +1,157 @@
+use rusqlite::{params, OptionalExtension, Row};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::catalog::with_connection;
+use crate::jobs::now_millis;
+use crate::marketplace::Marketplace;
+use crate::{access, purchase_requests, queue, IngestionConfig};
+
+// Items the studio owns on a marketplace but has not downloaded, such as
+// claimed free assets. They count as owned without taking up disk space.
+#[derive(Debug, Serialize, Clone)]
+pub struct ExternalAsset {
+    id: i64,
+    marketplace: Marketplace,
+    item_id: String,
+    name: String,
+    url: Option<String>,
+    author: Option<String>,
+    added_at: u64,
+    materialize_job_id: Option<String>,
+}
+
+const COLUMNS: &str = "id, marketplace, item_id, name, url, author, added_at, materialize_job_id";
+
+fn from_row(row: &Row) -> rusqlite::Result<ExternalAsset> {
+    let marketplace: String = row.get(1)?;
+    Ok(ExternalAsset {
+        id: row.get(0)?,
+        marketplace: Marketplace::parse(&marketplace).unwrap_or(Marketplace::Fab),
+        item_id: row.get(2)?,
+        name: row.get(3)?,
+        url: row.get(4)?,
+        author: row.get(5)?,
+        added_at: row.get::<_, i64>(6)?.max(0) as u64,
+        materialize_job_id: row.get(7)?,
+    })
+}
+
+fn get(app: &AppHandle, id: i64) -> Result<ExternalAsset, String> {
+    let sql = format!("SELECT {} FROM external_assets WHERE id = ?1", COLUMNS);
+    with_connection(app, |conn| {
+        conn.query_row(&sql, params![id], from_row)
+            .optional()
+            .map_err(|e| format!("Failed to read external asset {}: {}", id, e))?
+            .ok_or(format!("Unknown external asset: {}", id))
+    })
+}
+
+#[tauri::command]
+pub fn add_external_asset(
+    app: AppHandle,
+    marketplace: Marketplace,
+    item_id: String,
+    name: String,
+    url: Option<String>,
+    author: Option<String>,
+) -> Result<ExternalAsset, String> {
+    access::ensure_writable(&app)?;
+    let item_id = item_id.trim().to_string();
+    let name = name.trim().to_string();
+    if item_id.is_empty() || name.is_empty() {
+        return Err("External assets need an item id and a name".to_string());
+    }
+
+    let id = with_connection(&app, |conn| {
+        conn.execute(
+            "INSERT INTO external_assets (marketplace, item_id, name, url, author, added_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(marketplace, item_id) DO UPDATE SET
+                 name = excluded.name,
+                 url = COALESCE(excluded.url, url),
+                 author = COALESCE(excluded.author, author)",
+            params![
+                marketplace.as_str(),
+                item_id,
+                name,
+                url,
+                author,
+                now_millis() as i64
+            ],
+        )
+        .and_then(|_| {
+            conn.query_row(
+                "SELECT id FROM external_assets WHERE marketplace = ?1 AND item_id = ?2",
+                params![marketplace.as_str(), item_id],
+                |row| row.get(0),
+            )
+        })
+        .map_err(|e| format!("Failed to save external asset: {}", e))
+    })?;
+    purchase_requests::link_owned_item(&app, marketplace, &item_id, id)?;
+    get(&app, id)
+}
+
+#[tauri::command]
+pub fn list_external_assets(app: AppHandle) -> Result<Vec<ExternalAsset>, String> {
+    let sql = format!(
+        "SELECT {} FROM external_assets ORDER BY name COLLATE NOCASE",
+        COLUMNS
+    );
+    with_connection(&app, |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to query external assets: {}", e))?;
+        let rows = stmt
+            .query_map([], from_row)
+            .map_err(|e| format!("Failed to query external assets: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read external assets: {}", e))
+    })
+}
+
+// Queues a download of a single external item. Only the Unity Asset Store
+// helper can fetch one library item at a time today.
+#[tauri::command]
+pub fn materialize(
+    app: AppHandle,
+    asset_id: i64,
+    ingestion_path: String,
+    output_dir: Option<String>,
+) -> Result<String, String> {
+    access::ensure_writable(&app)?;
+    let asset = get(&app, asset_id)?;
+    if asset.marketplace != Marketplace::UnityAssetStore {
+        return Err(format!(
+            "Downloading single {} items is not supported yet",
+            asset.marketplace.as_str()
+        ));
+    }
+
+    let config = IngestionConfig {
+        path: None,
+        name: Some(asset.name.clone()),
+        source: "uas".to_string(),
+        tags: Vec::new(),
+        license: None,
+        download_strategy: Some("download".to_string()),
+        download_filters: None,
+        output_dir,
+        priority: None,
+        force_sync: None,
+        sandbox: None,
+        asset_id: Some(asset.item_id.clone()),
+        timeout_secs: None,
+    };
+    let job_id = queue::enqueue_ingestion(app.clone(), config, ingestion_path)?;
+
+    with_connection(&app, |conn| {
+        conn.execute(
+            "UPDATE external_assets SET materialize_job_id = ?1 WHERE id = ?2",
+            params![job_id, asset_id],
+        )
+        .map_err(|e| format!("Failed to update external asset {}: {}", asset_id, e))
+    })?;
+    Ok(job_id)
+}
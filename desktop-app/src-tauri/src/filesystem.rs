@@ -0,0 +1,303 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tauri::AppHandle;
+
+use crate::archives;
+use crate::audio_metadata;
+use crate::error::Error;
+use crate::hashing::{self, HASH_KEY};
+use crate::manifest::{AssetFile, AssetManifest, AssetSource};
+use crate::messages::Message;
+use crate::progress::{self, ToolEvent};
+use crate::{jobs, log_batch, runtime, LogEntry};
+
+const MAX_METADATA_LEN: usize = 2048;
+const PROGRESS_EVERY: u64 = 50;
+
+pub struct PackArgs<'a> {
+    pub name: &'a str,
+    pub source: &'a str,
+    pub tags: &'a [String],
+    pub license: Option<&'a str>,
+    // Where an archive given as the pack path is unpacked.
+    pub output_dir: Option<&'a str>,
+}
+
+pub enum Scan {
+    Done(AssetManifest),
+    Cancelled,
+    TimedOut,
+}
+
+// The same checks the Python tool applied to `--license`.
+fn check_license(license: &str) -> Result<(), String> {
+    match license.split_once(':') {
+        Some((scheme, _)) if !matches!(scheme, "http" | "https") => Err(format!(
+            "Invalid URL scheme: {}. Only http and https are allowed.",
+            scheme
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Hidden files are skipped but hidden directories are not, and symlinks that
+// resolve outside the pack are dropped, matching the Python scanner.
+fn collect_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            // Linked directories are not followed, as with `os.walk`.
+            if file_type.is_symlink()
+                && !fs::canonicalize(&path)
+                    .is_ok_and(|target| target.starts_with(root) && target.is_file())
+            {
+                continue;
+            }
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+fn describe(root: &Path, path: &Path) -> io::Result<AssetFile> {
+    let relative = path.strip_prefix(root).map_err(io::Error::other)?;
+    let size_bytes = fs::metadata(path)?.len();
+    let file_type = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut metadata = audio_metadata::read(path, &file_type).unwrap_or_default();
+    for value in metadata.values_mut() {
+        if let Some((cut, _)) = value.char_indices().nth(MAX_METADATA_LEN) {
+            value.truncate(cut);
+        }
+    }
+
+    // Joined with `/` on every platform, as the Python scanner's POSIX paths
+    // were, so manifests match wherever they were built.
+    let mut parts: Vec<String> = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let relative_path = parts.join("/");
+    parts.pop();
+    let local_tags = parts;
+
+    Ok(AssetFile {
+        relative_path,
+        file_type,
+        size_bytes,
+        metadata,
+        local_tags,
+    })
+}
+
+// Builds the manifest the Python `ingest` command would print for `path`,
+// without needing uv or the ingestion project. Files are hashed on the
+// analysis threads; cancelling or passing `deadline` stops at the next file.
+pub fn scan(
+    app: &AppHandle,
+    job_id: &str,
+    path: &str,
+    pack: PackArgs<'_>,
+    deadline: Option<Instant>,
+) -> Result<Scan, Error> {
+    if let Some(license) = pack.license {
+        check_license(license).map_err(Error::InvalidConfig)?;
+    }
+    let registry = jobs::registry(app);
+    let stopped = || registry.is_cancelled(job_id) || deadline.is_some_and(|d| Instant::now() > d);
+
+    // Zipped packs are unpacked first and ingested from where they landed.
+    let mut path = PathBuf::from(path);
+    if archives::detect(&path).is_some() {
+        progress::emit(
+            app,
+            job_id,
+            ToolEvent::Stage {
+                stage: "extracting".to_string(),
+                message: Some(format!("Extracting {}", path.display())),
+            },
+        );
+        let Some(destination) = archives::stage(app, &path, pack.output_dir, job_id, stopped)
+            .map_err(Error::InvalidPath)?
+        else {
+            return Ok(if registry.is_cancelled(job_id) {
+                Scan::Cancelled
+            } else {
+                Scan::TimedOut
+            });
+        };
+        path = destination;
+    }
+    let root = fs::canonicalize(&path).map_err(|e| {
+        Error::InvalidPath(format!(
+            "Pack path {} is not accessible: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    if !root.is_dir() {
+        return Err(Error::InvalidPath(format!(
+            "Pack path {} is not a directory",
+            path.display()
+        )));
+    }
+    progress::emit(
+        app,
+        job_id,
+        ToolEvent::Stage {
+            stage: "scanning".to_string(),
+            message: Some(format!("Scanning directory: {}", root.display())),
+        },
+    );
+    let files = collect_files(&root);
+    let total = files.len() as u64;
+    let done = AtomicU64::new(0);
+    let described = runtime::parallel_map(app, &files, |file| {
+        if stopped() {
+            return None;
+        }
+        let asset = describe(&root, file);
+        let current = done.fetch_add(1, Ordering::Relaxed) + 1;
+        if current.is_multiple_of(PROGRESS_EVERY) || current == total {
+            progress::emit(
+                app,
+                job_id,
+                ToolEvent::Progress {
+                    current,
+                    total: Some(total),
+                    file: Some(
+                        file.strip_prefix(&root)
+                            .unwrap_or(file)
+                            .display()
+                            .to_string(),
+                    ),
+                },
+            );
+        }
+        Some(asset)
+    });
+
+    let described: Vec<(PathBuf, io::Result<AssetFile>)> = files
+        .into_iter()
+        .zip(described)
+        .filter_map(|(file, asset)| Some((file, asset?)))
+        .collect();
+    let (readable, failed): (Vec<_>, Vec<_>) =
+        described.into_iter().partition(|(_, asset)| asset.is_ok());
+    let paths: Vec<PathBuf> = readable.iter().map(|(file, _)| file.clone()).collect();
+    progress::emit(
+        app,
+        job_id,
+        ToolEvent::Stage {
+            stage: "hashing".to_string(),
+            message: None,
+        },
+    );
+    let digests = hashing::hash_files(
+        runtime::limits(app).analysis_threads,
+        &paths,
+        stopped,
+        |progress| {
+            hashing::emit_progress(app, Some(job_id), None, progress);
+            progress::emit(
+                app,
+                job_id,
+                ToolEvent::Progress {
+                    current: progress.files_hashed,
+                    total: Some(progress.total_files),
+                    file: None,
+                },
+            );
+        },
+    );
+
+    if registry.is_cancelled(job_id) {
+        return Ok(Scan::Cancelled);
+    }
+    if stopped() {
+        return Ok(Scan::TimedOut);
+    }
+    let warn = |file: &Path, e: io::Error| {
+        let line = format!("Warning: Failed to process {}: {}", file.display(), e);
+        registry.record_output(app, job_id, &line);
+        log_batch::emit(
+            app,
+            LogEntry::new(
+                "stderr",
+                Message::new("ingestion.output").param("line", line),
+            ),
+        );
+    };
+    // Unreadable files are reported and skipped, as the tool did.
+    for (file, asset) in failed {
+        if let Err(e) = asset {
+            warn(&file, e);
+        }
+    }
+    let mut assets = Vec::with_capacity(readable.len());
+    for ((file, asset), digest) in readable.into_iter().zip(digests) {
+        let (Ok(mut asset), Some(digest)) = (asset, digest) else {
+            continue;
+        };
+        match digest {
+            Ok(digest) => {
+                asset.metadata.insert(HASH_KEY.to_string(), digest);
+                assets.push(asset);
+            }
+            Err(e) => warn(&file, e),
+        }
+    }
+
+    Ok(Scan::Done(AssetManifest {
+        pack_id: uuid::Uuid::new_v4().to_string(),
+        pack_name: pack.name.to_string(),
+        root_path: root.to_string_lossy().to_string(),
+        source: Some(AssetSource::from(pack.source.to_string())),
+        license_link: Some(pack.license.unwrap_or_default().to_string()),
+        global_tags: pack.tags.to_vec(),
+        assets,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_joins_path_parts_with_slashes() {
+        let root = std::env::temp_dir().join(format!("filesystem-{}", std::process::id()));
+        let path = root.join("Textures").join("Rock").join("Albedo.PNG");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"png").unwrap();
+
+        let asset = describe(&root, &path).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(asset.relative_path, "Textures/Rock/Albedo.PNG");
+        assert_eq!(asset.local_tags, vec!["Textures", "Rock"]);
+        assert_eq!(asset.file_type, "png");
+        assert_eq!(asset.size_bytes, 3);
+    }
+}
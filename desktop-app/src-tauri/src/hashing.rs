@@ -0,0 +1,185 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::catalog::{self, RecordedHash};
+use crate::runtime;
+
+pub const HASH_KEY: &str = "sha256";
+const CHUNK: usize = 1024 * 1024;
+const REPORT_EVERY: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct HashProgress {
+    pub bytes_hashed: u64,
+    pub total_bytes: u64,
+    pub files_hashed: u64,
+    pub total_files: u64,
+}
+
+// Emitted as `hash-progress` for each gigabyte hashed and once at the end.
+#[derive(Debug, Serialize, Clone)]
+pub struct HashProgressEvent {
+    job_id: Option<String>,
+    pack_id: Option<String>,
+    #[serde(flatten)]
+    progress: HashProgress,
+}
+
+pub fn emit_progress(
+    app: &AppHandle,
+    job_id: Option<&str>,
+    pack_id: Option<&str>,
+    progress: &HashProgress,
+) {
+    let _ = app.emit(
+        "hash-progress",
+        HashProgressEvent {
+            job_id: job_id.map(str::to_string),
+            pack_id: pack_id.map(str::to_string),
+            progress: progress.clone(),
+        },
+    );
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Streams `reader` through SHA-256 in fixed-size chunks, so memory use does
+// not depend on the file size. `on_read` is told how many bytes each chunk
+// added.
+pub fn sha256_reader(reader: &mut impl Read, mut on_read: impl FnMut(u64)) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; CHUNK];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => {
+                hasher.update(&buffer[..read]);
+                on_read(read as u64);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(hex(&hasher.finalize()))
+}
+
+// Hashes `paths` on `threads` threads, one file per thread at a time, and
+// returns the digests in input order. `report` runs each time another
+// gigabyte has been read across all threads and once when done. Files not
+// started before `stop` returns true are left as `None`.
+//
+// Taking the thread count rather than the app keeps this usable from the
+// benchmark and from tools that have no app handle.
+pub fn hash_files(
+    threads: usize,
+    paths: &[PathBuf],
+    stop: impl Fn() -> bool + Sync,
+    report: impl Fn(&HashProgress) + Sync,
+) -> Vec<Option<io::Result<String>>> {
+    let total_bytes = paths
+        .iter()
+        .map(|path| fs::metadata(path).map_or(0, |metadata| metadata.len()))
+        .sum();
+    let bytes_hashed = AtomicU64::new(0);
+    let files_hashed = AtomicU64::new(0);
+    // Serialises reports so they arrive in order.
+    let reporting = Mutex::new(());
+    let progress = || HashProgress {
+        bytes_hashed: bytes_hashed.load(Ordering::Relaxed),
+        total_bytes,
+        files_hashed: files_hashed.load(Ordering::Relaxed),
+        total_files: paths.len() as u64,
+    };
+
+    let digests = runtime::parallel_map_on(threads, paths, |path| {
+        if stop() {
+            return None;
+        }
+        let digest = File::open(path).and_then(|mut file| {
+            sha256_reader(&mut file, |read| {
+                let before = bytes_hashed.fetch_add(read, Ordering::Relaxed);
+                if (before + read) / REPORT_EVERY > before / REPORT_EVERY {
+                    let _guard = reporting.lock().unwrap();
+                    report(&progress());
+                }
+            })
+        });
+        files_hashed.fetch_add(1, Ordering::Relaxed);
+        Some(digest)
+    });
+    report(&progress());
+    digests
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct VerifyReport {
+    pack_id: String,
+    checked: u64,
+    // Assets ingested before hashes were recorded.
+    unhashed: u64,
+    missing: Vec<String>,
+    unreadable: Vec<String>,
+    mismatched: Vec<String>,
+}
+
+fn verify(
+    app: &AppHandle,
+    pack_id: String,
+    root: PathBuf,
+    assets: Vec<RecordedHash>,
+) -> VerifyReport {
+    let (hashed, unhashed): (Vec<_>, Vec<_>) =
+        assets.into_iter().partition(|(_, digest)| digest.is_some());
+    let paths: Vec<PathBuf> = hashed
+        .iter()
+        .map(|(relative, _)| root.join(relative))
+        .collect();
+    let threads = runtime::limits(app).analysis_threads;
+    let digests = hash_files(
+        threads,
+        &paths,
+        || false,
+        |progress| emit_progress(app, None, Some(&pack_id), progress),
+    );
+
+    let mut report = VerifyReport {
+        pack_id,
+        checked: 0,
+        unhashed: unhashed.len() as u64,
+        missing: Vec::new(),
+        unreadable: Vec::new(),
+        mismatched: Vec::new(),
+    };
+    for ((relative, expected), digest) in hashed.into_iter().zip(digests) {
+        match digest {
+            Some(Ok(digest)) => {
+                report.checked += 1;
+                if Some(digest) != expected {
+                    report.mismatched.push(relative);
+                }
+            }
+            Some(Err(e)) if e.kind() == io::ErrorKind::NotFound => report.missing.push(relative),
+            Some(Err(e)) => report.unreadable.push(format!("{}: {}", relative, e)),
+            None => {}
+        }
+    }
+    report
+}
+
+// Re-hashes a pack's files and compares them with the digests recorded at
+// ingestion.
+#[tauri::command]
+pub async fn verify_pack_hashes(app: AppHandle, pack_id: String) -> Result<VerifyReport, String> {
+    let (root, assets) = catalog::pack_hashes(&app, &pack_id)?;
+    tauri::async_runtime::spawn_blocking(move || verify(&app, pack_id, root, assets))
+        .await
+        .map_err(|e| format!("Verification failed: {}", e))
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::Error;
+use crate::jobs::{self, JobPhase, JobStatus};
+use crate::{access, queue, storage, IngestionConfig, IngestionResult};
+
+const HISTORY_FILE: &str = "job-history.json";
+const MAX_RUNS: usize = 500;
+
+// Everything needed to repeat a finished ingestion exactly.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobRun {
+    // Job ids restart with each launch, so runs get their own id.
+    run_id: String,
+    job_id: String,
+    config: IngestionConfig,
+    ingestion_path: String,
+    phase: JobPhase,
+    started_at: u64,
+    finished_at: u64,
+    exit_code: Option<i32>,
+    error: Option<String>,
+    log_tail: Vec<String>,
+    manifest_ids: Vec<String>,
+    rerun_of: Option<String>,
+}
+
+// Serializes read-modify-write cycles on the history file and remembers
+// which queued jobs are re-runs.
+#[derive(Default)]
+pub struct JobHistory {
+    lock: Mutex<()>,
+    reruns: Mutex<HashMap<String, String>>,
+}
+
+pub fn record(
+    app: &AppHandle,
+    status: &JobStatus,
+    config: IngestionConfig,
+    ingestion_path: String,
+    outcome: &Result<IngestionResult, Error>,
+) {
+    let Some(finished_at) = status.finished_at else {
+        return;
+    };
+    let history = app.state::<JobHistory>();
+    let manifest_ids = match outcome {
+        Ok(IngestionResult {
+            manifests: Some(manifests),
+            ..
+        }) => manifests
+            .iter()
+            .map(|manifest| manifest.pack_id.clone())
+            .collect(),
+        _ => Vec::new(),
+    };
+    let run = JobRun {
+        run_id: format!("{}-{}", status.started_at, status.id),
+        job_id: status.id.clone(),
+        config,
+        ingestion_path,
+        phase: status.phase,
+        started_at: status.started_at,
+        finished_at,
+        exit_code: status.exit_code,
+        error: status.last_error.clone(),
+        log_tail: jobs::registry(app).recent_output(&status.id),
+        manifest_ids,
+        rerun_of: history.reruns.lock().unwrap().remove(&status.id),
+    };
+
+    let _guard = history.lock.lock().unwrap();
+    let mut runs: Vec<JobRun> = storage::load_json(app, HISTORY_FILE);
+    runs.push(run);
+    if runs.len() > MAX_RUNS {
+        runs.drain(..runs.len() - MAX_RUNS);
+    }
+    let _ = storage::save_json(app, HISTORY_FILE, &runs);
+}
+
+// Newest first.
+#[tauri::command]
+pub fn get_job_history(
+    app: AppHandle,
+    history: State<'_, JobHistory>,
+    source: Option<String>,
+    failed_only: Option<bool>,
+    limit: Option<usize>,
+) -> Vec<JobRun> {
+    let _guard = history.lock.lock().unwrap();
+    let runs: Vec<JobRun> = storage::load_json(&app, HISTORY_FILE);
+    runs.into_iter()
+        .rev()
+        .filter(|run| {
+            source
+                .as_ref()
+                .is_none_or(|source| run.config.source == *source)
+        })
+        .filter(|run| !failed_only.unwrap_or(false) || run.phase == JobPhase::Failed)
+        .take(limit.unwrap_or(MAX_RUNS))
+        .collect()
+}
+
+// Queues the run again with its recorded config and ingestion path, and
+// returns the new job id.
+#[tauri::command]
+pub fn rerun_job(
+    app: AppHandle,
+    history: State<'_, JobHistory>,
+    run_id: String,
+) -> Result<String, String> {
+    access::ensure_writable(&app)?;
+    let run = {
+        let _guard = history.lock.lock().unwrap();
+        let runs: Vec<JobRun> = storage::load_json(&app, HISTORY_FILE);
+        runs.into_iter()
+            .find(|run| run.run_id == run_id)
+            .ok_or(format!("Unknown run: {}", run_id))?
+    };
+    // Held across enqueueing so a job that fails at once still finds it.
+    let mut reruns = history.reruns.lock().unwrap();
+    let job_id = queue::enqueue(&app, run.config, run.ingestion_path);
+    reruns.insert(job_id.clone(), run_id);
+    Ok(job_id)
+}
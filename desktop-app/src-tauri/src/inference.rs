@@ -0,0 +1,277 @@
+use serde::Serialize;
+use std::path::Path;
+
+const DOUBLE_EXTENSIONS: &[&str] = &[".tar"];
+// Tokens that only describe the packaging, never the pack.
+const NOISE_TOKENS: &[&str] = &["final", "copy", "free"];
+const ENGINE_PREFIXES: &[&str] = &["ue", "unity"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Inferred {
+    value: String,
+    confidence: f32,
+}
+
+impl Inferred {
+    fn new(value: impl Into<String>, confidence: f32) -> Self {
+        Inferred {
+            value: value.into(),
+            confidence,
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PackInference {
+    name: Inferred,
+    author: Option<Inferred>,
+    version: Option<Inferred>,
+}
+
+impl PackInference {
+    pub fn name(&self) -> &str {
+        self.name.value()
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_ref().map(Inferred::value)
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_ref().map(Inferred::value)
+    }
+}
+
+// Directory names are used as-is; archives lose their extension first.
+pub fn stem_for(path: &Path) -> String {
+    let name = if path.is_dir() {
+        path.file_name()
+    } else {
+        path.file_stem()
+    };
+    let mut stem = name
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    for ext in DOUBLE_EXTENSIONS {
+        if stem.to_ascii_lowercase().ends_with(ext) {
+            stem.truncate(stem.len() - ext.len());
+        }
+    }
+    stem
+}
+
+// Heuristics for the names marketplaces and artists give their downloads:
+// `Author - Pack Name`, `Pack_Name_by_Author`, `[Author] PackName v1.2`,
+// `PackName_UE5.3`. Confidence reflects how explicit the pattern was.
+pub fn infer(stem: &str) -> PackInference {
+    let mut text = stem.replace('_', " ").trim().to_string();
+    let mut author = None;
+
+    if let Some((bracketed, rest)) = split_bracketed_prefix(&text) {
+        author = Some(Inferred::new(bracketed, 0.6));
+        text = rest;
+    }
+    if author.is_none() {
+        if let Some(index) = find_word(&text, "by") {
+            let name = text[..index].trim().to_string();
+            let by = text[index + 2..].trim().to_string();
+            if !name.is_empty() && !by.is_empty() {
+                author = Some(Inferred::new(split_words(&by).join(" "), 0.8));
+                text = name;
+            }
+        }
+    }
+    if author.is_none() {
+        if let Some((left, right)) = text.split_once(" - ") {
+            let (left, right) = (left.trim(), right.trim());
+            if !left.is_empty() && !right.is_empty() && !right.contains(" - ") {
+                author = Some(Inferred::new(split_words(left).join(" "), 0.5));
+                text = right.to_string();
+            }
+        }
+    }
+
+    let mut version = None;
+    let mut words = Vec::new();
+    for token in text.split_whitespace() {
+        let lower = token.to_lowercase();
+        if NOISE_TOKENS.contains(&lower.as_str()) || is_engine_token(&lower) {
+            continue;
+        }
+        if let Some(found) = parse_version(token) {
+            version = Some(found);
+            continue;
+        }
+        words.extend(split_words(token));
+    }
+
+    let name = if words.is_empty() {
+        Inferred::new(stem, 0.2)
+    } else {
+        let single_token = text.split_whitespace().count() <= 1;
+        let confidence = match (single_token, words.len()) {
+            (false, _) => 0.8,
+            (true, 1) => 0.3,
+            (true, _) => 0.5,
+        };
+        Inferred::new(words.join(" "), confidence)
+    };
+
+    PackInference {
+        name,
+        author,
+        version,
+    }
+}
+
+fn split_bracketed_prefix(text: &str) -> Option<(String, String)> {
+    let close = match text.chars().next()? {
+        '[' => ']',
+        '(' => ')',
+        _ => return None,
+    };
+    let end = text.find(close)?;
+    let inner = text[1..end].trim();
+    let rest = text[end + 1..].trim();
+    (!inner.is_empty() && !rest.is_empty()).then(|| (inner.to_string(), rest.to_string()))
+}
+
+// Byte index of a standalone, case-insensitive word.
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let lower = text.to_ascii_lowercase();
+    lower
+        .match_indices(word)
+        .map(|(index, _)| index)
+        .find(|&index| {
+            let before = lower[..index].chars().next_back();
+            let after = lower[index + word.len()..].chars().next();
+            before.is_some_and(char::is_whitespace) && after.is_some_and(char::is_whitespace)
+        })
+}
+
+fn is_engine_token(lower: &str) -> bool {
+    ENGINE_PREFIXES.iter().any(|prefix| {
+        lower.strip_prefix(prefix).is_some_and(|rest| {
+            !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit() || c == '.')
+        })
+    })
+}
+
+// `v2`, `v1.0.3`, `ver1.2` and bare dotted numbers like `1.2.0`.
+fn parse_version(token: &str) -> Option<Inferred> {
+    let lower = token.to_lowercase();
+    let (digits, prefixed) = match lower
+        .strip_prefix("ver")
+        .or_else(|| lower.strip_prefix('v'))
+    {
+        Some(rest) => (rest, true),
+        None => (lower.as_str(), false),
+    };
+    let valid = !digits.is_empty()
+        && digits.starts_with(|c: char| c.is_ascii_digit())
+        && !digits.ends_with('.')
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.');
+    if !valid {
+        return None;
+    }
+    let dots = digits.matches('.').count();
+    let confidence = match (prefixed, dots) {
+        (true, _) | (false, 2..) => 0.9,
+        (false, 1) => 0.6,
+        (false, 0) => return None,
+    };
+    Some(Inferred::new(digits, confidence))
+}
+
+// Splits on hyphens and camel-case boundaries, keeping acronyms together:
+// `SciFi-HDRPack` becomes `Sci Fi HDR Pack`.
+fn split_words(token: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in token.split(|c: char| c == '-' || c.is_whitespace()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut current = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let prev = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1).copied();
+            let boundary = match prev {
+                Some(prev) if c.is_uppercase() => {
+                    prev.is_lowercase()
+                        || prev.is_ascii_digit()
+                        || (prev.is_uppercase() && next.is_some_and(char::is_lowercase))
+                }
+                _ => false,
+            };
+            if boundary && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.push(c);
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+    }
+    words
+}
+
+#[tauri::command]
+pub fn infer_pack_details(path: String) -> PackInference {
+    infer(&stem_for(Path::new(&path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_author_from_common_patterns() {
+        let by = infer("Medieval_Props_by_JohnDoe");
+        assert_eq!(
+            (by.name(), by.author()),
+            ("Medieval Props", Some("John Doe"))
+        );
+
+        let bracketed = infer("[Kenney] Nature Kit v1.2");
+        assert_eq!(bracketed.author(), Some("Kenney"));
+        assert_eq!(bracketed.name(), "Nature Kit");
+        assert_eq!(bracketed.version(), Some("1.2"));
+
+        let dashed = infer("Quixel - Rock Pack");
+        assert_eq!(
+            (dashed.name(), dashed.author()),
+            ("Rock Pack", Some("Quixel"))
+        );
+    }
+
+    #[test]
+    fn drops_engine_and_noise_tokens() {
+        let inferred = infer("SciFi-HDRPack_UE5.3_FINAL");
+        assert_eq!(inferred.name(), "Sci Fi HDR Pack");
+        assert_eq!(inferred.author(), None);
+        assert_eq!(inferred.version(), None);
+    }
+
+    #[test]
+    fn parses_versions_but_not_plain_numbers() {
+        assert_eq!(parse_version("v2").unwrap().value(), "2");
+        assert_eq!(parse_version("ver1.0.3").unwrap().value(), "1.0.3");
+        assert_eq!(parse_version("1.2.0").unwrap().value(), "1.2.0");
+        assert!(parse_version("2024").is_none());
+        assert!(parse_version("v1.").is_none());
+        assert!(parse_version("vfx").is_none());
+    }
+
+    #[test]
+    fn falls_back_to_the_stem() {
+        assert_eq!(infer("___").name(), "___");
+    }
+
+    #[test]
+    fn stem_for_strips_double_extensions() {
+        assert_eq!(stem_for(Path::new("/downloads/Pack.tar.gz")), "Pack");
+        assert_eq!(stem_for(Path::new("/downloads/Pack.zip")), "Pack");
+    }
+}
@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::manifest::{self, AssetFile, AssetManifest, ManifestIssue};
+use crate::{jobs, storage};
+
+const OUTPUT_DIR: &str = "job-output";
+const KEEP_RESULTS: usize = 20;
+const DEFAULT_PAGE_SIZE: usize = 500;
+const INDEX_FILE: &str = "index.json";
+// Assets between recorded offsets; a page read skips at most this many lines.
+const OFFSET_STRIDE: usize = 256;
+
+// Totals returned with a result in place of the manifests themselves.
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct ManifestSummary {
+    pub manifest_count: u64,
+    pub asset_count: u64,
+    pub total_bytes: u64,
+}
+
+impl ManifestSummary {
+    pub fn of(manifests: &[AssetManifest]) -> Self {
+        let assets = manifests.iter().flat_map(|manifest| &manifest.assets);
+        ManifestSummary {
+            manifest_count: manifests.len() as u64,
+            asset_count: assets.clone().count() as u64,
+            total_bytes: assets.map(|asset| asset.size_bytes).sum(),
+        }
+    }
+}
+
+// A saved result is a folder holding each manifest's assets as JSON lines,
+// `<index>.jsonl`, and an index with the manifests' other fields and the
+// byte offset of every `OFFSET_STRIDE`th asset line.
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexEntry {
+    manifest: AssetManifest,
+    total_assets: usize,
+    offsets: Vec<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestPage {
+    pub manifest: AssetManifest,
+    pub offset: usize,
+    pub total_assets: usize,
+}
+
+fn output_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = storage::data_path(app, OUTPUT_DIR)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+// A tool's stdout, written to disk as it arrives so a large manifest is never
+// held in memory as text. The file is removed when the spool is dropped.
+pub struct Spool {
+    path: PathBuf,
+    file: Option<BufWriter<File>>,
+    error: Option<String>,
+}
+
+impl Spool {
+    pub fn create(app: &AppHandle, job_id: &str) -> Result<Self, String> {
+        let path = output_dir(app)?.join(format!("{}.stdout", job_id));
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        Ok(Spool {
+            path,
+            file: Some(BufWriter::new(file)),
+            error: None,
+        })
+    }
+
+    // A failed write is reported by `parse`, so the process output is still
+    // drained and the job finishes normally.
+    pub fn write(&mut self, text: &str) {
+        let (Some(file), None) = (&mut self.file, &self.error) else {
+            return;
+        };
+        if let Err(e) = file.write_all(text.as_bytes()) {
+            self.error = Some(format!("Failed to write {}: {}", self.path.display(), e));
+        }
+    }
+
+    pub fn parse(&mut self) -> Result<Result<Vec<AssetManifest>, Vec<ManifestIssue>>, String> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        let read_err = |e: std::io::Error| format!("Failed to read {}: {}", self.path.display(), e);
+        let Some(writer) = &mut self.file else {
+            return Ok(Ok(Vec::new()));
+        };
+        writer.flush().map_err(read_err)?;
+        let file = writer.get_mut();
+        file.seek(SeekFrom::Start(0)).map_err(read_err)?;
+        Ok(manifest::parse(BufReader::new(file)))
+    }
+}
+
+impl Drop for Spool {
+    fn drop(&mut self) {
+        // Close the file first; Windows cannot remove it while open.
+        self.file.take();
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn result_dir(app: &AppHandle, handle: &str) -> Result<PathBuf, String> {
+    if handle.is_empty()
+        || !handle
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid manifest handle: {}", handle));
+    }
+    Ok(output_dir(app)?.join(handle))
+}
+
+fn assets_file(index: usize) -> String {
+    format!("{}.jsonl", index)
+}
+
+fn write_result(dir: &Path, manifests: &[AssetManifest]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut entries = Vec::with_capacity(manifests.len());
+    for (index, manifest) in manifests.iter().enumerate() {
+        let mut writer = BufWriter::new(File::create(dir.join(assets_file(index)))?);
+        let mut written = 0u64;
+        let mut offsets = Vec::new();
+        for (position, asset) in manifest.assets.iter().enumerate() {
+            if position % OFFSET_STRIDE == 0 {
+                offsets.push(written);
+            }
+            let mut line = serde_json::to_vec(asset)?;
+            line.push(b'\n');
+            writer.write_all(&line)?;
+            written += line.len() as u64;
+        }
+        writer.flush()?;
+        entries.push(IndexEntry {
+            manifest: AssetManifest {
+                pack_id: manifest.pack_id.clone(),
+                pack_name: manifest.pack_name.clone(),
+                root_path: manifest.root_path.clone(),
+                source: manifest.source.clone(),
+                license_link: manifest.license_link.clone(),
+                global_tags: manifest.global_tags.clone(),
+                assets: Vec::new(),
+            },
+            total_assets: manifest.assets.len(),
+            offsets,
+        });
+    }
+    let writer = BufWriter::new(File::create(dir.join(INDEX_FILE))?);
+    serde_json::to_writer(writer, &entries)?;
+    Ok(())
+}
+
+// Writes a job's checked manifests for `read_manifest_page` and returns their
+// handle. Job ids restart with each launch, so the handle is timestamped.
+pub fn save(app: &AppHandle, job_id: &str, manifests: &[AssetManifest]) -> Result<String, String> {
+    let handle = format!("{}-{}", jobs::now_millis(), job_id);
+    let dir = result_dir(app, &handle)?;
+    // Written beside the target and renamed, so a page is never read from a
+    // half-written result.
+    let tmp = dir.with_extension("tmp");
+    let written = write_result(&tmp, manifests)
+        .and_then(|_| fs::rename(&tmp, &dir))
+        .map_err(|e| format!("Failed to write {}: {}", dir.display(), e));
+    if written.is_err() {
+        let _ = fs::remove_dir_all(&tmp);
+    }
+    written?;
+    prune(app);
+    Ok(handle)
+}
+
+// Handles start with a timestamp, so name order is age order.
+fn prune(app: &AppHandle) {
+    let Ok(dir) = output_dir(app) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+    let mut results: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir() && path.extension().is_none())
+        .collect();
+    results.sort();
+    let excess = results.len().saturating_sub(KEEP_RESULTS);
+    for path in &results[..excess] {
+        let _ = fs::remove_dir_all(path);
+    }
+}
+
+// Reads only the index and the requested lines, starting from the nearest
+// recorded offset.
+fn read_page(
+    dir: &Path,
+    handle: &str,
+    index: usize,
+    offset: usize,
+    limit: usize,
+) -> Result<ManifestPage, String> {
+    let read_err = |e: String| format!("Failed to read {}: {}", dir.display(), e);
+    let file = File::open(dir.join(INDEX_FILE))
+        .map_err(|_| format!("No saved manifests for {}", handle))?;
+    let mut entries: Vec<IndexEntry> =
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| read_err(e.to_string()))?;
+    if index >= entries.len() {
+        return Err(format!("{} has no manifest {}", handle, index));
+    }
+    let IndexEntry {
+        mut manifest,
+        total_assets,
+        offsets,
+    } = entries.swap_remove(index);
+
+    if offset < total_assets && limit > 0 {
+        let mut file =
+            File::open(dir.join(assets_file(index))).map_err(|e| read_err(e.to_string()))?;
+        let start = offsets.get(offset / OFFSET_STRIDE).copied().unwrap_or(0);
+        file.seek(SeekFrom::Start(start))
+            .map_err(|e| read_err(e.to_string()))?;
+        manifest.assets = BufReader::new(file)
+            .lines()
+            .skip(offset % OFFSET_STRIDE)
+            .take(limit)
+            .map(|line| {
+                let line = line.map_err(|e| read_err(e.to_string()))?;
+                serde_json::from_str::<AssetFile>(&line).map_err(|e| read_err(e.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+    }
+    Ok(ManifestPage {
+        manifest,
+        offset,
+        total_assets,
+    })
+}
+
+// Ingestion results carry a handle and totals rather than the manifests, so a
+// pack with tens of thousands of files is fetched a page of assets at a time.
+#[tauri::command]
+pub async fn read_manifest_page(
+    app: AppHandle,
+    handle: String,
+    manifest: Option<usize>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<ManifestPage, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        read_page(
+            &result_dir(&app, &handle)?,
+            &handle,
+            manifest.unwrap_or(0),
+            offset.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+    })
+    .await
+    .map_err(|e| format!("Failed to read manifests: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::AssetFile;
+
+    fn manifest(assets: usize) -> AssetManifest {
+        AssetManifest {
+            pack_id: "pack".to_string(),
+            pack_name: "Pack".to_string(),
+            root_path: "/packs/pack".to_string(),
+            source: None,
+            license_link: None,
+            global_tags: vec!["rock".to_string()],
+            assets: (0..assets)
+                .map(|n| AssetFile {
+                    relative_path: format!("file_{}.png", n),
+                    file_type: "png".to_string(),
+                    size_bytes: n as u64,
+                    metadata: Default::default(),
+                    local_tags: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn paths(page: &ManifestPage) -> Vec<String> {
+        page.manifest
+            .assets
+            .iter()
+            .map(|asset| asset.relative_path.clone())
+            .collect()
+    }
+
+    #[test]
+    fn pages_span_offset_strides() {
+        let dir = std::env::temp_dir().join(format!("job-output-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        write_result(&dir, &[manifest(3), manifest(OFFSET_STRIDE * 2 + 10)]).unwrap();
+
+        let page = read_page(&dir, "h", 1, OFFSET_STRIDE - 2, 5).unwrap();
+        assert_eq!(page.total_assets, OFFSET_STRIDE * 2 + 10);
+        assert_eq!(page.manifest.global_tags, vec!["rock".to_string()]);
+        let expected: Vec<String> = (OFFSET_STRIDE - 2..OFFSET_STRIDE + 3)
+            .map(|n| format!("file_{}.png", n))
+            .collect();
+        assert_eq!(paths(&page), expected);
+
+        let tail = read_page(&dir, "h", 1, OFFSET_STRIDE * 2 + 8, 100).unwrap();
+        assert_eq!(tail.manifest.assets.len(), 2);
+        assert!(read_page(&dir, "h", 1, 10_000, 10)
+            .unwrap()
+            .manifest
+            .assets
+            .is_empty());
+        assert_eq!(paths(&read_page(&dir, "h", 0, 0, 10).unwrap()).len(), 3);
+        assert!(read_page(&dir, "h", 2, 0, 10).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,381 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_shell::process::CommandChild;
+
+use crate::error::Error;
+use crate::messages::Message;
+use crate::{access, log_batch, process_tree, IngestionResult, LogEntry};
+
+const RETAINED_OUTPUT_LINES: usize = 200;
+// Finished jobs stay pollable for a while after they end; older ones live on
+// only in the job history.
+const RETAINED_FINISHED_JOBS: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPhase {
+    Queued,
+    Starting,
+    Syncing,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+// Concise, pollable view of a job so the UI does not have to reconstruct
+// state from the log stream.
+#[derive(Debug, Serialize, Clone)]
+pub struct JobStatus {
+    pub(crate) id: String,
+    pub(crate) source: String,
+    pub(crate) name: Option<String>,
+    pub(crate) phase: JobPhase,
+    pub(crate) started_at: u64,
+    updated_at: u64,
+    pub(crate) finished_at: Option<u64>,
+    output_lines: u64,
+    stage: Option<String>,
+    progress_current: Option<u64>,
+    progress_total: Option<u64>,
+    manifest_count: Option<u64>,
+    pub(crate) asset_count: Option<u64>,
+    pub(crate) total_bytes: Option<u64>,
+    last_output: Option<String>,
+    pub(crate) last_error: Option<String>,
+    // Set for jobs that ran an external process.
+    pub(crate) exit_code: Option<i32>,
+}
+
+#[derive(Default)]
+pub struct JobRegistry {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<String, JobStatus>>,
+    // Handles of running ingestion processes, dropped once they exit.
+    children: Mutex<HashMap<String, CommandChild>>,
+    cancelled: Mutex<HashSet<String>>,
+    timed_out: Mutex<HashSet<String>>,
+    // The tail of each job's output, kept for support bundles.
+    output: Mutex<HashMap<String, VecDeque<String>>>,
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl JobRegistry {
+    pub fn create(
+        &self,
+        app: &AppHandle,
+        source: &str,
+        name: Option<String>,
+        phase: JobPhase,
+    ) -> String {
+        let id = format!("job-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let now = now_millis();
+        let status = JobStatus {
+            id: id.clone(),
+            source: source.to_string(),
+            name,
+            phase,
+            started_at: now,
+            updated_at: now,
+            finished_at: None,
+            output_lines: 0,
+            stage: None,
+            progress_current: None,
+            progress_total: None,
+            manifest_count: None,
+            asset_count: None,
+            total_bytes: None,
+            last_output: None,
+            last_error: None,
+            exit_code: None,
+        };
+        self.jobs.lock().unwrap().insert(id.clone(), status.clone());
+        let _ = app.emit("job-status", status);
+        id
+    }
+
+    pub fn get(&self, id: &str) -> Option<JobStatus> {
+        self.jobs.lock().unwrap().get(id).cloned()
+    }
+
+    pub fn list(&self) -> Vec<JobStatus> {
+        let mut jobs: Vec<JobStatus> = self.jobs.lock().unwrap().values().cloned().collect();
+        jobs.sort_by_key(|job| {
+            job.id
+                .trim_start_matches("job-")
+                .parse::<u64>()
+                .unwrap_or(0)
+        });
+        jobs
+    }
+
+    fn update(&self, app: &AppHandle, id: &str, emit: bool, f: impl FnOnce(&mut JobStatus)) {
+        let snapshot = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let Some(status) = jobs.get_mut(id) else {
+                return;
+            };
+            f(status);
+            status.updated_at = now_millis();
+            status.clone()
+        };
+        if emit {
+            let _ = app.emit("job-status", snapshot);
+        }
+    }
+
+    pub fn set_phase(&self, app: &AppHandle, id: &str, phase: JobPhase) {
+        self.update(app, id, true, |status| status.phase = phase);
+    }
+
+    // Time spent waiting in the queue is not part of the job's duration.
+    pub fn start(&self, app: &AppHandle, id: &str) {
+        self.update(app, id, true, |status| {
+            status.phase = JobPhase::Starting;
+            status.started_at = now_millis();
+        });
+    }
+
+    // Output lines arrive at a high rate, so they update the polled status
+    // without pushing an event each.
+    pub fn record_output(&self, app: &AppHandle, id: &str, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
+        }
+        self.update(app, id, false, |status| {
+            status.output_lines += 1;
+            status.last_output = Some(line.to_string());
+        });
+        let mut output = self.output.lock().unwrap();
+        let lines = output.entry(id.to_string()).or_default();
+        if lines.len() == RETAINED_OUTPUT_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line.to_string());
+    }
+
+    pub fn recent_output(&self, id: &str) -> Vec<String> {
+        self.output
+            .lock()
+            .unwrap()
+            .get(id)
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    // A cancel that arrives before the process is attached (for example while
+    // dependencies are syncing) is honoured here as soon as it spawns.
+    pub fn attach_child(&self, id: &str, child: CommandChild) {
+        if self.is_cancelled(id) {
+            kill_child(child);
+            return;
+        }
+        self.children.lock().unwrap().insert(id.to_string(), child);
+    }
+
+    pub fn release_child(&self, id: &str) {
+        self.children.lock().unwrap().remove(id);
+    }
+
+    pub fn is_cancelled(&self, id: &str) -> bool {
+        self.cancelled.lock().unwrap().contains(id)
+    }
+
+    pub fn cancel(&self, app: &AppHandle, id: &str) -> Result<(), String> {
+        match self.get(id) {
+            None => return Err(format!("Unknown job: {}", id)),
+            Some(status) if status.finished_at.is_some() => {
+                return Err(format!("Job {} has already finished", id))
+            }
+            Some(_) => {}
+        }
+        self.cancelled.lock().unwrap().insert(id.to_string());
+        let child = self.children.lock().unwrap().remove(id);
+        if let Some(child) = child {
+            kill_child(child);
+        }
+        log_batch::emit(
+            app,
+            LogEntry::new(
+                "cancelled",
+                Message::new("ingestion.cancelled").param("job", id),
+            ),
+        );
+        Ok(())
+    }
+
+    pub fn is_timed_out(&self, id: &str) -> bool {
+        self.timed_out.lock().unwrap().contains(id)
+    }
+
+    // Fired by the per-job timer. A process that already exited has released
+    // its handle, so a late timer is a no-op.
+    pub fn time_out(&self, app: &AppHandle, id: &str, seconds: u64) {
+        let Some(child) = self.children.lock().unwrap().remove(id) else {
+            return;
+        };
+        self.timed_out.lock().unwrap().insert(id.to_string());
+        kill_child(child);
+        log_batch::emit(
+            app,
+            LogEntry::new(
+                "timeout",
+                Message::new("ingestion.timeout").param("seconds", seconds),
+            ),
+        );
+    }
+
+    // Progress is polled like output lines; the per-event stream is
+    // `ingestion-progress`.
+    pub fn record_progress(&self, app: &AppHandle, id: &str, current: u64, total: Option<u64>) {
+        self.update(app, id, false, |status| {
+            status.progress_current = Some(current);
+            status.progress_total = total.or(status.progress_total);
+        });
+    }
+
+    pub fn record_exit(&self, app: &AppHandle, id: &str, code: Option<i32>) {
+        self.update(app, id, false, |status| status.exit_code = code);
+    }
+
+    pub fn record_stage(&self, app: &AppHandle, id: &str, stage: &str) {
+        self.update(app, id, true, |status| {
+            status.stage = Some(stage.to_string());
+        });
+    }
+
+    pub fn finish(&self, app: &AppHandle, id: &str, outcome: &Result<IngestionResult, Error>) {
+        let cancelled = self.cancelled.lock().unwrap().remove(id);
+        self.timed_out.lock().unwrap().remove(id);
+        self.update(app, id, true, |status| {
+            status.finished_at = Some(now_millis());
+            if cancelled {
+                status.phase = JobPhase::Cancelled;
+                return;
+            }
+            match outcome {
+                Ok(result) if result.success => {
+                    status.phase = JobPhase::Completed;
+                    if let Some(summary) = &result.summary {
+                        status.manifest_count = Some(summary.manifest_count);
+                        status.asset_count = Some(summary.asset_count);
+                        status.total_bytes = Some(summary.total_bytes);
+                    }
+                }
+                Ok(result) => {
+                    status.phase = JobPhase::Failed;
+                    status.last_error = result
+                        .error
+                        .as_deref()
+                        .and_then(|error| error.lines().rev().find(|l| !l.trim().is_empty()))
+                        .map(|line| line.trim().to_string())
+                        .or_else(|| status.last_output.clone());
+                }
+                Err(error) => {
+                    status.phase = JobPhase::Failed;
+                    status.last_error = Some(error.to_string());
+                }
+            }
+        });
+    }
+
+    // Drops all but the most recently finished jobs, with their output.
+    // Called once a finished job has been written to the history.
+    pub fn prune(&self) {
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut finished: Vec<(u64, String)> = jobs
+            .values()
+            .filter_map(|job| Some((job.finished_at?, job.id.clone())))
+            .collect();
+        if finished.len() <= RETAINED_FINISHED_JOBS {
+            return;
+        }
+        finished.sort();
+        let mut output = self.output.lock().unwrap();
+        for (_, id) in &finished[..finished.len() - RETAINED_FINISHED_JOBS] {
+            jobs.remove(id);
+            output.remove(id);
+        }
+    }
+}
+
+fn kill_child(child: CommandChild) {
+    process_tree::kill(child.pid());
+    let _ = child.kill();
+}
+
+pub fn registry(app: &AppHandle) -> State<'_, JobRegistry> {
+    app.state::<JobRegistry>()
+}
+
+#[tauri::command]
+pub fn get_job_status(registry: State<'_, JobRegistry>, id: String) -> Result<JobStatus, String> {
+    registry.get(&id).ok_or(format!("Unknown job: {}", id))
+}
+
+#[tauri::command]
+pub fn cancel_ingestion(app: AppHandle, job_id: String) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    registry(&app).cancel(&app, &job_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(id: u64, finished_at: Option<u64>) -> JobStatus {
+        JobStatus {
+            id: format!("job-{}", id),
+            source: "filesystem".to_string(),
+            name: None,
+            phase: JobPhase::Running,
+            started_at: 0,
+            updated_at: 0,
+            finished_at,
+            output_lines: 0,
+            stage: None,
+            progress_current: None,
+            progress_total: None,
+            manifest_count: None,
+            asset_count: None,
+            total_bytes: None,
+            last_output: None,
+            last_error: None,
+            exit_code: None,
+        }
+    }
+
+    #[test]
+    fn prune_keeps_running_and_recently_finished_jobs() {
+        let registry = JobRegistry::default();
+        {
+            let mut jobs = registry.jobs.lock().unwrap();
+            let mut output = registry.output.lock().unwrap();
+            for id in 0..RETAINED_FINISHED_JOBS as u64 + 10 {
+                let status = status(id, Some(1_000 + id));
+                output.insert(status.id.clone(), VecDeque::from(["line".to_string()]));
+                jobs.insert(status.id.clone(), status);
+            }
+            jobs.insert("job-999".to_string(), status(999, None));
+        }
+
+        registry.prune();
+        assert_eq!(registry.list().len(), RETAINED_FINISHED_JOBS + 1);
+        assert!(registry.get("job-9").is_none());
+        assert!(registry.recent_output("job-9").is_empty());
+        assert!(registry.get("job-10").is_some());
+        assert_eq!(registry.recent_output("job-10"), ["line"]);
+        assert!(registry.get("job-999").is_some());
+    }
+}
@@ -0,0 +1,263 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Emitter};
+
+use crate::{access, actions, storage};
+
+const BINDINGS_FILE: &str = "keybindings.json";
+
+const DEFAULT_BINDINGS: &[(&str, &str)] = &[
+    ("ingestion.run", "CmdOrCtrl+Enter"),
+    ("batch.discover", "CmdOrCtrl+Shift+O"),
+    ("jobs.status", "CmdOrCtrl+J"),
+];
+
+// Shortcuts owned by the OS or the webview's editing commands. Binding them
+// would either never fire or break copy/paste in text fields.
+const RESERVED: &[&str] = &[
+    "CmdOrCtrl+Q",
+    "CmdOrCtrl+W",
+    "CmdOrCtrl+A",
+    "CmdOrCtrl+C",
+    "CmdOrCtrl+V",
+    "CmdOrCtrl+X",
+    "CmdOrCtrl+Z",
+    "CmdOrCtrl+Shift+Z",
+    "Alt+F4",
+    "Alt+Tab",
+];
+
+const MODIFIERS: &[(&str, &[&str])] = &[
+    ("CmdOrCtrl", &["cmdorctrl", "commandorcontrol"]),
+    ("Ctrl", &["ctrl", "control"]),
+    ("Super", &["super", "cmd", "command", "meta"]),
+    ("Alt", &["alt", "option"]),
+    ("Shift", &["shift"]),
+];
+
+const NAMED_KEYS: &[&str] = &[
+    "Enter",
+    "Escape",
+    "Tab",
+    "Space",
+    "Backspace",
+    "Delete",
+    "Insert",
+    "Home",
+    "End",
+    "PageUp",
+    "PageDown",
+    "Up",
+    "Down",
+    "Left",
+    "Right",
+    "Plus",
+    "Minus",
+    "Comma",
+    "Period",
+    "Slash",
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Keybinding {
+    action: String,
+    accelerator: Option<String>,
+    is_default: bool,
+}
+
+struct Accelerator {
+    modifiers: Vec<&'static str>,
+    key: String,
+}
+
+impl Accelerator {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = raw.split('+').map(str::trim).collect();
+        let Some((key, modifier_parts)) = parts.split_last() else {
+            return Err(format!("Invalid shortcut: '{}'", raw));
+        };
+
+        let mut modifiers = Vec::new();
+        for part in modifier_parts {
+            let lower = part.to_lowercase();
+            let Some((name, _)) = MODIFIERS
+                .iter()
+                .find(|(_, aliases)| aliases.contains(&lower.as_str()))
+            else {
+                return Err(format!("Unknown modifier '{}' in '{}'", part, raw));
+            };
+            if modifiers.contains(name) {
+                return Err(format!("Duplicate modifier '{}' in '{}'", part, raw));
+            }
+            modifiers.push(*name);
+        }
+        modifiers.sort_by_key(|name| MODIFIERS.iter().position(|(m, _)| m == name));
+
+        let key = normalize_key(key).ok_or(format!("Unknown key '{}' in '{}'", key, raw))?;
+        let is_function_key = key.starts_with('F') && key.len() > 1;
+        if !is_function_key && modifiers.iter().all(|m| *m == "Shift") {
+            return Err(format!(
+                "'{}' needs a Ctrl, Cmd or Alt modifier so it does not swallow typing",
+                raw
+            ));
+        }
+        Ok(Accelerator { modifiers, key })
+    }
+
+    fn canonical(&self) -> String {
+        let mut parts: Vec<&str> = self.modifiers.clone();
+        parts.push(&self.key);
+        parts.join("+")
+    }
+
+    // CmdOrCtrl collides with Super on macOS and Ctrl elsewhere, so conflicts
+    // are compared on the platform-resolved form.
+    fn resolved(&self) -> String {
+        let platform = if cfg!(target_os = "macos") {
+            "Super"
+        } else {
+            "Ctrl"
+        };
+        let mut modifiers: Vec<&str> = self
+            .modifiers
+            .iter()
+            .map(|m| if *m == "CmdOrCtrl" { platform } else { m })
+            .collect();
+        modifiers.sort_by_key(|name| MODIFIERS.iter().position(|(m, _)| m == name));
+        modifiers.dedup();
+        modifiers.push(&self.key);
+        modifiers.join("+")
+    }
+}
+
+fn normalize_key(key: &str) -> Option<String> {
+    if key.chars().count() == 1 {
+        let c = key.chars().next()?;
+        return c
+            .is_ascii_alphanumeric()
+            .then(|| c.to_ascii_uppercase().to_string());
+    }
+    if let Some(number) = key
+        .strip_prefix('F')
+        .or_else(|| key.strip_prefix('f'))
+        .and_then(|n| n.parse::<u8>().ok())
+    {
+        return (1..=24).contains(&number).then(|| format!("F{}", number));
+    }
+    NAMED_KEYS
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(key))
+        .map(|name| name.to_string())
+}
+
+// Only overrides are stored; a `None` value unbinds a default.
+fn load_overrides(app: &AppHandle) -> BTreeMap<String, Option<String>> {
+    storage::load_json(app, BINDINGS_FILE)
+}
+
+fn effective(overrides: &BTreeMap<String, Option<String>>) -> Vec<Keybinding> {
+    let mut bindings: BTreeMap<String, Keybinding> = DEFAULT_BINDINGS
+        .iter()
+        .map(|(action, accelerator)| {
+            (
+                action.to_string(),
+                Keybinding {
+                    action: action.to_string(),
+                    accelerator: Some(accelerator.to_string()),
+                    is_default: true,
+                },
+            )
+        })
+        .collect();
+    for (action, accelerator) in overrides {
+        bindings.insert(
+            action.clone(),
+            Keybinding {
+                action: action.clone(),
+                accelerator: accelerator.clone(),
+                is_default: false,
+            },
+        );
+    }
+    bindings.into_values().collect()
+}
+
+fn check_conflicts(
+    bindings: &[Keybinding],
+    action: &str,
+    accelerator: &Accelerator,
+) -> Result<(), String> {
+    let resolved = accelerator.resolved();
+    let reserved = RESERVED
+        .iter()
+        .filter_map(|raw| Accelerator::parse(raw).ok())
+        .any(|reserved| reserved.resolved() == resolved);
+    if reserved {
+        return Err(format!(
+            "{} is reserved by the system",
+            accelerator.canonical()
+        ));
+    }
+
+    let taken = bindings.iter().find(|binding| {
+        binding.action != action
+            && binding
+                .accelerator
+                .as_deref()
+                .and_then(|raw| Accelerator::parse(raw).ok())
+                .is_some_and(|other| other.resolved() == resolved)
+    });
+    if let Some(binding) = taken {
+        return Err(format!(
+            "{} is already bound to {}",
+            accelerator.canonical(),
+            binding.action
+        ));
+    }
+    Ok(())
+}
+
+fn save_and_notify(
+    app: &AppHandle,
+    overrides: &BTreeMap<String, Option<String>>,
+) -> Result<Vec<Keybinding>, String> {
+    storage::save_json(app, BINDINGS_FILE, overrides)?;
+    let bindings = effective(overrides);
+    let _ = app.emit("keybindings-changed", bindings.clone());
+    Ok(bindings)
+}
+
+#[tauri::command]
+pub fn get_keybindings(app: AppHandle) -> Vec<Keybinding> {
+    effective(&load_overrides(&app))
+}
+
+#[tauri::command]
+pub fn set_keybinding(
+    app: AppHandle,
+    action: String,
+    accelerator: Option<String>,
+) -> Result<Vec<Keybinding>, String> {
+    access::ensure_writable(&app)?;
+    if !actions::is_registered(&action) {
+        return Err(format!("Unknown action: {}", action));
+    }
+
+    let mut overrides = load_overrides(&app);
+    let accelerator = match accelerator.filter(|raw| !raw.trim().is_empty()) {
+        Some(raw) => {
+            let parsed = Accelerator::parse(&raw)?;
+            check_conflicts(&effective(&overrides), &action, &parsed)?;
+            Some(parsed.canonical())
+        }
+        None => None,
+    };
+    overrides.insert(action, accelerator);
+    save_and_notify(&app, &overrides)
+}
+
+#[tauri::command]
+pub fn reset_keybindings(app: AppHandle) -> Result<Vec<Keybinding>, String> {
+    access::ensure_writable(&app)?;
+    save_and_notify(&app, &BTreeMap::new())
+}
@@ -1,7 +1,91 @@
+mod access;
+mod actions;
+mod archives;
+mod asset_types;
+mod audio;
+mod audio_metadata;
+mod batch;
+mod benchmark;
+mod bundles;
+mod catalog;
+mod credentials;
+mod dedupe;
+mod documents;
+mod environment;
+mod error;
+mod external;
+mod filesystem;
+mod hashing;
+mod history;
+mod inference;
+mod job_output;
+mod jobs;
+mod keybindings;
+mod log_batch;
+mod manifest;
+mod marketplace;
+mod memory;
+mod mesh;
+mod messages;
+mod metrics;
+mod mirror;
+mod naming;
+mod notifications;
+mod preflight;
+mod preview;
+mod priority;
+mod process_tree;
+mod profiles;
+mod progress;
+mod purchase_requests;
+mod queue;
+mod remote;
+mod resume;
+mod review;
+mod runtime;
+mod sandbox;
+mod search;
+mod session;
+mod startup;
+mod storage;
+mod support;
+mod sync_cache;
+mod tags;
+mod textures;
+mod uv_binary;
+mod uv_command;
+mod variant_groups;
+mod variants;
+mod watch;
+mod wishlist;
+
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_shell::{process::CommandEvent, ShellExt};
 
+use error::Error;
+use filesystem::{PackArgs, Scan};
+use job_output::{ManifestSummary, Spool};
+use jobs::JobPhase;
+use manifest::{AssetManifest, ManifestIssue};
+use messages::Message;
+use priority::ProcessPriority;
+use sandbox::SandboxPolicy;
+use uv_command::{DownloadFilters, MarketplaceArgs, UvCommand};
+
+// Lower-cased fragments of marketplace client output that mean the stored
+// login was rejected.
+const AUTH_FAILURE_MARKERS: &[&str] = &[
+    "401 unauthorized",
+    "401 client error",
+    "token expired",
+    "token has expired",
+    "invalid_grant",
+    "authentication failed",
+];
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct IngestionConfig {
     path: Option<String>,
@@ -10,7 +94,13 @@ pub struct IngestionConfig {
     tags: Vec<String>,
     license: Option<String>,
     download_strategy: Option<String>,
+    download_filters: Option<DownloadFilters>,
     output_dir: Option<String>,
+    priority: Option<ProcessPriority>,
+    force_sync: Option<bool>,
+    sandbox: Option<bool>,
+    asset_id: Option<String>,
+    timeout_secs: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -18,13 +108,35 @@ pub struct LogEntry {
     #[serde(rename = "type")]
     log_type: String,
     message: String,
+    code: &'static str,
+    params: BTreeMap<String, String>,
+}
+
+impl LogEntry {
+    fn new(log_type: &str, message: Message) -> Self {
+        LogEntry {
+            log_type: log_type.to_string(),
+            message: message.render(),
+            code: message.code(),
+            params: message.params().clone(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
 pub struct IngestionResult {
+    job_id: String,
     success: bool,
-    manifest_json: Option<String>,
+    // Kept in-process for the catalog; the webview pages through the saved
+    // copy named by `manifest_handle`.
+    #[serde(skip)]
+    manifests: Option<Vec<AssetManifest>>,
+    manifest_handle: Option<String>,
+    summary: Option<ManifestSummary>,
     error: Option<String>,
+    error_code: Option<&'static str>,
+    validation_errors: Vec<ManifestIssue>,
+    timed_out: bool,
 }
 
 #[tauri::command]
@@ -32,200 +144,824 @@ async fn run_ingestion(
     app: AppHandle,
     config: IngestionConfig,
     ingestion_path: String,
-) -> Result<IngestionResult, String> {
-    match config.source.as_str() {
-        "filesystem" => run_filesystem_ingestion(app, config, ingestion_path).await,
-        "fab" | "uas" => run_marketplace_ingestion(app, config, ingestion_path).await,
-        _ => Err(format!("Unknown source type: {}", config.source)),
+) -> Result<IngestionResult, Error> {
+    access::ensure_writable(&app)?;
+    run_ingestion_job(app, config, ingestion_path).await
+}
+
+async fn run_ingestion_job(
+    app: AppHandle,
+    config: IngestionConfig,
+    ingestion_path: String,
+) -> Result<IngestionResult, Error> {
+    let job_id = jobs::registry(&app).create(
+        &app,
+        &config.source,
+        config.name.clone(),
+        JobPhase::Starting,
+    );
+    execute_ingestion_job(app, config, ingestion_path, job_id).await
+}
+
+async fn execute_ingestion_job(
+    app: AppHandle,
+    config: IngestionConfig,
+    ingestion_path: String,
+    job_id: String,
+) -> Result<IngestionResult, Error> {
+    // Jobs started by a watch folder have no checkout of their own.
+    if !ingestion_path.is_empty() {
+        session::remember_ingestion_path(&app, &ingestion_path);
     }
+
+    let recorded = (config.clone(), ingestion_path.clone());
+    let outcome = match config.source.as_str() {
+        "filesystem" => run_filesystem_ingestion(app.clone(), config, job_id.clone()).await,
+        "fab" | "uas" => {
+            run_marketplace_ingestion(app.clone(), config, ingestion_path, job_id.clone()).await
+        }
+        _ => Err(Error::InvalidConfig(format!(
+            "Unknown source type: {}",
+            config.source
+        ))),
+    };
+
+    // Deliver the job's last lines before its result.
+    log_batch::flush(&app);
+    let registry = jobs::registry(&app);
+    registry.finish(&app, &job_id, &outcome);
+    resume::finish(
+        &app,
+        &job_id,
+        matches!(&outcome, Ok(IngestionResult { success: true, .. })),
+    );
+    if let Some(status) = registry.get(&job_id) {
+        metrics::record(&app, &status);
+        notifications::job_finished(&app, &status);
+        history::record(&app, &status, recorded.0, recorded.1, &outcome);
+    }
+    registry.prune();
+    if let Ok(IngestionResult {
+        success: true,
+        manifests: Some(manifests),
+        ..
+    }) = &outcome
+    {
+        catalog::record_ingestion(&app, manifests);
+        mirror::mirror_manifests(&app, manifests);
+    }
+    outcome
 }
 
+// Folder packs are scanned in-process, so they work without uv or a checkout
+// of the ingestion project.
 async fn run_filesystem_ingestion(
     app: AppHandle,
     config: IngestionConfig,
-    ingestion_path: String,
-) -> Result<IngestionResult, String> {
-    let path = config.path.ok_or("Path is required for filesystem source")?;
-    let name = config.name.ok_or("Name is required for filesystem source")?;
-
-    let mut args = vec![
-        "run".to_string(),
-        "ingest".to_string(),
-        "--path".to_string(),
-        path,
-        "--name".to_string(),
-        name,
-        "--source".to_string(),
-        "filesystem".to_string(),
-    ];
-
-    if !config.tags.is_empty() {
-        args.push("--tags".to_string());
-        args.extend(config.tags.iter().cloned());
-    }
-
-    if let Some(license) = &config.license {
-        if !license.is_empty() {
-            args.push("--license".to_string());
-            args.push(license.clone());
+    job_id: String,
+) -> Result<IngestionResult, Error> {
+    let path = config.path.ok_or(Error::InvalidConfig(
+        "Path is required for filesystem source".to_string(),
+    ))?;
+    let name = config.name.ok_or(Error::InvalidConfig(
+        "Name is required for filesystem source".to_string(),
+    ))?;
+    let tags = tags::normalize_all(&config.tags).map_err(Error::InvalidConfig)?;
+
+    let license = config
+        .license
+        .filter(|license| !license.is_empty())
+        .or_else(|| detect_pack_license(&app, &path));
+
+    if jobs::registry(&app).is_cancelled(&job_id) {
+        return Ok(cancelled_result(job_id));
+    }
+    jobs::registry(&app).set_phase(&app, &job_id, JobPhase::Running);
+    let timeout = config.timeout_secs.map(Duration::from_secs);
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let scan_app = app.clone();
+    let scan_job = job_id.clone();
+    let scan = tauri::async_runtime::spawn_blocking(move || {
+        filesystem::scan(
+            &scan_app,
+            &scan_job,
+            &path,
+            PackArgs {
+                name: &name,
+                source: &config.source,
+                tags: &tags,
+                license: license.as_deref(),
+                output_dir: config.output_dir.as_deref(),
+            },
+            deadline,
+        )
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Scan failed: {}", e)))??;
+
+    match scan {
+        Scan::Done(manifest) => Ok(checked_result(
+            &app,
+            job_id,
+            manifest::check(vec![manifest]),
+        )),
+        Scan::Cancelled => Ok(cancelled_result(job_id)),
+        Scan::TimedOut => {
+            let seconds = timeout.map_or(0, |timeout| timeout.as_secs());
+            log_batch::emit(
+                &app,
+                LogEntry::new(
+                    "timeout",
+                    Message::new("ingestion.timeout").param("seconds", seconds),
+                ),
+            );
+            Ok(timed_out_result(job_id))
         }
     }
+}
+
+fn sandbox_policy(
+    app: &AppHandle,
+    config: &IngestionConfig,
+    ingestion_path: &str,
+    job_id: &str,
+) -> Result<Option<SandboxPolicy>, Error> {
+    if !sandbox::enabled_for(app, &config.source, config.sandbox) {
+        return Ok(None);
+    }
+    SandboxPolicy::for_ingestion(app, job_id, ingestion_path, config.output_dir.as_deref())
+        .map(Some)
+        .map_err(Error::Internal)
+}
 
-    run_uv_command(app, args, ingestion_path).await
+fn detect_pack_license(app: &AppHandle, path: &str) -> Option<String> {
+    let detected = documents::harvest(std::path::Path::new(path)).detected_license?;
+    log_batch::emit(
+        app,
+        LogEntry::new(
+            "info",
+            Message::new("ingestion.license_detected")
+                .param("license", detected.id)
+                .param("path", &detected.source_path),
+        ),
+    );
+    Some(detected.link.to_string())
 }
 
 async fn run_marketplace_ingestion(
     app: AppHandle,
     config: IngestionConfig,
     ingestion_path: String,
-) -> Result<IngestionResult, String> {
-    let _ = app.emit(
-        "ingestion-log",
-        LogEntry {
-            log_type: "info".to_string(),
-            message: format!("Syncing {} dependencies...", config.source),
-        },
-    );
-
-    run_uv_sync(&app, &ingestion_path, &config.source).await?;
+    job_id: String,
+) -> Result<IngestionResult, Error> {
+    environment::warn_on_drift(&app, &ingestion_path);
+    jobs::registry(&app).set_phase(&app, &job_id, JobPhase::Syncing);
+    let force_sync = config.force_sync.unwrap_or(false);
+    if !force_sync && sync_cache::is_fresh(&app, &ingestion_path, &config.source) {
+        log_batch::emit(
+            &app,
+            LogEntry::new(
+                "info",
+                Message::new("ingestion.sync_skipped").param("source", &config.source),
+            ),
+        );
+    } else {
+        log_batch::emit(
+            &app,
+            LogEntry::new(
+                "info",
+                Message::new("ingestion.sync_started").param("source", &config.source),
+            ),
+        );
+        run_uv_sync(&app, &ingestion_path, &config.source).await?;
+        sync_cache::mark_synced(&app, &ingestion_path, &config.source);
+    }
 
-    let mut args = vec![
-        "run".to_string(),
-        "python".to_string(),
-        "-m".to_string(),
-        "game_asset_tracker_ingestion.gui_helper".to_string(),
-        config.source.clone(),
-    ];
+    let resume_from =
+        resume::begin(&app, &job_id, &config, &ingestion_path).map_err(Error::Internal)?;
+    let command = UvCommand::gui_helper(
+        &ingestion_path,
+        MarketplaceArgs {
+            source: &config.source,
+            download_strategy: config.download_strategy.as_deref(),
+            output_dir: config.output_dir.as_deref(),
+            asset_id: config.asset_id.as_deref(),
+            filters: config.download_filters.as_ref(),
+            resume_from: resume_from.as_deref(),
+        },
+    )?;
 
-    if let Some(strategy) = &config.download_strategy {
-        args.push("--download-strategy".to_string());
-        args.push(strategy.clone());
+    // Unity download strategies print download records or nothing at all
+    // instead of asset manifests.
+    let prints_manifests = config.source != "uas"
+        || matches!(
+            config.download_strategy.as_deref(),
+            None | Some("metadata_only")
+        );
+    let priority = config.priority.unwrap_or_default();
+    let sandbox = sandbox_policy(&app, &config, &ingestion_path, &job_id)?;
+    let timeout = config.timeout_secs.map(Duration::from_secs);
+    let result = run_uv_command(
+        app,
+        command,
+        priority,
+        sandbox.clone(),
+        job_id,
+        timeout,
+        prints_manifests,
+    )
+    .await;
+    if let Some(policy) = &sandbox {
+        policy.discard();
     }
-
-    if let Some(output) = &config.output_dir {
-        args.push("--output-dir".to_string());
-        args.push(output.clone());
+    let result = result?;
+    // A rejected login fails the helper like any other error, but the fix is
+    // to sign in again rather than to retry.
+    if !result.success && result.error.as_deref().is_some_and(is_auth_failure) {
+        return Err(Error::AuthExpired {
+            marketplace: config.source,
+        });
     }
+    Ok(result)
+}
 
-    run_uv_command(app, args, ingestion_path).await
+fn is_auth_failure(output: &str) -> bool {
+    let output = output.to_lowercase();
+    AUTH_FAILURE_MARKERS
+        .iter()
+        .any(|marker| output.contains(marker))
 }
 
-async fn run_uv_sync(app: &AppHandle, working_dir: &str, extra: &str) -> Result<(), String> {
-    let shell = app.shell();
-    let args = vec!["sync", "--extra", extra];
-    
-    let output = shell
-        .command("uv")
+#[derive(Debug, Serialize, Clone)]
+struct SyncComplete {
+    source: String,
+    success: bool,
+}
+
+// Resolving dependencies can take minutes, so uv's output is forwarded to the
+// log as it arrives rather than collected at the end.
+async fn run_uv_sync(app: &AppHandle, working_dir: &str, extra: &str) -> Result<(), Error> {
+    let (args, working_dir) = UvCommand::sync(working_dir, extra)?.into_parts();
+
+    let (mut rx, _child) = uv_binary::command(app)
         .args(&args)
         .current_dir(working_dir)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run uv sync: {}", e))?;
+        .spawn()
+        .map_err(|e| Error::UvUnavailable(e.to_string()))?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Dependency sync failed: {}", stderr));
+    let mut stderr_buffer = String::new();
+    let mut outcome = Err(Error::Process("uv sync ended unexpectedly".to_string()));
+    while let Some(event) = rx.recv().await {
+        let (log_type, line) = match event {
+            CommandEvent::Stdout(line) => ("stdout", line),
+            CommandEvent::Stderr(line) => ("stderr", line),
+            CommandEvent::Terminated(payload) => {
+                outcome = if payload.code == Some(0) {
+                    Ok(())
+                } else {
+                    Err(Error::SyncFailed(stderr_buffer.clone()))
+                };
+                break;
+            }
+            CommandEvent::Error(err) => {
+                outcome = Err(Error::Process(format!("Failed to run uv sync: {}", err)));
+                break;
+            }
+            _ => continue,
+        };
+        let text = String::from_utf8_lossy(&line).trim_end().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        if log_type == "stderr" {
+            stderr_buffer.push_str(&text);
+            stderr_buffer.push('\n');
+        }
+        log_batch::emit(
+            app,
+            LogEntry::new(
+                log_type,
+                Message::new("ingestion.output").param("line", text),
+            ),
+        );
     }
-    Ok(())
+
+    let _ = app.emit(
+        "sync-complete",
+        SyncComplete {
+            source: extra.to_string(),
+            success: outcome.is_ok(),
+        },
+    );
+    outcome
 }
 
 async fn run_uv_command(
     app: AppHandle,
-    args: Vec<String>,
-    working_dir: String,
-) -> Result<IngestionResult, String> {
-    let shell = app.shell();
-    let command = shell
-        .command("uv")
+    command: UvCommand,
+    priority: ProcessPriority,
+    sandbox: Option<SandboxPolicy>,
+    job_id: String,
+    timeout: Option<Duration>,
+    prints_manifests: bool,
+) -> Result<IngestionResult, Error> {
+    let mut env = command.env();
+    env.extend(sandbox.iter().flat_map(SandboxPolicy::env));
+    let (args, working_dir) = command.into_parts();
+    let (program, args) = match &sandbox {
+        Some(policy) => {
+            sandbox::wrap(&uv_binary::program(&app), args, policy).map_err(Error::Process)?
+        }
+        None => (uv_binary::program(&app), args),
+    };
+
+    if jobs::registry(&app).is_cancelled(&job_id) {
+        return Ok(cancelled_result(job_id));
+    }
+
+    let mut stdout = if prints_manifests {
+        Some(Spool::create(&app, &job_id).map_err(Error::Internal)?)
+    } else {
+        None
+    };
+
+    let command = uv_binary::with_env(&app, app.shell().command(program))
         .args(&args)
+        .envs(env)
         .current_dir(&working_dir);
 
-    let (mut rx, _child) = command.spawn().map_err(|e| format!("Failed to spawn: {}", e))?;
+    let (mut rx, child) = command
+        .spawn()
+        .map_err(|e| Error::UvUnavailable(e.to_string()))?;
+    let pid = child.pid();
+    jobs::registry(&app).attach_child(&job_id, child);
+    jobs::registry(&app).set_phase(&app, &job_id, JobPhase::Running);
+    if let Some(timeout) = timeout {
+        let app = app.clone();
+        let job_id = job_id.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(timeout);
+            jobs::registry(&app).time_out(&app, &job_id, timeout.as_secs());
+        });
+    }
+
+    if let Err(err) = priority::apply(pid, priority) {
+        log_batch::emit(
+            &app,
+            LogEntry::new(
+                "warn",
+                Message::new("ingestion.priority_failed").param("reason", err),
+            ),
+        );
+    }
 
-    let mut stdout_buffer = String::new();
     let mut stderr_buffer = String::new();
+    let mut held = memory::reserve(&app, memory::Pool::JobOutput, 0);
 
     while let Some(event) = rx.recv().await {
         match event {
             CommandEvent::Stdout(line) => {
-                let text = String::from_utf8_lossy(&line).to_string();
-                stdout_buffer.push_str(&text);
+                if let Some(stdout) = &mut stdout {
+                    stdout.write(&String::from_utf8_lossy(&line));
+                }
             }
             CommandEvent::Stderr(line) => {
                 let text = String::from_utf8_lossy(&line).to_string();
+                if let Some(event) = progress::parse(&text) {
+                    progress::emit(&app, &job_id, event);
+                    continue;
+                }
                 stderr_buffer.push_str(&text);
                 stderr_buffer.push('\n');
-                let _ = app.emit(
-                    "ingestion-log",
-                    LogEntry {
-                        log_type: "stderr".to_string(),
-                        message: text,
-                    },
+                held.resize(stderr_buffer.len() as u64);
+                jobs::registry(&app).record_output(&app, &job_id, &text);
+                log_batch::emit(
+                    &app,
+                    LogEntry::new(
+                        "stderr",
+                        Message::new("ingestion.output").param("line", text),
+                    ),
                 );
             }
             CommandEvent::Terminated(payload) => {
+                let registry = jobs::registry(&app);
+                registry.release_child(&job_id);
+                registry.record_exit(&app, &job_id, payload.code);
+                if registry.is_cancelled(&job_id) {
+                    return Ok(cancelled_result(job_id));
+                }
+                if registry.is_timed_out(&job_id) {
+                    return Ok(timed_out_result(job_id));
+                }
                 if payload.code == Some(0) {
-                    return Ok(IngestionResult {
-                        success: true,
-                        manifest_json: Some(stdout_buffer),
-                        error: None,
-                    });
+                    let Some(stdout) = &mut stdout else {
+                        return Ok(IngestionResult {
+                            job_id,
+                            success: true,
+                            manifests: None,
+                            manifest_handle: None,
+                            summary: None,
+                            error: None,
+                            error_code: None,
+                            validation_errors: Vec::new(),
+                            timed_out: false,
+                        });
+                    };
+                    return manifest_result(&app, job_id, stdout);
                 } else {
+                    let error_code = match (payload.code, payload.signal) {
+                        (None, Some(_)) => "ingestion.terminated",
+                        _ => "ingestion.failed",
+                    };
                     return Ok(IngestionResult {
+                        job_id,
                         success: false,
-                        manifest_json: None,
+                        manifests: None,
+                        manifest_handle: None,
+                        summary: None,
                         error: Some(stderr_buffer),
+                        error_code: Some(error_code),
+                        validation_errors: Vec::new(),
+                        timed_out: false,
                     });
                 }
             }
             CommandEvent::Error(err) => {
-                return Err(format!("Command error: {}", err));
+                jobs::registry(&app).release_child(&job_id);
+                return Err(Error::Process(format!("Command error: {}", err)));
             }
             _ => {}
         }
     }
 
-    Err("Process ended unexpectedly".to_string())
+    jobs::registry(&app).release_child(&job_id);
+    Err(Error::Process("Process ended unexpectedly".to_string()))
+}
+
+// A run that exits cleanly but prints manifests that do not match the schema
+// is reported as failed, with the individual violations attached.
+fn manifest_result(
+    app: &AppHandle,
+    job_id: String,
+    stdout: &mut Spool,
+) -> Result<IngestionResult, Error> {
+    Ok(checked_result(
+        app,
+        job_id,
+        stdout.parse().map_err(Error::Internal)?,
+    ))
+}
+
+fn checked_result(
+    app: &AppHandle,
+    job_id: String,
+    manifests: Result<Vec<AssetManifest>, Vec<ManifestIssue>>,
+) -> IngestionResult {
+    match manifests {
+        Ok(mut manifests) => {
+            asset_types::annotate(&mut manifests);
+            variants::annotate(&mut manifests);
+            variant_groups::annotate(&mut manifests);
+            naming::annotate(app, &mut manifests);
+            mesh::annotate(app, &mut manifests);
+            audio::annotate(app, &mut manifests);
+            let manifest_handle = match job_output::save(app, &job_id, &manifests) {
+                Ok(handle) => Some(handle),
+                Err(reason) => {
+                    log_batch::emit(
+                        app,
+                        LogEntry::new(
+                            "warn",
+                            Message::new("job_output.save_failed").param("reason", reason),
+                        ),
+                    );
+                    None
+                }
+            };
+            IngestionResult {
+                job_id,
+                success: true,
+                summary: Some(ManifestSummary::of(&manifests)),
+                manifests: Some(manifests),
+                manifest_handle,
+                error: None,
+                error_code: None,
+                validation_errors: Vec::new(),
+                timed_out: false,
+            }
+        }
+        Err(issues) => IngestionResult {
+            job_id,
+            success: false,
+            manifests: None,
+            manifest_handle: None,
+            summary: None,
+            error: Some(manifest::summarize(&issues)),
+            error_code: Some("manifest.invalid"),
+            validation_errors: issues,
+            timed_out: false,
+        },
+    }
+}
+
+fn timed_out_result(job_id: String) -> IngestionResult {
+    IngestionResult {
+        job_id,
+        success: false,
+        manifests: None,
+        manifest_handle: None,
+        summary: None,
+        error: Some(Message::new("ingestion.timed_out").render()),
+        error_code: Some("ingestion.timed_out"),
+        validation_errors: Vec::new(),
+        timed_out: true,
+    }
+}
+
+fn cancelled_result(job_id: String) -> IngestionResult {
+    IngestionResult {
+        error: Some(
+            Message::new("ingestion.cancelled")
+                .param("job", &job_id)
+                .render(),
+        ),
+        job_id,
+        success: false,
+        manifests: None,
+        manifest_handle: None,
+        summary: None,
+        error_code: Some("ingestion.cancelled"),
+        validation_errors: Vec::new(),
+        timed_out: false,
+    }
 }
 
 #[tauri::command]
-fn validate_ingestion_path(path: String) -> Result<bool, String> {
+fn validate_ingestion_path(path: String) -> Result<bool, Error> {
     let pyproject = std::path::Path::new(&path).join("pyproject.toml");
     Ok(pyproject.exists())
 }
 
-#[tauri::command]
-fn check_source_available(source: String, ingestion_path: String) -> Result<bool, String> {
-    match source.as_str() {
-        "filesystem" => Ok(true),
-        "fab" => {
-            let pyproject = std::path::Path::new(&ingestion_path).join("pyproject.toml");
-            if !pyproject.exists() {
-                return Ok(false);
-            }
-            Ok(true)
+// Packages each marketplace extra has to pull in for its source to work.
+fn required_packages(source: &str) -> &'static [&'static str] {
+    match source {
+        "fab" => &["fab-api-client", "fab-egl-adapter"],
+        "uas" => &["uas-api-client", "uas-adapter"],
+        _ => &[],
+    }
+}
+
+// "fab-api-client>=2.1; python_version>'3.10'" names "fab-api-client".
+fn requirement_name(requirement: &str) -> String {
+    requirement
+        .split(|c: char| "<>=!~;[ @(".contains(c))
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+        .replace('_', "-")
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SourceAvailability {
+    available: bool,
+    // Why the source cannot be used; `None` when it is available.
+    reason: Option<String>,
+    missing_dependencies: Vec<String>,
+}
+
+impl SourceAvailability {
+    fn unavailable(reason: String, missing_dependencies: Vec<String>) -> Self {
+        SourceAvailability {
+            available: false,
+            reason: Some(reason),
+            missing_dependencies,
         }
-        "uas" => {
-            let pyproject = std::path::Path::new(&ingestion_path).join("pyproject.toml");
-            if !pyproject.exists() {
-                return Ok(false);
-            }
-            Ok(true)
+    }
+}
+
+// Marketplace sources run through a uv extra of the same name, so the extra
+// has to be declared and list the source's client packages.
+pub fn source_availability(source: &str, ingestion_path: &str) -> SourceAvailability {
+    if !matches!(source, "filesystem" | "fab" | "uas") {
+        return SourceAvailability::unavailable(
+            format!("Unknown source type: {}", source),
+            Vec::new(),
+        );
+    }
+    // Folder packs are scanned natively and need nothing from the project.
+    if source == "filesystem" {
+        return SourceAvailability {
+            available: true,
+            reason: None,
+            missing_dependencies: Vec::new(),
+        };
+    }
+    let path = std::path::Path::new(ingestion_path).join("pyproject.toml");
+    let required: Vec<String> = required_packages(source)
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            return SourceAvailability::unavailable(
+                format!("Cannot read {}: {}", path.display(), e),
+                required,
+            )
+        }
+    };
+    let pyproject: toml::Table = match text.parse() {
+        Ok(table) => table,
+        Err(e) => {
+            return SourceAvailability::unavailable(
+                format!("{} is not valid TOML: {}", path.display(), e),
+                required,
+            )
         }
-        _ => Ok(false),
+    };
+
+    let Some(extra) = pyproject
+        .get("project")
+        .and_then(|project| project.get("optional-dependencies"))
+        .and_then(|extras| extras.get(source))
+        .and_then(|extra| extra.as_array())
+    else {
+        return SourceAvailability::unavailable(
+            format!("pyproject.toml does not declare the \"{}\" extra", source),
+            required,
+        );
+    };
+    let declared: Vec<String> = extra
+        .iter()
+        .filter_map(|requirement| requirement.as_str())
+        .map(requirement_name)
+        .collect();
+    let missing: Vec<String> = required
+        .into_iter()
+        .filter(|name| !declared.contains(name))
+        .collect();
+    if missing.is_empty() {
+        SourceAvailability {
+            available: true,
+            reason: None,
+            missing_dependencies: missing,
+        }
+    } else {
+        SourceAvailability::unavailable(
+            format!("The \"{}\" extra is missing {}", source, missing.join(", ")),
+            missing,
+        )
     }
 }
 
+#[tauri::command]
+fn check_source_available(
+    source: String,
+    ingestion_path: String,
+) -> Result<SourceAvailability, Error> {
+    Ok(source_availability(&source, &ingestion_path))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(access::AccessMode::from_launch())
+        .manage(jobs::JobRegistry::default())
+        .manage(metrics::PerformanceHistory::default())
+        .manage(session::SessionStore::default())
+        .manage(profiles::ProfileStore::default())
+        .manage(queue::JobQueue::default())
+        .manage(catalog::Catalog::default())
+        .manage(preview::PreviewPool::default())
+        .manage(preview::PreviewCache::default())
+        .manage(memory::MemoryTracker::default())
+        .manage(runtime::RuntimeState::default())
+        .manage(startup::StartupState::default())
+        .manage(remote::RemoteAssist::default())
+        .manage(watch::WatchState::default())
+        .manage(history::JobHistory::default())
+        .manage(resume::ResumeState::default())
+        .manage(log_batch::LogBuffer::default())
+        .setup(|app| {
+            startup::start(app.handle());
+            watch::start(app.handle());
+            log_batch::start(app.handle());
+            Ok(())
+        })
+        .register_asynchronous_uri_scheme_protocol("preview", preview::handle)
         .invoke_handler(tauri::generate_handler![
             run_ingestion,
             validate_ingestion_path,
-            check_source_available
+            check_source_available,
+            documents::harvest_pack_documents,
+            documents::search_pack_documents,
+            asset_types::summarize_asset_types,
+            tags::get_tag_tree,
+            tags::find_assets_by_tag,
+            batch::discover_packs,
+            inference::infer_pack_details,
+            batch::run_batch_ingestion,
+            messages::get_message_catalog,
+            jobs::get_job_status,
+            jobs::cancel_ingestion,
+            queue::enqueue_ingestion,
+            queue::list_jobs,
+            queue::cancel_job,
+            queue::set_job_concurrency,
+            metrics::get_performance_history,
+            sync_cache::clear_uv_sync_cache,
+            environment::get_environment_info,
+            environment::verify_lockfile,
+            environment::pin_environment,
+            access::get_access_mode,
+            actions::list_actions,
+            actions::invoke_action,
+            keybindings::get_keybindings,
+            keybindings::set_keybinding,
+            keybindings::reset_keybindings,
+            session::get_session_state,
+            session::set_active_collection,
+            session::save_wizard_state,
+            profiles::list_ingestion_profiles,
+            profiles::save_ingestion_profile,
+            profiles::delete_ingestion_profile,
+            profiles::run_ingestion_with_profile,
+            mirror::get_manifest_mirror,
+            mirror::set_manifest_mirror,
+            catalog::save_manifest,
+            catalog::list_assets,
+            catalog::get_asset,
+            catalog::delete_asset,
+            marketplace::resolve_marketplace_url,
+            external::add_external_asset,
+            external::list_external_assets,
+            external::materialize,
+            search::search_assets,
+            purchase_requests::file_purchase_request,
+            purchase_requests::list_purchase_requests,
+            purchase_requests::decide_purchase_request,
+            wishlist::add_to_wishlist,
+            wishlist::set_wishlist_vote,
+            wishlist::list_wishlist,
+            wishlist::remove_from_wishlist,
+            review::get_review_gate,
+            review::set_review_gate,
+            review::approve_asset,
+            review::reject_asset,
+            naming::get_naming_rules,
+            naming::set_naming_rules,
+            preflight::preflight_check,
+            textures::validate_texture_sets,
+            uv_binary::detect_uv,
+            uv_binary::install_uv,
+            uv_binary::get_uv_settings,
+            uv_binary::set_uv_settings,
+            archives::list_archive,
+            archives::extract_archive,
+            dedupe::find_duplicates,
+            dedupe::hash_catalog_assets,
+            hashing::verify_pack_hashes,
+            memory::get_memory_report,
+            memory::set_memory_budget,
+            runtime::get_runtime_limits,
+            sandbox::get_sandbox_settings,
+            sandbox::set_sandbox_settings,
+            runtime::set_runtime_limits,
+            benchmark::run_benchmark,
+            startup::get_startup_status,
+            support::preview_support_bundle,
+            support::create_support_bundle,
+            remote::start_remote_assist,
+            remote::stop_remote_assist,
+            remote::get_remote_assist_status,
+            watch::list_watch_folders,
+            watch::add_watch_folder,
+            watch::remove_watch_folder,
+            notifications::get_notification_settings,
+            notifications::set_notification_settings,
+            history::get_job_history,
+            history::rerun_job,
+            job_output::read_manifest_page,
+            resume::list_resumable_jobs,
+            resume::resume_job,
+            resume::discard_download_checkpoint,
+            bundles::create_bundle,
+            variants::get_pack_variants,
+            variants::list_game_ready_assets,
+            credentials::store_credential,
+            credentials::get_credential_status,
+            credentials::delete_credential,
+            mesh::get_mesh_checks,
+            mesh::set_mesh_checks,
+            mesh::list_problem_meshes,
+            audio::get_audio_checks,
+            audio::set_audio_checks,
+            audio::list_flagged_audio
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,67 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::LogEntry;
+
+const EVENT: &str = "ingestion-log-batch";
+const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_BUFFERED: usize = 1000;
+
+#[derive(Debug, Serialize, Clone)]
+struct LogBatch {
+    entries: Vec<LogEntry>,
+    // Lines discarded because the buffer filled between flushes.
+    dropped: u64,
+}
+
+#[derive(Default)]
+struct Pending {
+    entries: VecDeque<LogEntry>,
+    dropped: u64,
+}
+
+// Log lines waiting for the next flush. A busy ingestion prints thousands of
+// lines a second, so the webview receives them as one event per interval.
+#[derive(Default)]
+pub struct LogBuffer {
+    pending: Mutex<Pending>,
+}
+
+// Once the buffer is full the oldest lines give way to the newest.
+pub fn emit(app: &AppHandle, entry: LogEntry) {
+    let buffer = app.state::<LogBuffer>();
+    let mut pending = buffer.pending.lock().unwrap();
+    if pending.entries.len() >= MAX_BUFFERED {
+        pending.entries.pop_front();
+        pending.dropped += 1;
+    }
+    pending.entries.push_back(entry);
+}
+
+pub fn flush(app: &AppHandle) {
+    let batch = {
+        let buffer = app.state::<LogBuffer>();
+        let mut pending = buffer.pending.lock().unwrap();
+        if pending.entries.is_empty() && pending.dropped == 0 {
+            return;
+        }
+        let pending = std::mem::take(&mut *pending);
+        LogBatch {
+            entries: pending.entries.into(),
+            dropped: pending.dropped,
+        }
+    };
+    let _ = app.emit(EVENT, batch);
+}
+
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(FLUSH_INTERVAL);
+        flush(&app);
+    });
+}
@@ -0,0 +1,271 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+
+const MAX_NAME_LEN: usize = 255;
+const MAX_SOURCE_LEN: usize = 255;
+const MAX_LICENSE_LEN: usize = 2048;
+const MAX_TAG_LEN: usize = 100;
+const MAX_FILE_TYPE_LEN: usize = 20;
+
+// Mirrors schemas/manifest.schema.json. Unknown fields are rejected just like
+// `additionalProperties: false` in the schema.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AssetManifest {
+    pub pack_id: String,
+    pub pack_name: String,
+    pub root_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<AssetSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_link: Option<String>,
+    #[serde(default)]
+    pub global_tags: Vec<String>,
+    pub assets: Vec<AssetFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AssetFile {
+    pub relative_path: String,
+    pub file_type: String,
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    pub local_tags: Vec<String>,
+}
+
+// The schema keeps `source` free-form. The labels written by the bundled
+// platforms are recognised; anything else, such as a filesystem pack labelled
+// "NAS", is kept verbatim.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum AssetSource {
+    UnityAssetStore,
+    Fab,
+    Filesystem,
+    Other(String),
+}
+
+const UAS_LABEL: &str = "UAS - Unity Asset Store";
+const FAB_LABEL: &str = "Fab - Epic Games";
+const FILESYSTEM_LABEL: &str = "Filesystem";
+
+impl AssetSource {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AssetSource::UnityAssetStore => UAS_LABEL,
+            AssetSource::Fab => FAB_LABEL,
+            AssetSource::Filesystem => FILESYSTEM_LABEL,
+            AssetSource::Other(label) => label,
+        }
+    }
+}
+
+impl From<String> for AssetSource {
+    fn from(label: String) -> Self {
+        match label.as_str() {
+            UAS_LABEL => AssetSource::UnityAssetStore,
+            FAB_LABEL => AssetSource::Fab,
+            FILESYSTEM_LABEL => AssetSource::Filesystem,
+            _ => AssetSource::Other(label),
+        }
+    }
+}
+
+impl From<AssetSource> for String {
+    fn from(source: AssetSource) -> Self {
+        source.as_str().to_string()
+    }
+}
+
+// One schema violation. `path` is a JSON pointer into the offending manifest,
+// with `manifest` giving its position in the tool output.
+#[derive(Debug, Serialize, Clone)]
+pub struct ManifestIssue {
+    manifest: usize,
+    path: String,
+    message: String,
+}
+
+impl ManifestIssue {
+    fn new(manifest: usize, path: impl Into<String>, message: impl Into<String>) -> Self {
+        ManifestIssue {
+            manifest,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+pub fn summarize(issues: &[ManifestIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| {
+            format!(
+                "manifest {} at {}: {}",
+                issue.manifest,
+                if issue.path.is_empty() {
+                    "/"
+                } else {
+                    &issue.path
+                },
+                issue.message
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| {
+            group.len() == len
+                && group
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+        })
+}
+
+fn check_tags(index: usize, path: &str, tags: &[String], issues: &mut Vec<ManifestIssue>) {
+    let mut seen = HashSet::new();
+    for (i, tag) in tags.iter().enumerate() {
+        let tag_path = format!("{}/{}", path, i);
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_LEN {
+            issues.push(ManifestIssue::new(
+                index,
+                &tag_path,
+                format!("tags must be 1-{} characters", MAX_TAG_LEN),
+            ));
+        }
+        if !seen.insert(tag) {
+            issues.push(ManifestIssue::new(index, tag_path, "duplicate tag"));
+        }
+    }
+}
+
+impl AssetManifest {
+    // Checks the constraints serde cannot express: patterns, lengths,
+    // uniqueness and the non-empty asset list.
+    fn validate(&self, index: usize, issues: &mut Vec<ManifestIssue>) {
+        if !is_uuid(&self.pack_id) {
+            issues.push(ManifestIssue::new(
+                index,
+                "/pack_id",
+                "must be a lowercase UUID",
+            ));
+        }
+        let name_len = self.pack_name.chars().count();
+        if name_len == 0 || name_len > MAX_NAME_LEN {
+            issues.push(ManifestIssue::new(
+                index,
+                "/pack_name",
+                format!("must be 1-{} characters", MAX_NAME_LEN),
+            ));
+        }
+        if self.root_path.is_empty() {
+            issues.push(ManifestIssue::new(index, "/root_path", "must not be empty"));
+        }
+        if let Some(source) = &self.source {
+            if source.as_str().chars().count() > MAX_SOURCE_LEN {
+                issues.push(ManifestIssue::new(
+                    index,
+                    "/source",
+                    format!("must be at most {} characters", MAX_SOURCE_LEN),
+                ));
+            }
+        }
+        if let Some(license) = &self.license_link {
+            if license.chars().count() > MAX_LICENSE_LEN {
+                issues.push(ManifestIssue::new(
+                    index,
+                    "/license_link",
+                    format!("must be at most {} characters", MAX_LICENSE_LEN),
+                ));
+            }
+        }
+        check_tags(index, "/global_tags", &self.global_tags, issues);
+
+        if self.assets.is_empty() {
+            issues.push(ManifestIssue::new(
+                index,
+                "/assets",
+                "must contain at least one asset",
+            ));
+        }
+        for (i, asset) in self.assets.iter().enumerate() {
+            let path = format!("/assets/{}", i);
+            if asset.relative_path.is_empty() {
+                issues.push(ManifestIssue::new(
+                    index,
+                    format!("{}/relative_path", path),
+                    "must not be empty",
+                ));
+            }
+            let file_type = &asset.file_type;
+            if file_type.is_empty()
+                || file_type.len() > MAX_FILE_TYPE_LEN
+                || !file_type
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            {
+                issues.push(ManifestIssue::new(
+                    index,
+                    format!("{}/file_type", path),
+                    format!(
+                        "must be 1-{} lowercase letters or digits",
+                        MAX_FILE_TYPE_LEN
+                    ),
+                ));
+            }
+            check_tags(
+                index,
+                &format!("{}/local_tags", path),
+                &asset.local_tags,
+                issues,
+            );
+        }
+    }
+}
+
+// The tool prints one manifest per pack, either as a single pretty-printed
+// document or as JSON lines, so the output is read as a stream of values.
+pub fn parse(output: impl io::Read) -> Result<Vec<AssetManifest>, Vec<ManifestIssue>> {
+    let mut manifests = Vec::new();
+    let mut issues = Vec::new();
+    let stream = serde_json::Deserializer::from_reader(output).into_iter::<AssetManifest>();
+    for (index, manifest) in stream.enumerate() {
+        match manifest {
+            Ok(manifest) => {
+                manifest.validate(index, &mut issues);
+                manifests.push(manifest);
+            }
+            Err(e) => {
+                issues.push(ManifestIssue::new(index, "", e.to_string()));
+                // The stream cannot resynchronise after a syntax error.
+                break;
+            }
+        }
+    }
+    if issues.is_empty() {
+        Ok(manifests)
+    } else {
+        Err(issues)
+    }
+}
+
+// Applies the same checks to manifests built in-process.
+pub fn check(manifests: Vec<AssetManifest>) -> Result<Vec<AssetManifest>, Vec<ManifestIssue>> {
+    let mut issues = Vec::new();
+    for (index, manifest) in manifests.iter().enumerate() {
+        manifest.validate(index, &mut issues);
+    }
+    if issues.is_empty() {
+        Ok(manifests)
+    } else {
+        Err(issues)
+    }
+}
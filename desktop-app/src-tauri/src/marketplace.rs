@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_PAGE_BYTES: usize = 2 * 1024 * 1024;
+const USER_AGENT: &str = concat!("game-asset-tracker/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Marketplace {
+    Fab,
+    UnityAssetStore,
+    Unreal,
+    Itch,
+}
+
+impl Marketplace {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Marketplace::Fab => "fab",
+            Marketplace::UnityAssetStore => "unity_asset_store",
+            Marketplace::Unreal => "unreal",
+            Marketplace::Itch => "itch",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            Marketplace::Fab,
+            Marketplace::UnityAssetStore,
+            Marketplace::Unreal,
+            Marketplace::Itch,
+        ]
+        .into_iter()
+        .find(|marketplace| marketplace.as_str() == value)
+    }
+
+    // Ingestion source to use once the item is owned, if the tool supports it.
+    pub fn ingestion_source(self) -> Option<&'static str> {
+        match self {
+            Marketplace::Fab => Some("fab"),
+            Marketplace::UnityAssetStore => Some("uas"),
+            Marketplace::Unreal | Marketplace::Itch => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MarketplaceItem {
+    marketplace: Marketplace,
+    item_id: String,
+    url: String,
+    name: Option<String>,
+    author: Option<String>,
+    description: Option<String>,
+    price: Option<String>,
+    currency: Option<String>,
+    thumbnail_url: Option<String>,
+    ingestion_source: Option<&'static str>,
+}
+
+struct ParsedUrl {
+    marketplace: Marketplace,
+    item_id: String,
+    author: Option<String>,
+}
+
+fn split_url(url: &str) -> Option<(String, Vec<String>)> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let rest = rest.split(['?', '#']).next()?;
+    let mut parts = rest.split('/');
+    let host = parts.next()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+    let segments = parts
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect();
+    Some((host, segments))
+}
+
+// Recognises listing URLs such as
+//   https://www.fab.com/listings/<uuid>
+//   https://assetstore.unity.com/packages/3d/characters/some-pack-123456
+//   https://www.unrealengine.com/marketplace/en-US/product/some-pack
+//   https://author.itch.io/some-pack
+fn parse_url(url: &str) -> Result<ParsedUrl, String> {
+    let unsupported = || format!("Not a recognised marketplace listing: {}", url);
+    let (host, segments) = split_url(url).ok_or_else(unsupported)?;
+    let position = |name: &str| segments.iter().position(|s| s == name);
+
+    let parsed = match host.as_str() {
+        "fab.com" => {
+            let index = position("listings").ok_or_else(unsupported)?;
+            ParsedUrl {
+                marketplace: Marketplace::Fab,
+                item_id: segments.get(index + 1).cloned().ok_or_else(unsupported)?,
+                author: None,
+            }
+        }
+        "assetstore.unity.com" => {
+            if segments.first().map(String::as_str) != Some("packages") {
+                return Err(unsupported());
+            }
+            let slug = segments.last().ok_or_else(unsupported)?;
+            let id = slug
+                .rsplit('-')
+                .next()
+                .filter(|id| id.chars().all(|c| c.is_ascii_digit()))
+                .ok_or_else(unsupported)?;
+            ParsedUrl {
+                marketplace: Marketplace::UnityAssetStore,
+                item_id: id.to_string(),
+                author: None,
+            }
+        }
+        "unrealengine.com" => {
+            let index = position("product").ok_or_else(unsupported)?;
+            ParsedUrl {
+                marketplace: Marketplace::Unreal,
+                item_id: segments.get(index + 1).cloned().ok_or_else(unsupported)?,
+                author: None,
+            }
+        }
+        host if host.ends_with(".itch.io") => ParsedUrl {
+            marketplace: Marketplace::Itch,
+            item_id: segments.first().cloned().ok_or_else(unsupported)?,
+            author: host.strip_suffix(".itch.io").map(str::to_string),
+        },
+        _ => return Err(unsupported()),
+    };
+    Ok(parsed)
+}
+
+// Identifies a listing without fetching it.
+pub fn listing_id(url: &str) -> Result<(Marketplace, String), String> {
+    parse_url(url.trim()).map(|parsed| (parsed.marketplace, parsed.item_id))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+        let preceded = lower[..start].ends_with(char::is_whitespace);
+        let rest = lower[search..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)?;
+        return Some(decode_entities(&value[1..1 + end]));
+    }
+    None
+}
+
+// Public listing pages expose their metadata through OpenGraph and product
+// meta tags, which is stable enough across all supported stores.
+fn meta_tags(html: &str) -> Vec<(String, String)> {
+    let lower = html.to_ascii_lowercase();
+    let mut tags = Vec::new();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find("<meta") {
+        let start = search + found;
+        let Some(end) = lower[start..].find('>') else {
+            break;
+        };
+        let tag = &html[start..start + end];
+        search = start + end;
+        let key = attribute(tag, "property").or_else(|| attribute(tag, "name"));
+        if let (Some(key), Some(content)) = (key, attribute(tag, "content")) {
+            tags.push((key.to_lowercase(), content.trim().to_string()));
+        }
+    }
+    tags
+}
+
+fn meta(tags: &[(String, String)], keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| {
+        tags.iter()
+            .find(|(name, value)| name == key && !value.is_empty())
+            .map(|(_, value)| value.clone())
+    })
+}
+
+async fn fetch_page(url: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", url, e))?
+    {
+        body.extend_from_slice(&chunk);
+        if body.len() > MAX_PAGE_BYTES {
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).to_string())
+}
+
+#[tauri::command]
+pub async fn resolve_marketplace_url(url: String) -> Result<MarketplaceItem, String> {
+    let url = url.trim().to_string();
+    let parsed = parse_url(&url)?;
+    let html = fetch_page(&url).await?;
+    let tags = meta_tags(&html);
+
+    Ok(MarketplaceItem {
+        marketplace: parsed.marketplace,
+        item_id: parsed.item_id,
+        name: meta(&tags, &["og:title", "twitter:title"]),
+        author: meta(&tags, &["author", "og:author", "twitter:creator"]).or(parsed.author),
+        description: meta(&tags, &["og:description", "description"]),
+        price: meta(&tags, &["product:price:amount", "og:price:amount"]),
+        currency: meta(&tags, &["product:price:currency", "og:price:currency"]),
+        thumbnail_url: meta(&tags, &["og:image", "twitter:image"]),
+        ingestion_source: parsed.marketplace.ingestion_source(),
+        url,
+    })
+}
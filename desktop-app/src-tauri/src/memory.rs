@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::{access, preview, storage};
+
+const MEMORY_BUDGET_FILE: &str = "memory-budget.json";
+const MB: u64 = 1024 * 1024;
+const MIN_TOTAL_MB: u64 = 64;
+const MAX_TOTAL_MB: u64 = 64 * 1024;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Pool {
+    // Normalized previews kept for replay.
+    PreviewCache,
+    // Preview bodies being read, decoded or sent to the webview.
+    PreviewBuffers,
+    // WAV files decoded for loudness analysis.
+    AudioAnalysis,
+    // Output collected from running ingestion tools.
+    JobOutput,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+struct Usage {
+    bytes: u64,
+    peak: u64,
+}
+
+// Only the app's own large allocations are counted, not the webview or the
+// allocator's overhead, so the total is a lower bound on real usage.
+#[derive(Default)]
+pub struct MemoryTracker {
+    pools: Mutex<BTreeMap<Pool, Usage>>,
+    // Read once, since it is checked on every reservation.
+    budget: Mutex<Option<MemoryBudget>>,
+}
+
+impl MemoryTracker {
+    fn adjust(&self, pool: Pool, add: u64, remove: u64) -> u64 {
+        let mut pools = self.pools.lock().unwrap();
+        let usage = pools.entry(pool).or_default();
+        usage.bytes = (usage.bytes + add).saturating_sub(remove);
+        usage.peak = usage.peak.max(usage.bytes);
+        pools.values().map(|usage| usage.bytes).sum()
+    }
+
+    fn total(&self) -> u64 {
+        self.pools
+            .lock()
+            .unwrap()
+            .values()
+            .map(|usage| usage.bytes)
+            .sum()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MemoryBudget {
+    // Once tracked usage passes this, cached previews are dropped and new
+    // previews wait for running ones to finish.
+    total_mb: u64,
+    preview_cache_mb: u64,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        MemoryBudget {
+            total_mb: 1024,
+            preview_cache_mb: 128,
+        }
+    }
+}
+
+impl MemoryBudget {
+    pub fn preview_cache_bytes(&self) -> u64 {
+        self.preview_cache_mb * MB
+    }
+}
+
+fn tracker(app: &AppHandle) -> State<'_, MemoryTracker> {
+    app.state::<MemoryTracker>()
+}
+
+pub fn budget(app: &AppHandle) -> MemoryBudget {
+    tracker(app)
+        .budget
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| storage::load_json(app, MEMORY_BUDGET_FILE))
+        .clone()
+}
+
+// Memory held for as long as the guard lives.
+pub struct Reservation {
+    app: AppHandle,
+    pool: Pool,
+    bytes: u64,
+}
+
+impl Reservation {
+    // Growing past the budget shrinks the preview cache to make room.
+    pub fn resize(&mut self, bytes: u64) {
+        let total = tracker(&self.app).adjust(self.pool, bytes, self.bytes);
+        self.bytes = bytes;
+        if total > budget(&self.app).total_mb * MB {
+            enforce(&self.app);
+        }
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        tracker(&self.app).adjust(self.pool, 0, self.bytes);
+    }
+}
+
+pub fn reserve(app: &AppHandle, pool: Pool, bytes: u64) -> Reservation {
+    let mut reservation = Reservation {
+        app: app.clone(),
+        pool,
+        bytes: 0,
+    };
+    reservation.resize(bytes);
+    reservation
+}
+
+// For long-lived holdings such as the preview cache, which change in steps
+// rather than being released all at once.
+pub fn record(app: &AppHandle, pool: Pool, added: u64, removed: u64) {
+    tracker(app).adjust(pool, added, removed);
+}
+
+pub fn over_budget(app: &AppHandle) -> bool {
+    tracker(app).total() > budget(app).total_mb * MB
+}
+
+pub fn enforce(app: &AppHandle) {
+    let limit = budget(app).total_mb * MB;
+    let excess = tracker(app).total().saturating_sub(limit);
+    if excess > 0 {
+        preview::shrink_cache(app, excess);
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PoolReport {
+    pool: Pool,
+    bytes: u64,
+    peak: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MemoryReport {
+    pools: Vec<PoolReport>,
+    tracked_bytes: u64,
+    // Whole-process resident size where the platform reports it cheaply.
+    resident_bytes: Option<u64>,
+    budget: MemoryBudget,
+    over_budget: bool,
+}
+
+#[cfg(target_os = "linux")]
+fn resident_bytes() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // SAFETY: sysconf has no preconditions.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(pages * u64::try_from(page_size).ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_bytes() -> Option<u64> {
+    None
+}
+
+#[tauri::command]
+pub fn get_memory_report(app: AppHandle) -> MemoryReport {
+    let pools: Vec<PoolReport> = tracker(&app)
+        .pools
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(pool, usage)| PoolReport {
+            pool: *pool,
+            bytes: usage.bytes,
+            peak: usage.peak,
+        })
+        .collect();
+    let tracked_bytes = pools.iter().map(|pool| pool.bytes).sum();
+    let budget = budget(&app);
+    MemoryReport {
+        pools,
+        tracked_bytes,
+        resident_bytes: resident_bytes(),
+        over_budget: tracked_bytes > budget.total_mb * MB,
+        budget,
+    }
+}
+
+#[tauri::command]
+pub fn set_memory_budget(app: AppHandle, budget: MemoryBudget) -> Result<MemoryReport, String> {
+    access::ensure_writable(&app)?;
+    if !(MIN_TOTAL_MB..=MAX_TOTAL_MB).contains(&budget.total_mb) {
+        return Err(format!(
+            "Memory budget must be between {} and {} MB",
+            MIN_TOTAL_MB, MAX_TOTAL_MB
+        ));
+    }
+    if budget.preview_cache_mb > budget.total_mb {
+        return Err("Preview cache cannot be larger than the total budget".to_string());
+    }
+    storage::save_json(&app, MEMORY_BUDGET_FILE, &budget)?;
+    *tracker(&app).budget.lock().unwrap() = Some(budget);
+    preview::shrink_cache(&app, 0);
+    enforce(&app);
+    Ok(get_memory_report(app))
+}
@@ -0,0 +1,338 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::catalog::{self, AssetRecord};
+use crate::manifest::AssetManifest;
+use crate::{access, runtime, storage};
+
+const MESH_CHECKS_FILE: &str = "mesh-checks.json";
+const WARNINGS_KEY: &str = "mesh_warnings";
+const MAX_MESH_BYTES: u64 = 256 * 1024 * 1024;
+const MAX_TRIANGLES: usize = 5_000_000;
+// Bounds outside this range (in metres) usually mean a unit mix-up on export.
+const MIN_EXTENT: f32 = 0.001;
+const MAX_EXTENT: f32 = 10_000.0;
+const MAX_NODE_SCALE: f32 = 1000.0;
+
+const GL_TRIANGLES: u64 = 4;
+const GL_UNSIGNED_BYTE: u64 = 5121;
+const GL_UNSIGNED_SHORT: u64 = 5123;
+const GL_UNSIGNED_INT: u64 = 5125;
+const GL_FLOAT: u64 = 5126;
+
+// Off by default: reading every mesh slows ingestion of large packs.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MeshChecks {
+    enabled: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MeshWarning {
+    DegenerateTriangles,
+    MissingUvs,
+    AbsurdScale,
+    NonManifold,
+}
+
+impl MeshWarning {
+    fn as_str(self) -> &'static str {
+        match self {
+            MeshWarning::DegenerateTriangles => "degenerate_triangles",
+            MeshWarning::MissingUvs => "missing_uvs",
+            MeshWarning::AbsurdScale => "absurd_scale",
+            MeshWarning::NonManifold => "non_manifold",
+        }
+    }
+}
+
+struct Gltf {
+    json: Value,
+    bin: Option<Vec<u8>>,
+}
+
+fn le_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn load(path: &Path, extension: &str) -> Option<Gltf> {
+    if fs::metadata(path).ok()?.len() > MAX_MESH_BYTES {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    if extension == "gltf" {
+        return Some(Gltf {
+            json: serde_json::from_slice(&bytes).ok()?,
+            bin: None,
+        });
+    }
+
+    // GLB: 12-byte header, then a JSON chunk and an optional BIN chunk.
+    if bytes.get(..4)? != b"glTF" {
+        return None;
+    }
+    let json_len = le_u32(&bytes, 12)? as usize;
+    let json = serde_json::from_slice(bytes.get(20..20 + json_len)?).ok()?;
+    let bin_start = 20 + json_len;
+    let bin = le_u32(&bytes, bin_start).and_then(|len| {
+        let start = bin_start + 8;
+        bytes.get(start..start + len as usize).map(<[u8]>::to_vec)
+    });
+    Some(Gltf { json, bin })
+}
+
+impl Gltf {
+    // Embedded data URIs are not decoded; such meshes only get the checks
+    // that need no vertex data.
+    fn buffer<'a>(&'a self, dir: &Path, index: usize) -> Option<Cow<'a, [u8]>> {
+        let buffer = self.json["buffers"].get(index)?;
+        match buffer["uri"].as_str() {
+            None => self.bin.as_deref().map(Cow::Borrowed),
+            Some(uri) if uri.starts_with("data:") || uri.contains("..") => None,
+            Some(uri) => fs::read(dir.join(uri)).ok().map(Cow::Owned),
+        }
+    }
+
+    // Returns the accessor's elements as raw byte slices.
+    fn elements(
+        &self,
+        dir: &Path,
+        accessor: &Value,
+        element_size: usize,
+    ) -> Option<(Vec<u8>, usize, usize)> {
+        let view = &self.json["bufferViews"][accessor["bufferView"].as_u64()? as usize];
+        let data = self.buffer(dir, view["buffer"].as_u64()? as usize)?;
+        let start = view["byteOffset"].as_u64().unwrap_or(0) as usize
+            + accessor["byteOffset"].as_u64().unwrap_or(0) as usize;
+        let stride = view["byteStride"]
+            .as_u64()
+            .map_or(element_size, |s| s as usize);
+        let count = accessor["count"].as_u64()? as usize;
+        let end = start + stride * count.saturating_sub(1) + element_size;
+        Some((data.get(start..end)?.to_vec(), stride, count))
+    }
+
+    fn indices(&self, dir: &Path, accessor: &Value) -> Option<Vec<u32>> {
+        let size = match accessor["componentType"].as_u64()? {
+            GL_UNSIGNED_BYTE => 1,
+            GL_UNSIGNED_SHORT => 2,
+            GL_UNSIGNED_INT => 4,
+            _ => return None,
+        };
+        let (bytes, stride, count) = self.elements(dir, accessor, size)?;
+        (0..count)
+            .map(|i| {
+                let at = i * stride;
+                match size {
+                    1 => Some(u32::from(bytes[at])),
+                    2 => Some(u32::from(u16::from_le_bytes([bytes[at], bytes[at + 1]]))),
+                    _ => le_u32(&bytes, at),
+                }
+            })
+            .collect()
+    }
+
+    fn positions(&self, dir: &Path, accessor: &Value) -> Option<Vec<[f32; 3]>> {
+        if accessor["componentType"].as_u64()? != GL_FLOAT || accessor["type"] != "VEC3" {
+            return None;
+        }
+        let (bytes, stride, count) = self.elements(dir, accessor, 12)?;
+        Some(
+            (0..count)
+                .map(|i| {
+                    let at = i * stride;
+                    let f = |o: usize| {
+                        f32::from_le_bytes(bytes[at + o..at + o + 4].try_into().unwrap())
+                    };
+                    [f(0), f(4), f(8)]
+                })
+                .collect(),
+        )
+    }
+}
+
+fn is_absurd(extent: f32) -> bool {
+    extent > MAX_EXTENT || (extent > 0.0 && extent < MIN_EXTENT)
+}
+
+fn is_degenerate(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> bool {
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let cross = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2] < 1e-12
+}
+
+fn check_triangles(positions: &[[f32; 3]], indices: &[u32], warnings: &mut Vec<MeshWarning>) {
+    let mut edges: HashMap<(u32, u32), u8> = HashMap::new();
+    let mut degenerate = false;
+    for triangle in indices.chunks_exact(3).take(MAX_TRIANGLES) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+        let corners = [a, b, c].map(|i| positions.get(i as usize).copied());
+        let [Some(pa), Some(pb), Some(pc)] = corners else {
+            continue;
+        };
+        if a == b || b == c || a == c || is_degenerate(pa, pb, pc) {
+            degenerate = true;
+            continue;
+        }
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            let count = edges.entry((from.min(to), from.max(to))).or_default();
+            *count = count.saturating_add(1);
+        }
+    }
+    if degenerate {
+        warnings.push(MeshWarning::DegenerateTriangles);
+    }
+    // An edge shared by more than two faces cannot be part of a manifold.
+    if edges.values().any(|count| *count > 2) {
+        warnings.push(MeshWarning::NonManifold);
+    }
+}
+
+// Only glTF is inspected; FBX is a proprietary format with no parser here.
+fn inspect(path: &Path, extension: &str) -> Vec<MeshWarning> {
+    let Some(gltf) = load(path, extension) else {
+        return Vec::new();
+    };
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut warnings = Vec::new();
+
+    let scaled = gltf.json["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|node| node["scale"].as_array())
+        .flatten()
+        .filter_map(Value::as_f64)
+        .any(|s| {
+            let s = s.abs() as f32;
+            s > MAX_NODE_SCALE || (s > 0.0 && s < 1.0 / MAX_NODE_SCALE)
+        });
+    if scaled {
+        warnings.push(MeshWarning::AbsurdScale);
+    }
+
+    let primitives = gltf.json["meshes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|mesh| mesh["primitives"].as_array())
+        .flatten()
+        .filter(|primitive| primitive["mode"].as_u64().unwrap_or(GL_TRIANGLES) == GL_TRIANGLES);
+    for primitive in primitives {
+        let attributes = &primitive["attributes"];
+        if attributes.get("TEXCOORD_0").is_none() {
+            warnings.push(MeshWarning::MissingUvs);
+        }
+        let Some(position) = attributes["POSITION"]
+            .as_u64()
+            .and_then(|index| gltf.json["accessors"].get(index as usize))
+        else {
+            continue;
+        };
+
+        // POSITION accessors are required to carry their bounds.
+        let bound = |key: &str| -> Option<Vec<f32>> {
+            position[key]
+                .as_array()?
+                .iter()
+                .map(|v| v.as_f64().map(|v| v as f32))
+                .collect()
+        };
+        if let (Some(min), Some(max)) = (bound("min"), bound("max")) {
+            let extent = min
+                .iter()
+                .zip(&max)
+                .map(|(lo, hi)| hi - lo)
+                .fold(0.0, f32::max);
+            if is_absurd(extent) {
+                warnings.push(MeshWarning::AbsurdScale);
+            }
+        }
+
+        let Some(positions) = gltf.positions(dir, position) else {
+            continue;
+        };
+        let indices = match primitive["indices"].as_u64() {
+            Some(index) => gltf.json["accessors"]
+                .get(index as usize)
+                .and_then(|accessor| gltf.indices(dir, accessor)),
+            None => Some((0..positions.len() as u32).collect()),
+        };
+        if let Some(indices) = indices {
+            check_triangles(&positions, &indices, &mut warnings);
+        }
+    }
+
+    warnings.sort();
+    warnings.dedup();
+    warnings
+}
+
+// Records problems as a comma-separated `mesh_warnings` metadata entry so the
+// catalog can be filtered for them.
+pub fn annotate(app: &AppHandle, manifests: &mut [AssetManifest]) {
+    let settings: MeshChecks = storage::load_json(app, MESH_CHECKS_FILE);
+    if !settings.enabled {
+        return;
+    }
+    let is_gltf = |file_type: &str| file_type == "gltf" || file_type == "glb";
+    let files: Vec<(PathBuf, &str)> = manifests
+        .iter()
+        .flat_map(|manifest| {
+            let root = Path::new(&manifest.root_path);
+            manifest
+                .assets
+                .iter()
+                .filter(|asset| is_gltf(&asset.file_type))
+                .map(move |asset| (root.join(&asset.relative_path), asset.file_type.as_str()))
+        })
+        .collect();
+    let results = runtime::parallel_map(app, &files, |(path, extension)| inspect(path, extension));
+
+    let assets = manifests
+        .iter_mut()
+        .flat_map(|manifest| manifest.assets.iter_mut())
+        .filter(|asset| is_gltf(&asset.file_type));
+    for (asset, warnings) in assets.zip(results) {
+        if warnings.is_empty() {
+            continue;
+        }
+        let joined = warnings
+            .iter()
+            .map(|warning| warning.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        asset.metadata.insert(WARNINGS_KEY.to_string(), joined);
+    }
+}
+
+#[tauri::command]
+pub fn get_mesh_checks(app: AppHandle) -> MeshChecks {
+    storage::load_json(&app, MESH_CHECKS_FILE)
+}
+
+#[tauri::command]
+pub fn set_mesh_checks(app: AppHandle, enabled: bool) -> Result<MeshChecks, String> {
+    access::ensure_writable(&app)?;
+    let settings = MeshChecks { enabled };
+    storage::save_json(&app, MESH_CHECKS_FILE, &settings)?;
+    Ok(settings)
+}
+
+#[tauri::command]
+pub fn list_problem_meshes(
+    app: AppHandle,
+    warning: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<AssetRecord>, String> {
+    catalog::list_flagged(&app, WARNINGS_KEY, warning.as_deref(), limit)
+}
@@ -0,0 +1,105 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::OnceLock;
+
+const DEFAULT_LOCALE: &str = "en";
+const BUNDLED_CATALOGS: &[(&str, &str)] = &[("en", include_str!("../locales/en.json"))];
+
+type Catalog = HashMap<String, String>;
+
+fn catalogs() -> &'static HashMap<&'static str, Catalog> {
+    static CATALOGS: OnceLock<HashMap<&'static str, Catalog>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        BUNDLED_CATALOGS
+            .iter()
+            .map(|(locale, json)| {
+                let catalog = serde_json::from_str(json)
+                    .unwrap_or_else(|e| panic!("invalid bundled catalog {}: {}", locale, e));
+                (*locale, catalog)
+            })
+            .collect()
+    })
+}
+
+// Accepts BCP 47 style tags and falls back from `pt-BR` to `pt` to English.
+fn resolve_locale(locale: &str) -> &'static str {
+    let catalogs = catalogs();
+    let normalized = locale.replace('_', "-").to_lowercase();
+    let language = normalized.split('-').next().unwrap_or_default();
+
+    let resolved = [normalized.as_str(), language]
+        .into_iter()
+        .find_map(|candidate| catalogs.get_key_value(candidate).map(|(key, _)| *key));
+    resolved.unwrap_or(DEFAULT_LOCALE)
+}
+
+// A user-facing string identified by a stable code. The frontend localizes it
+// from the catalog; `render` produces the English text for logs and fallbacks.
+#[derive(Debug, Serialize, Clone)]
+pub struct Message {
+    code: &'static str,
+    params: BTreeMap<String, String>,
+}
+
+impl Message {
+    pub fn new(code: &'static str) -> Self {
+        Message {
+            code,
+            params: BTreeMap::new(),
+        }
+    }
+
+    pub fn param(mut self, key: &str, value: impl ToString) -> Self {
+        self.params.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn params(&self) -> &BTreeMap<String, String> {
+        &self.params
+    }
+
+    pub fn render(&self) -> String {
+        let template = catalogs()
+            .get(DEFAULT_LOCALE)
+            .and_then(|catalog| catalog.get(self.code))
+            .map(String::as_str)
+            .unwrap_or(self.code);
+
+        self.params
+            .iter()
+            .fold(template.to_string(), |text, (key, value)| {
+                text.replace(&format!("{{{}}}", key), value)
+            })
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MessageCatalog {
+    locale: String,
+    messages: Catalog,
+}
+
+#[tauri::command]
+pub fn get_message_catalog(locale: String) -> Result<MessageCatalog, String> {
+    let resolved = resolve_locale(&locale);
+    let mut messages = catalogs().get(DEFAULT_LOCALE).cloned().unwrap_or_default();
+    if let Some(localized) = catalogs().get(resolved) {
+        messages.extend(localized.clone());
+    }
+
+    Ok(MessageCatalog {
+        locale: resolved.to_string(),
+        messages,
+    })
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::jobs::{JobPhase, JobStatus};
+use crate::storage;
+
+const HISTORY_FILE: &str = "performance-history.json";
+const MAX_RECORDS: usize = 1000;
+const RECENT_PER_SOURCE: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerformanceRecord {
+    source: String,
+    started_at: u64,
+    duration_ms: u64,
+    success: bool,
+    assets: u64,
+    bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SourcePerformance {
+    source: String,
+    runs: u64,
+    failures: u64,
+    failure_rate: f64,
+    average_duration_ms: u64,
+    // Only successful runs that produced assets contribute to throughput.
+    average_bytes_per_second: Option<f64>,
+    recent: Vec<PerformanceRecord>,
+}
+
+// Serializes read-modify-write cycles on the history file.
+#[derive(Default)]
+pub struct PerformanceHistory {
+    lock: Mutex<()>,
+}
+
+pub fn record(app: &AppHandle, status: &JobStatus) {
+    let Some(finished_at) = status.finished_at else {
+        return;
+    };
+    // User cancellations say nothing about how reliable a source is.
+    if status.phase == JobPhase::Cancelled {
+        return;
+    }
+    let record = PerformanceRecord {
+        source: status.source.clone(),
+        started_at: status.started_at,
+        duration_ms: finished_at.saturating_sub(status.started_at),
+        success: status.phase == JobPhase::Completed,
+        assets: status.asset_count.unwrap_or(0),
+        bytes: status.total_bytes.unwrap_or(0),
+    };
+
+    let history = app.state::<PerformanceHistory>();
+    let _guard = history.lock.lock().unwrap();
+    let mut records: Vec<PerformanceRecord> = storage::load_json(app, HISTORY_FILE);
+    records.push(record);
+    if records.len() > MAX_RECORDS {
+        records.drain(..records.len() - MAX_RECORDS);
+    }
+    let _ = storage::save_json(app, HISTORY_FILE, &records);
+}
+
+fn summarize(source: String, records: Vec<PerformanceRecord>) -> SourcePerformance {
+    let runs = records.len() as u64;
+    let failures = records.iter().filter(|r| !r.success).count() as u64;
+    let total_duration: u64 = records.iter().map(|r| r.duration_ms).sum();
+
+    let throughput: Vec<f64> = records
+        .iter()
+        .filter(|r| r.success && r.bytes > 0 && r.duration_ms > 0)
+        .map(|r| r.bytes as f64 / (r.duration_ms as f64 / 1000.0))
+        .collect();
+    let average_bytes_per_second = if throughput.is_empty() {
+        None
+    } else {
+        Some(throughput.iter().sum::<f64>() / throughput.len() as f64)
+    };
+
+    let recent_start = records.len().saturating_sub(RECENT_PER_SOURCE);
+    SourcePerformance {
+        source,
+        runs,
+        failures,
+        failure_rate: if runs == 0 {
+            0.0
+        } else {
+            failures as f64 / runs as f64
+        },
+        average_duration_ms: total_duration.checked_div(runs).unwrap_or(0),
+        average_bytes_per_second,
+        recent: records[recent_start..].to_vec(),
+    }
+}
+
+#[tauri::command]
+pub fn get_performance_history(
+    app: AppHandle,
+    history: State<'_, PerformanceHistory>,
+) -> Result<Vec<SourcePerformance>, String> {
+    let records: Vec<PerformanceRecord> = {
+        let _guard = history.lock.lock().unwrap();
+        storage::load_json(&app, HISTORY_FILE)
+    };
+
+    let mut by_source: BTreeMap<String, Vec<PerformanceRecord>> = BTreeMap::new();
+    for record in records {
+        by_source
+            .entry(record.source.clone())
+            .or_default()
+            .push(record);
+    }
+
+    Ok(by_source
+        .into_iter()
+        .map(|(source, records)| summarize(source, records))
+        .collect())
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::manifest::AssetManifest;
+use crate::messages::Message;
+use crate::{access, log_batch, storage, LogEntry};
+
+const MIRROR_FILE: &str = "manifest-mirror.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MirrorSettings {
+    directory: Option<String>,
+}
+
+// serde_json's default map is ordered by key, so re-serializing a parsed
+// manifest already sorts object keys. Assets are sorted by path as well so
+// re-ingesting an unchanged pack produces an identical file.
+fn canonicalize(mut manifest: Value) -> Value {
+    if let Some(assets) = manifest.get_mut("assets").and_then(Value::as_array_mut) {
+        assets.sort_by(|a, b| {
+            let path = |asset: &Value| asset["relative_path"].as_str().unwrap_or("").to_string();
+            path(a).cmp(&path(b))
+        });
+    }
+    manifest
+}
+
+fn mirror_path(directory: &Path, manifest: &Value) -> Result<PathBuf, String> {
+    let pack_id = manifest["pack_id"]
+        .as_str()
+        .filter(|id| !id.is_empty())
+        .ok_or("Manifest has no pack_id")?;
+    if !pack_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(format!("Unexpected pack_id: {}", pack_id));
+    }
+    Ok(directory.join(format!("{}.json", pack_id)))
+}
+
+fn write_manifest(directory: &Path, manifest: Value) -> Result<(), String> {
+    let path = mirror_path(directory, &manifest)?;
+    let mut json = serde_json::to_string_pretty(&canonicalize(manifest))
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    json.push('\n');
+
+    // Leave the file untouched when nothing changed so the Git history only
+    // shows real edits.
+    if fs::read_to_string(&path).ok().as_deref() == Some(json.as_str()) {
+        return Ok(());
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// Mirroring is best effort: a failure is reported in the log but never fails
+// the ingestion that produced the manifests.
+pub fn mirror_manifests(app: &AppHandle, manifests: &[AssetManifest]) {
+    let settings: MirrorSettings = storage::load_json(app, MIRROR_FILE);
+    let Some(directory) = settings.directory else {
+        return;
+    };
+    let directory = PathBuf::from(directory);
+
+    for manifest in manifests {
+        let result = serde_json::to_value(manifest)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))
+            .and_then(|manifest| write_manifest(&directory, manifest));
+        if let Err(reason) = result {
+            log_batch::emit(
+                app,
+                LogEntry::new(
+                    "warn",
+                    Message::new("mirror.failed")
+                        .param("directory", directory.display())
+                        .param("reason", reason),
+                ),
+            );
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_manifest_mirror(app: AppHandle) -> MirrorSettings {
+    storage::load_json(&app, MIRROR_FILE)
+}
+
+#[tauri::command]
+pub fn set_manifest_mirror(
+    app: AppHandle,
+    directory: Option<String>,
+) -> Result<MirrorSettings, String> {
+    access::ensure_writable(&app)?;
+    let directory = match directory.filter(|d| !d.trim().is_empty()) {
+        Some(directory) => {
+            let path = fs::canonicalize(&directory)
+                .map_err(|e| format!("Mirror directory {} is not accessible: {}", directory, e))?;
+            if !path.is_dir() {
+                return Err(format!("Mirror directory {} is not a directory", directory));
+            }
+            Some(path.to_string_lossy().to_string())
+        }
+        None => None,
+    };
+    let settings = MirrorSettings { directory };
+    storage::save_json(&app, MIRROR_FILE, &settings)?;
+    Ok(settings)
+}
@@ -0,0 +1,197 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::asset_types::{self, AssetType};
+use crate::manifest::AssetManifest;
+use crate::{access, storage};
+
+const NAMING_FILE: &str = "naming-rules.json";
+const VIOLATION_KEY: &str = "naming_violation";
+const SUGGESTION_KEY: &str = "naming_suggestion";
+
+// A rule applies to files of `asset_type` whose path matches `applies_to`,
+// and requires the file stem to match `pattern`. For example normal maps:
+//   { "asset_type": "texture", "applies_to": "(?i)normal|_n$",
+//     "pattern": "^T_.+_N$", "rename": "T_{stem}_N" }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NamingRule {
+    name: String,
+    asset_type: Option<AssetType>,
+    applies_to: Option<String>,
+    pattern: String,
+    // Template for the conventional name; `{stem}` is the original stem.
+    rename: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NamingSettings {
+    rules: Vec<NamingRule>,
+    // Installs use the suggested names instead of the originals.
+    auto_rename: bool,
+}
+
+struct CompiledRule<'a> {
+    rule: &'a NamingRule,
+    applies_to: Option<Regex>,
+    pattern: Regex,
+}
+
+fn compile(rule: &NamingRule) -> Result<CompiledRule<'_>, String> {
+    let regex = |source: &str| {
+        Regex::new(source)
+            .map_err(|e| format!("Invalid pattern in naming rule \"{}\": {}", rule.name, e))
+    };
+    Ok(CompiledRule {
+        rule,
+        applies_to: rule.applies_to.as_deref().map(regex).transpose()?,
+        pattern: regex(&rule.pattern)?,
+    })
+}
+
+fn split_name(relative_path: &str) -> (&str, &str, &str) {
+    let (dir, file) = match relative_path.rfind('/') {
+        Some(index) => relative_path.split_at(index + 1),
+        None => ("", relative_path),
+    };
+    match file.rfind('.') {
+        Some(index) if index > 0 => (dir, &file[..index], &file[index..]),
+        _ => (dir, file, ""),
+    }
+}
+
+// Records the first violated rule per file in its metadata, along with the
+// conventional name when the rule has a rename template.
+pub fn annotate(app: &AppHandle, manifests: &mut [AssetManifest]) {
+    apply(&storage::load_json(app, NAMING_FILE), manifests);
+}
+
+fn apply(settings: &NamingSettings, manifests: &mut [AssetManifest]) {
+    // Rules are validated when saved, so one that fails here is skipped.
+    let rules: Vec<CompiledRule> = settings
+        .rules
+        .iter()
+        .filter_map(|rule| compile(rule).ok())
+        .collect();
+    if rules.is_empty() {
+        return;
+    }
+
+    for asset in manifests
+        .iter_mut()
+        .flat_map(|manifest| manifest.assets.iter_mut())
+    {
+        let asset_type = asset_types::classify(&asset.relative_path, &asset.file_type);
+        let (dir, stem, extension) = split_name(&asset.relative_path);
+        let violated = rules.iter().find(|compiled| {
+            compiled
+                .rule
+                .asset_type
+                .is_none_or(|expected| expected == asset_type)
+                && compiled
+                    .applies_to
+                    .as_ref()
+                    .is_none_or(|applies| applies.is_match(&asset.relative_path))
+                && !compiled.pattern.is_match(stem)
+        });
+        let Some(violated) = violated else {
+            continue;
+        };
+
+        let suggestion =
+            violated.rule.rename.as_ref().map(|template| {
+                format!("{}{}{}", dir, template.replace("{stem}", stem), extension)
+            });
+        asset
+            .metadata
+            .insert(VIOLATION_KEY.to_string(), violated.rule.name.clone());
+        if let Some(suggestion) = suggestion {
+            asset
+                .metadata
+                .insert(SUGGESTION_KEY.to_string(), suggestion);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_naming_rules(app: AppHandle) -> NamingSettings {
+    storage::load_json(&app, NAMING_FILE)
+}
+
+#[tauri::command]
+pub fn set_naming_rules(
+    app: AppHandle,
+    rules: Vec<NamingRule>,
+    auto_rename: bool,
+) -> Result<NamingSettings, String> {
+    access::ensure_writable(&app)?;
+    for rule in &rules {
+        if rule.name.trim().is_empty() {
+            return Err("Naming rules need a name".to_string());
+        }
+        compile(rule)?;
+    }
+    let settings = NamingSettings { rules, auto_rename };
+    storage::save_json(&app, NAMING_FILE, &settings)?;
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::tests::manifest;
+
+    fn rule(asset_type: Option<AssetType>, applies_to: Option<&str>, pattern: &str) -> NamingRule {
+        NamingRule {
+            name: "normal maps".to_string(),
+            asset_type,
+            applies_to: applies_to.map(str::to_string),
+            pattern: pattern.to_string(),
+            rename: Some("T_{stem}_N".to_string()),
+        }
+    }
+
+    #[test]
+    fn split_name_keeps_dotfiles_whole() {
+        assert_eq!(split_name("a/b/Rock.n.png"), ("a/b/", "Rock.n", ".png"));
+        assert_eq!(split_name(".gitignore"), ("", ".gitignore", ""));
+    }
+
+    #[test]
+    fn flags_the_first_violated_rule_with_a_suggestion() {
+        let settings = NamingSettings {
+            rules: vec![rule(
+                Some(AssetType::Texture),
+                Some("(?i)normal"),
+                "^T_.+_N$",
+            )],
+            auto_rename: false,
+        };
+        let mut manifests = [manifest(
+            "rocks",
+            "Rocks",
+            &[
+                "Tex/Rock_Normal.png",
+                "Tex/T_Rock_N.png",
+                "Tex/Rock_Albedo.png",
+                "Normal.fbx",
+            ],
+        )];
+        apply(&settings, &mut manifests);
+
+        let assets = &manifests[0].assets;
+        assert_eq!(assets[0].metadata[VIOLATION_KEY], "normal maps");
+        assert_eq!(
+            assets[0].metadata[SUGGESTION_KEY],
+            "Tex/T_Rock_Normal_N.png"
+        );
+        // Conforming, not covered by `applies_to`, or another asset type.
+        assert!(assets[1..].iter().all(|asset| asset.metadata.is_empty()));
+    }
+
+    #[test]
+    fn invalid_patterns_are_reported() {
+        assert!(compile(&rule(None, Some("("), ".*")).is_err());
+        assert!(compile(&rule(None, None, "[")).is_err());
+    }
+}
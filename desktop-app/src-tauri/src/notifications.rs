@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::jobs::{JobPhase, JobStatus};
+use crate::messages::Message;
+use crate::{access, storage};
+
+const NOTIFICATIONS_FILE: &str = "notifications.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct NotificationSettings {
+    // Show a system notification when an ingestion job succeeds or fails.
+    // Jobs the user cancelled are never announced.
+    job_finished: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings { job_finished: true }
+    }
+}
+
+pub fn job_finished(app: &AppHandle, status: &JobStatus) {
+    let settings: NotificationSettings = storage::load_json(app, NOTIFICATIONS_FILE);
+    if !settings.job_finished {
+        return;
+    }
+    let job = status.name.clone().unwrap_or_else(|| status.source.clone());
+    let (title, body) = match status.phase {
+        JobPhase::Completed => (
+            Message::new("notification.job_completed").param("name", job),
+            Message::new("notification.job_completed_body")
+                .param("assets", status.asset_count.unwrap_or(0)),
+        ),
+        JobPhase::Failed => (
+            Message::new("notification.job_failed").param("name", job),
+            status
+                .last_error
+                .as_ref()
+                .map_or(Message::new("ingestion.failed"), |error| {
+                    Message::new("ingestion.output").param("line", error)
+                }),
+        ),
+        _ => return,
+    };
+    let _ = app
+        .notification()
+        .builder()
+        .title(title.render())
+        .body(body.render())
+        .show();
+}
+
+#[tauri::command]
+pub fn get_notification_settings(app: AppHandle) -> NotificationSettings {
+    storage::load_json(&app, NOTIFICATIONS_FILE)
+}
+
+#[tauri::command]
+pub fn set_notification_settings(
+    app: AppHandle,
+    settings: NotificationSettings,
+) -> Result<NotificationSettings, String> {
+    access::ensure_writable(&app)?;
+    storage::save_json(&app, NOTIFICATIONS_FILE, &settings)?;
+    Ok(settings)
+}
@@ -0,0 +1,248 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::uv_binary;
+
+const MIN_UV_VERSION: (u64, u64, u64) = (0, 4, 0);
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOW_DISK_BYTES: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PreflightCheck {
+    id: &'static str,
+    status: CheckStatus,
+    message: String,
+}
+
+// `ready` is false when any check failed; warnings leave the decision to the
+// user.
+#[derive(Debug, Serialize, Clone)]
+pub struct PreflightReport {
+    ready: bool,
+    checks: Vec<PreflightCheck>,
+}
+
+fn check(id: &'static str, status: CheckStatus, message: impl Into<String>) -> PreflightCheck {
+    PreflightCheck {
+        id,
+        status,
+        message: message.into(),
+    }
+}
+
+// `uv --version` prints e.g. "uv 0.5.11 (c4d0caaee 2024-12-19)".
+fn parse_uv_version(output: &str) -> Option<(u64, u64, u64)> {
+    let version = output.split_whitespace().nth(1)?;
+    let mut parts = version.split('.').map(|part| part.parse::<u64>().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+async fn check_uv(app: &AppHandle) -> PreflightCheck {
+    let output = uv_binary::command(app).args(["--version"]).output().await;
+    let Some(text) = output
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    else {
+        return check("uv", CheckStatus::Fail, "uv was not found");
+    };
+    let (major, minor, patch) = MIN_UV_VERSION;
+    match parse_uv_version(&text) {
+        Some(version) if version >= MIN_UV_VERSION => check("uv", CheckStatus::Pass, text),
+        Some(_) => check(
+            "uv",
+            CheckStatus::Fail,
+            format!(
+                "{} is older than the required {}.{}.{}",
+                text, major, minor, patch
+            ),
+        ),
+        None => check(
+            "uv",
+            CheckStatus::Warn,
+            format!("Could not read the uv version from \"{}\"", text),
+        ),
+    }
+}
+
+fn check_pyproject(ingestion_path: &str, source: &str) -> Vec<PreflightCheck> {
+    let path = Path::new(ingestion_path).join("pyproject.toml");
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            return vec![check(
+                "pyproject",
+                CheckStatus::Fail,
+                format!("Cannot read {}: {}", path.display(), e),
+            )]
+        }
+    };
+    if let Err(e) = text.parse::<toml::Table>() {
+        return vec![check(
+            "pyproject",
+            CheckStatus::Fail,
+            format!("{} is not valid TOML: {}", path.display(), e),
+        )];
+    }
+    let mut checks = vec![check(
+        "pyproject",
+        CheckStatus::Pass,
+        format!("{} is valid", path.display()),
+    )];
+
+    checks.push(
+        match crate::source_availability(source, ingestion_path).reason {
+            None => check(
+                "extras",
+                CheckStatus::Pass,
+                format!("Extra \"{}\" is declared", source),
+            ),
+            Some(reason) => check("extras", CheckStatus::Fail, reason),
+        },
+    );
+    checks
+}
+
+fn marketplace_host(source: &str) -> Option<&'static str> {
+    match source {
+        "fab" => Some("https://www.fab.com"),
+        "uas" => Some("https://assetstore.unity.com"),
+        _ => None,
+    }
+}
+
+// Any HTTP response counts as reachable; only connection failures fail.
+async fn check_network(url: &str) -> PreflightCheck {
+    let client = match reqwest::Client::builder().timeout(NETWORK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            return check(
+                "network",
+                CheckStatus::Warn,
+                format!("Failed to create HTTP client: {}", e),
+            )
+        }
+    };
+    match client.head(url).send().await {
+        Ok(_) => check(
+            "network",
+            CheckStatus::Pass,
+            format!("{} is reachable", url),
+        ),
+        Err(e) => check(
+            "network",
+            CheckStatus::Fail,
+            format!("{} is not reachable: {}", url, e),
+        ),
+    }
+}
+
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)] // statvfs field types differ between platforms
+fn available_bytes(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is a valid C string and `stat` is only read on success.
+    let stat = unsafe {
+        if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn available_bytes(path: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available = 0u64;
+    // SAFETY: `wide` is NUL-terminated and the unused outputs may be null.
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(available)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn available_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+// The output directory is often created by the run itself, so the nearest
+// existing ancestor is measured instead.
+fn check_disk(output_dir: &str) -> PreflightCheck {
+    let existing = Path::new(output_dir).ancestors().find(|path| path.exists());
+    let Some(available) = existing.and_then(available_bytes) else {
+        return check(
+            "disk",
+            CheckStatus::Warn,
+            format!("Could not determine free space for {}", output_dir),
+        );
+    };
+    let gib = available as f64 / (1024.0 * 1024.0 * 1024.0);
+    if available < LOW_DISK_BYTES {
+        check(
+            "disk",
+            CheckStatus::Warn,
+            format!("Only {:.2} GiB free for {}", gib, output_dir),
+        )
+    } else {
+        check(
+            "disk",
+            CheckStatus::Pass,
+            format!("{:.1} GiB free for {}", gib, output_dir),
+        )
+    }
+}
+
+#[tauri::command]
+pub async fn preflight_check(
+    app: AppHandle,
+    source: String,
+    ingestion_path: String,
+    output_dir: Option<String>,
+) -> Result<PreflightReport, String> {
+    if !matches!(source.as_str(), "filesystem" | "fab" | "uas") {
+        return Err(format!("Unknown source type: {}", source));
+    }
+    let mut checks = Vec::new();
+    // Folder packs are scanned natively; only marketplace runs go through uv.
+    if source != "filesystem" {
+        checks.push(check_uv(&app).await);
+        checks.extend(check_pyproject(&ingestion_path, &source));
+    }
+    if let Some(url) = marketplace_host(&source) {
+        checks.push(check_network(url).await);
+    }
+    if let Some(output_dir) = output_dir.filter(|dir| !dir.trim().is_empty()) {
+        checks.push(check_disk(&output_dir));
+    }
+
+    Ok(PreflightReport {
+        ready: checks.iter().all(|check| check.status != CheckStatus::Fail),
+        checks,
+    })
+}
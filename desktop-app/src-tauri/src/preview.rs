@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, UriSchemeContext, UriSchemeResponder, Wry};
+
+use crate::asset_types::{self, AssetType};
+use crate::memory::{self, Pool};
+use crate::{audio, catalog, runtime};
+
+// Memory held by other work is only released over time, so a preview held
+// back by the budget re-checks it at this interval.
+const BUDGET_RECHECK: Duration = Duration::from_millis(250);
+
+// Caps how many previews are read and decoded at once; the limit is re-read
+// on every request so changes apply immediately. While the memory budget is
+// exceeded only one preview runs at a time.
+#[derive(Default)]
+pub struct PreviewPool {
+    active: Mutex<usize>,
+    freed: Condvar,
+}
+
+impl PreviewPool {
+    fn run<T>(&self, app: &AppHandle, work: impl FnOnce() -> T) -> T {
+        {
+            let mut active = self.active.lock().unwrap();
+            while *active >= runtime::limits(app).preview_workers
+                || (*active > 0 && memory::over_budget(app))
+            {
+                active = self.freed.wait_timeout(active, BUDGET_RECHECK).unwrap().0;
+            }
+            *active += 1;
+        }
+        let result = work();
+        *self.active.lock().unwrap() -= 1;
+        self.freed.notify_one();
+        result
+    }
+}
+
+// Asset id, requested level and file modification time.
+type CacheKey = (i64, Option<u64>, Option<SystemTime>);
+
+// Normalized previews are expensive to produce and tend to be replayed, so
+// the most recent ones are kept up to the cache budget.
+#[derive(Default)]
+pub struct PreviewCache {
+    entries: Mutex<VecDeque<(CacheKey, Vec<u8>)>>,
+}
+
+impl PreviewCache {
+    fn get(&self, key: &CacheKey) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().unwrap();
+        let index = entries.iter().position(|(k, _)| k == key)?;
+        let entry = entries.remove(index)?;
+        let body = entry.1.clone();
+        entries.push_back(entry);
+        Some(body)
+    }
+
+    // Drops least recently used entries until `free` bytes are released and
+    // the rest fits in `limit`. Returns the bytes released.
+    fn evict(&self, free: u64, limit: u64) -> u64 {
+        let mut entries = self.entries.lock().unwrap();
+        let mut held: u64 = entries.iter().map(|(_, body)| body.len() as u64).sum();
+        let mut released = 0;
+        while released < free || held > limit {
+            let Some((_, body)) = entries.pop_front() else {
+                break;
+            };
+            held -= body.len() as u64;
+            released += body.len() as u64;
+        }
+        released
+    }
+}
+
+fn cache_insert(app: &AppHandle, key: CacheKey, body: Vec<u8>) {
+    let limit = memory::budget(app).preview_cache_bytes();
+    let size = body.len() as u64;
+    if size > limit {
+        return;
+    }
+    let cache = app.state::<PreviewCache>();
+    cache.entries.lock().unwrap().push_back((key, body));
+    let released = cache.evict(0, limit);
+    memory::record(app, Pool::PreviewCache, size, released);
+    memory::enforce(app);
+}
+
+// Frees at least `bytes` from the cache, or as much as it holds, and trims
+// it to its configured budget.
+pub fn shrink_cache(app: &AppHandle, bytes: u64) {
+    let limit = memory::budget(app).preview_cache_bytes();
+    let released = app.state::<PreviewCache>().evict(bytes, limit);
+    memory::record(app, Pool::PreviewCache, 0, released);
+}
+
+fn content_type(file_type: &str) -> &'static str {
+    match file_type {
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "ogg" | "opus" => "audio/ogg",
+        "flac" => "audio/flac",
+        "aif" | "aiff" => "audio/aiff",
+        "m4a" => "audio/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+fn error(status: StatusCode, message: String) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(message.into_bytes())
+        .unwrap()
+}
+
+// `normalize` with no value uses the configured preview level; a number is
+// taken as the target in LUFS.
+fn normalize_target(query: Option<&str>) -> Option<Option<f64>> {
+    query?.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        (key == "normalize").then(|| value.parse().ok())
+    })
+}
+
+fn serve(app: &AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let id = request.uri().path().trim_matches('/');
+    let Ok(id) = id.parse::<i64>() else {
+        return error(StatusCode::BAD_REQUEST, format!("Invalid asset id: {}", id));
+    };
+    let (path, file_type) = match catalog::asset_file(app, id) {
+        Ok(found) => found,
+        Err(e) => return error(StatusCode::NOT_FOUND, e),
+    };
+    let relative = path.to_string_lossy();
+    if asset_types::classify(&relative, &file_type) != AssetType::Audio {
+        return error(
+            StatusCode::BAD_REQUEST,
+            format!("Asset {} is not audio", id),
+        );
+    }
+
+    let normalized = match normalize_target(request.uri().query()) {
+        Some(target) if file_type == "wav" => {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            let key = (id, target.map(f64::to_bits), modified);
+            let cached = app.state::<PreviewCache>().get(&key);
+            cached.or_else(|| {
+                let body = audio::normalized_wave(app, &path, target)?;
+                cache_insert(app, key, body.clone());
+                Some(body)
+            })
+        }
+        _ => None,
+    };
+    let body = match normalized {
+        Some(body) => body,
+        None => match fs::read(&path) {
+            Ok(body) => body,
+            Err(e) => {
+                return error(
+                    StatusCode::NOT_FOUND,
+                    format!("Failed to read {}: {}", path.display(), e),
+                )
+            }
+        },
+    };
+    Response::builder()
+        .header(header::CONTENT_TYPE, content_type(&file_type))
+        .body(body)
+        .unwrap()
+}
+
+// Serves catalog audio to the webview as `preview://localhost/<asset id>`
+// (`http://preview.localhost/<asset id>` on Windows). Decoding for
+// normalization runs off the main thread.
+pub fn handle(
+    ctx: UriSchemeContext<'_, Wry>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = ctx.app_handle().clone();
+    std::thread::spawn(move || {
+        let response = app
+            .state::<PreviewPool>()
+            .run(&app, || serve(&app, &request));
+        // The body stays in memory until the webview has taken it.
+        let _held = memory::reserve(&app, Pool::PreviewBuffers, response.body().len() as u64);
+        responder.respond(response);
+    });
+}
@@ -0,0 +1,60 @@
+// uv starts Python as a grandchild (and the sandbox wrapper adds another
+// level), so killing only the direct child would leave the ingestion running.
+#[cfg(unix)]
+pub fn kill(pid: u32) {
+    let mut victims = descendants(pid);
+    victims.push(pid);
+    for victim in victims {
+        // SAFETY: kill only takes integer arguments.
+        unsafe {
+            libc::kill(victim as libc::pid_t, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn descendants(root: u32) -> Vec<u32> {
+    let Ok(output) = std::process::Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid="])
+        .output()
+    else {
+        return Vec::new();
+    };
+    let pairs: Vec<(u32, u32)> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let ppid = fields.next()?.parse().ok()?;
+            Some((pid, ppid))
+        })
+        .collect();
+
+    let mut found = Vec::new();
+    let mut frontier = vec![root];
+    while let Some(parent) = frontier.pop() {
+        for &(pid, ppid) in &pairs {
+            if ppid == parent && !found.contains(&pid) {
+                found.push(pid);
+                frontier.push(pid);
+            }
+        }
+    }
+    // Deepest processes first so parents cannot respawn them.
+    found.reverse();
+    found
+}
+
+#[cfg(windows)]
+pub fn kill(pid: u32) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let _ = std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn kill(_pid: u32) {}
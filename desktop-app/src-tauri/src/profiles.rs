@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::Error;
+use crate::jobs::now_millis;
+use crate::{access, run_ingestion_job, storage, tags, IngestionConfig, IngestionResult};
+
+const PROFILES_FILE: &str = "ingestion-profiles.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IngestionProfile {
+    id: String,
+    name: String,
+    config: IngestionConfig,
+    updated_at: u64,
+}
+
+// Serializes read-modify-write cycles on the profiles file.
+#[derive(Default)]
+pub struct ProfileStore {
+    lock: Mutex<()>,
+}
+
+fn profile_id(name: &str) -> String {
+    let slug = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        format!("profile-{}", now_millis())
+    } else {
+        slug
+    }
+}
+
+// Overrides are a partial config: any top-level key replaces the profile's
+// value, so `{ "path": ..., "name": ... }` is enough to reuse a profile.
+fn apply_overrides(config: &IngestionConfig, overrides: Value) -> Result<IngestionConfig, String> {
+    let mut merged =
+        serde_json::to_value(config).map_err(|e| format!("Failed to read profile: {}", e))?;
+    match overrides {
+        Value::Null => {}
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                merged[key] = value;
+            }
+        }
+        _ => return Err("Profile overrides must be an object".to_string()),
+    }
+    serde_json::from_value(merged).map_err(|e| format!("Invalid profile overrides: {}", e))
+}
+
+#[tauri::command]
+pub fn list_ingestion_profiles(
+    app: AppHandle,
+    store: State<'_, ProfileStore>,
+) -> Vec<IngestionProfile> {
+    let _guard = store.lock.lock().unwrap();
+    storage::load_json(&app, PROFILES_FILE)
+}
+
+#[tauri::command]
+pub fn save_ingestion_profile(
+    app: AppHandle,
+    store: State<'_, ProfileStore>,
+    name: String,
+    config: IngestionConfig,
+) -> Result<IngestionProfile, String> {
+    access::ensure_writable(&app)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    let config = IngestionConfig {
+        tags: tags::normalize_all(&config.tags)?,
+        ..config
+    };
+
+    let _guard = store.lock.lock().unwrap();
+    let mut profiles: Vec<IngestionProfile> = storage::load_json(&app, PROFILES_FILE);
+    let profile = IngestionProfile {
+        id: profile_id(&name),
+        name,
+        config,
+        updated_at: now_millis(),
+    };
+    match profiles.iter_mut().find(|p| p.id == profile.id) {
+        Some(existing) => *existing = profile.clone(),
+        None => profiles.push(profile.clone()),
+    }
+    storage::save_json(&app, PROFILES_FILE, &profiles)?;
+    Ok(profile)
+}
+
+#[tauri::command]
+pub fn delete_ingestion_profile(
+    app: AppHandle,
+    store: State<'_, ProfileStore>,
+    id: String,
+) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    let _guard = store.lock.lock().unwrap();
+    let mut profiles: Vec<IngestionProfile> = storage::load_json(&app, PROFILES_FILE);
+    let before = profiles.len();
+    profiles.retain(|p| p.id != id);
+    if profiles.len() == before {
+        return Err(format!("Unknown profile: {}", id));
+    }
+    storage::save_json(&app, PROFILES_FILE, &profiles)
+}
+
+#[tauri::command]
+pub async fn run_ingestion_with_profile(
+    app: AppHandle,
+    profile_id: String,
+    overrides: Value,
+    ingestion_path: String,
+) -> Result<IngestionResult, Error> {
+    access::ensure_writable(&app)?;
+    let config = {
+        let store = app.state::<ProfileStore>();
+        let _guard = store.lock.lock().unwrap();
+        let profiles: Vec<IngestionProfile> = storage::load_json(&app, PROFILES_FILE);
+        let profile =
+            profiles
+                .into_iter()
+                .find(|p| p.id == profile_id)
+                .ok_or(Error::InvalidConfig(format!(
+                    "Unknown profile: {}",
+                    profile_id
+                )))?;
+        apply_overrides(&profile.config, overrides).map_err(Error::InvalidConfig)?
+    };
+    run_ingestion_job(app, config, ingestion_path).await
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{jobs, resume};
+
+// The ingestion tool may interleave JSON-lines events with its human-readable
+// stderr output, one object per line:
+//
+//   {"event": "stage", "stage": "scanning", "message": "Scanning directory"}
+//   {"event": "progress", "current": 12, "total": 300, "file": "Models/a.fbx"}
+//   {"event": "item_started", "item": "1234", "partial": ["/out/extracted/1234"]}
+//   {"event": "item_completed", "item": "1234"}
+//
+// Lines that do not parse as one of these are treated as plain log output.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ToolEvent {
+    Progress {
+        current: u64,
+        total: Option<u64>,
+        file: Option<String>,
+    },
+    Stage {
+        stage: String,
+        message: Option<String>,
+    },
+    // A marketplace package download, recorded for resuming.
+    ItemStarted {
+        item: String,
+        #[serde(default)]
+        partial: Vec<String>,
+    },
+    ItemCompleted {
+        item: String,
+    },
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProgressEvent {
+    job_id: String,
+    current: u64,
+    total: Option<u64>,
+    file: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StageEvent {
+    job_id: String,
+    stage: String,
+    message: Option<String>,
+}
+
+pub fn parse(line: &str) -> Option<ToolEvent> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    serde_json::from_str(line).ok()
+}
+
+pub fn emit(app: &AppHandle, job_id: &str, event: ToolEvent) {
+    let registry = jobs::registry(app);
+    match event {
+        ToolEvent::Progress {
+            current,
+            total,
+            file,
+        } => {
+            registry.record_progress(app, job_id, current, total);
+            let _ = app.emit(
+                "ingestion-progress",
+                ProgressEvent {
+                    job_id: job_id.to_string(),
+                    current,
+                    total,
+                    file,
+                },
+            );
+        }
+        ToolEvent::Stage { stage, message } => {
+            registry.record_stage(app, job_id, &stage);
+            let _ = app.emit(
+                "ingestion-stage",
+                StageEvent {
+                    job_id: job_id.to_string(),
+                    stage,
+                    message,
+                },
+            );
+        }
+        ToolEvent::ItemStarted { item, partial } => {
+            resume::item_started(app, job_id, item, partial)
+        }
+        ToolEvent::ItemCompleted { item } => resume::item_completed(app, job_id, item),
+    }
+}
@@ -0,0 +1,254 @@
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::access;
+use crate::catalog::with_connection;
+use crate::jobs::now_millis;
+use crate::marketplace::{self, Marketplace};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestStatus {
+    Pending,
+    Approved,
+    Denied,
+    Purchased,
+}
+
+impl RequestStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            RequestStatus::Pending => "pending",
+            RequestStatus::Approved => "approved",
+            RequestStatus::Denied => "denied",
+            RequestStatus::Purchased => "purchased",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "approved" => RequestStatus::Approved,
+            "denied" => RequestStatus::Denied,
+            "purchased" => RequestStatus::Purchased,
+            _ => RequestStatus::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PurchaseRequest {
+    id: i64,
+    url: String,
+    marketplace: Marketplace,
+    item_id: String,
+    justification: String,
+    estimated_price: Option<String>,
+    requested_by: Option<String>,
+    status: RequestStatus,
+    decided_by: Option<String>,
+    decision_note: Option<String>,
+    external_asset_id: Option<i64>,
+    created_at: u64,
+    updated_at: u64,
+}
+
+const COLUMNS: &str = "id, url, marketplace, item_id, justification, estimated_price, requested_by,
+     status, decided_by, decision_note, external_asset_id, created_at, updated_at";
+
+fn from_row(row: &Row) -> rusqlite::Result<PurchaseRequest> {
+    let marketplace: String = row.get(2)?;
+    let status: String = row.get(7)?;
+    Ok(PurchaseRequest {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        marketplace: Marketplace::parse(&marketplace).unwrap_or(Marketplace::Fab),
+        item_id: row.get(3)?,
+        justification: row.get(4)?,
+        estimated_price: row.get(5)?,
+        requested_by: row.get(6)?,
+        status: RequestStatus::parse(&status),
+        decided_by: row.get(8)?,
+        decision_note: row.get(9)?,
+        external_asset_id: row.get(10)?,
+        created_at: row.get::<_, i64>(11)?.max(0) as u64,
+        updated_at: row.get::<_, i64>(12)?.max(0) as u64,
+    })
+}
+
+fn get(conn: &Connection, id: i64) -> Result<PurchaseRequest, String> {
+    let sql = format!("SELECT {} FROM purchase_requests WHERE id = ?1", COLUMNS);
+    conn.query_row(&sql, params![id], from_row)
+        .optional()
+        .map_err(|e| format!("Failed to read purchase request {}: {}", id, e))?
+        .ok_or(format!("Unknown purchase request: {}", id))
+}
+
+// Every state change is broadcast so open windows can refresh and notify.
+fn notify(app: &AppHandle, request: &PurchaseRequest) {
+    let _ = app.emit("purchase-request-changed", request);
+}
+
+fn optional_text(value: Option<String>) -> Option<String> {
+    value
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+#[tauri::command]
+pub fn file_purchase_request(
+    app: AppHandle,
+    url: String,
+    justification: String,
+    estimated_price: Option<String>,
+    requested_by: Option<String>,
+) -> Result<PurchaseRequest, String> {
+    access::ensure_writable(&app)?;
+    let (marketplace, item_id) = marketplace::listing_id(&url)?;
+    let justification = justification.trim().to_string();
+    if justification.is_empty() {
+        return Err("Purchase requests need a justification".to_string());
+    }
+
+    let request = with_connection(&app, |conn| {
+        let now = now_millis() as i64;
+        conn.execute(
+            "INSERT INTO purchase_requests
+                 (url, marketplace, item_id, justification, estimated_price, requested_by,
+                  status, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
+            params![
+                url.trim(),
+                marketplace.as_str(),
+                item_id,
+                justification,
+                optional_text(estimated_price),
+                optional_text(requested_by),
+                RequestStatus::Pending.as_str(),
+                now
+            ],
+        )
+        .map_err(|e| format!("Failed to save purchase request: {}", e))?;
+        get(conn, conn.last_insert_rowid())
+    })?;
+    notify(&app, &request);
+    Ok(request)
+}
+
+#[tauri::command]
+pub fn list_purchase_requests(
+    app: AppHandle,
+    status: Option<RequestStatus>,
+) -> Result<Vec<PurchaseRequest>, String> {
+    let sql = format!(
+        "SELECT {} FROM purchase_requests
+         WHERE (?1 IS NULL OR status = ?1)
+         ORDER BY created_at DESC",
+        COLUMNS
+    );
+    with_connection(&app, |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to query purchase requests: {}", e))?;
+        let rows = stmt
+            .query_map(params![status.map(RequestStatus::as_str)], from_row)
+            .map_err(|e| format!("Failed to query purchase requests: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read purchase requests: {}", e))
+    })
+}
+
+// Only pending requests can be decided; a decision is final.
+#[tauri::command]
+pub fn decide_purchase_request(
+    app: AppHandle,
+    id: i64,
+    approve: bool,
+    decided_by: Option<String>,
+    note: Option<String>,
+) -> Result<PurchaseRequest, String> {
+    access::ensure_writable(&app)?;
+    let status = if approve {
+        RequestStatus::Approved
+    } else {
+        RequestStatus::Denied
+    };
+    let request = with_connection(&app, |conn| {
+        let current = get(conn, id)?;
+        if current.status != RequestStatus::Pending {
+            return Err(format!(
+                "Purchase request {} is already {}",
+                id,
+                current.status.as_str()
+            ));
+        }
+        conn.execute(
+            "UPDATE purchase_requests
+             SET status = ?1, decided_by = ?2, decision_note = ?3, updated_at = ?4
+             WHERE id = ?5",
+            params![
+                status.as_str(),
+                optional_text(decided_by),
+                optional_text(note),
+                now_millis() as i64,
+                id
+            ],
+        )
+        .map_err(|e| format!("Failed to update purchase request {}: {}", id, e))?;
+        get(conn, id)
+    })?;
+    notify(&app, &request);
+    Ok(request)
+}
+
+// Approved requests for an item are closed once the item shows up as owned.
+pub fn link_owned_item(
+    app: &AppHandle,
+    marketplace: Marketplace,
+    item_id: &str,
+    external_asset_id: i64,
+) -> Result<(), String> {
+    let linked = with_connection(app, |conn| {
+        let ids = {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id FROM purchase_requests
+                     WHERE marketplace = ?1 AND item_id = ?2 AND status = ?3",
+                )
+                .map_err(|e| format!("Failed to query purchase requests: {}", e))?;
+            let rows = stmt
+                .query_map(
+                    params![
+                        marketplace.as_str(),
+                        item_id,
+                        RequestStatus::Approved.as_str()
+                    ],
+                    |row| row.get::<_, i64>(0),
+                )
+                .map_err(|e| format!("Failed to query purchase requests: {}", e))?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Failed to read purchase requests: {}", e))?
+        };
+        ids.into_iter()
+            .map(|id| {
+                conn.execute(
+                    "UPDATE purchase_requests
+                     SET status = ?1, external_asset_id = ?2, updated_at = ?3
+                     WHERE id = ?4",
+                    params![
+                        RequestStatus::Purchased.as_str(),
+                        external_asset_id,
+                        now_millis() as i64,
+                        id
+                    ],
+                )
+                .map_err(|e| format!("Failed to update purchase request {}: {}", id, e))?;
+                get(conn, id)
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    for request in &linked {
+        notify(app, request);
+    }
+    Ok(())
+}
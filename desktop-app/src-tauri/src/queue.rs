@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::Error;
+use crate::jobs::{self, JobPhase, JobStatus};
+use crate::{access, execute_ingestion_job, resume, runtime, IngestionConfig};
+
+const DEFAULT_CONCURRENCY: usize = 2;
+const MAX_CONCURRENCY: usize = 8;
+
+struct PendingJob {
+    id: String,
+    config: IngestionConfig,
+    ingestion_path: String,
+    downloads: bool,
+}
+
+struct QueueState {
+    concurrency: usize,
+    running: usize,
+    running_downloads: usize,
+    pending: VecDeque<PendingJob>,
+}
+
+// Marketplace runs with a download strategy fetch whole packages, so they
+// also count against the download connection limit.
+fn downloads(config: &IngestionConfig) -> bool {
+    config.source != "filesystem"
+        && !matches!(
+            config.download_strategy.as_deref(),
+            None | Some("metadata_only")
+        )
+}
+
+// Holds ingestions that have been accepted but not started. Progress and
+// results are reported through the job registry's `job-status` events.
+pub struct JobQueue {
+    state: Mutex<QueueState>,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        JobQueue {
+            state: Mutex::new(QueueState {
+                concurrency: DEFAULT_CONCURRENCY,
+                running: 0,
+                running_downloads: 0,
+                pending: VecDeque::new(),
+            }),
+        }
+    }
+}
+
+// Starts queued jobs until the concurrency limit is reached. Called whenever
+// a job is added, a job finishes or a limit changes. Downloading jobs over
+// their own limit wait while later jobs go ahead.
+pub fn pump(app: &AppHandle) {
+    let download_limit = runtime::limits(app).download_connections;
+    let queue = app.state::<JobQueue>();
+    let mut state = queue.state.lock().unwrap();
+    while let Some(job) = take_next(&mut state, download_limit) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            jobs::registry(&app).start(&app, &job.id);
+            let _ =
+                execute_ingestion_job(app.clone(), job.config, job.ingestion_path, job.id).await;
+            {
+                let queue = app.state::<JobQueue>();
+                let mut state = queue.state.lock().unwrap();
+                state.running -= 1;
+                if job.downloads {
+                    state.running_downloads -= 1;
+                }
+            }
+            pump(&app);
+        });
+    }
+}
+
+// Removes the first job that may start now and counts it as running.
+fn take_next(state: &mut QueueState, download_limit: usize) -> Option<PendingJob> {
+    if state.running >= state.concurrency {
+        return None;
+    }
+    let downloads_full = state.running_downloads >= download_limit;
+    let job = state
+        .pending
+        .iter()
+        .position(|job| !(job.downloads && downloads_full))
+        .and_then(|index| state.pending.remove(index))?;
+    state.running += 1;
+    if job.downloads {
+        state.running_downloads += 1;
+    }
+    Some(job)
+}
+
+pub fn enqueue(app: &AppHandle, config: IngestionConfig, ingestion_path: String) -> String {
+    let id = jobs::registry(app).create(app, &config.source, config.name.clone(), JobPhase::Queued);
+    app.state::<JobQueue>()
+        .state
+        .lock()
+        .unwrap()
+        .pending
+        .push_back(PendingJob {
+            id: id.clone(),
+            downloads: downloads(&config),
+            config,
+            ingestion_path,
+        });
+    pump(app);
+    id
+}
+
+#[tauri::command]
+pub fn enqueue_ingestion(
+    app: AppHandle,
+    config: IngestionConfig,
+    ingestion_path: String,
+) -> Result<String, String> {
+    access::ensure_writable(&app)?;
+    Ok(enqueue(&app, config, ingestion_path))
+}
+
+#[tauri::command]
+pub fn list_jobs(registry: State<'_, jobs::JobRegistry>) -> Vec<JobStatus> {
+    registry.list()
+}
+
+// Queued jobs are dropped before they start; running ones are killed.
+#[tauri::command]
+pub fn cancel_job(app: AppHandle, id: String) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    let was_pending = {
+        let queue = app.state::<JobQueue>();
+        let mut state = queue.state.lock().unwrap();
+        let before = state.pending.len();
+        state.pending.retain(|job| job.id != id);
+        state.pending.len() != before
+    };
+
+    let registry = jobs::registry(&app);
+    let cancelled = registry.cancel(&app, &id);
+    // A job that never started never reaches `execute_ingestion_job`, so its
+    // resume checkpoint is released here.
+    if was_pending {
+        resume::finish(&app, &id, false);
+        registry.finish(
+            &app,
+            &id,
+            &Err(Error::Process("Cancelled before starting".to_string())),
+        );
+        registry.prune();
+    }
+    cancelled
+}
+
+#[tauri::command]
+pub fn set_job_concurrency(app: AppHandle, limit: usize) -> Result<usize, String> {
+    access::ensure_writable(&app)?;
+    if !(1..=MAX_CONCURRENCY).contains(&limit) {
+        return Err(format!(
+            "Concurrency must be between 1 and {}",
+            MAX_CONCURRENCY
+        ));
+    }
+    app.state::<JobQueue>().state.lock().unwrap().concurrency = limit;
+    pump(&app);
+    Ok(limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(source: &str, strategy: Option<&str>) -> IngestionConfig {
+        serde_json::from_value(serde_json::json!({
+            "source": source,
+            "tags": [],
+            "download_strategy": strategy,
+        }))
+        .unwrap()
+    }
+
+    fn pending(id: &str, source: &str, strategy: Option<&str>) -> PendingJob {
+        let config = config(source, strategy);
+        PendingJob {
+            id: id.to_string(),
+            downloads: downloads(&config),
+            config,
+            ingestion_path: String::new(),
+        }
+    }
+
+    #[test]
+    fn only_download_strategies_count_as_downloads() {
+        assert!(!downloads(&config("filesystem", None)));
+        assert!(!downloads(&config("uas", None)));
+        assert!(!downloads(&config("uas", Some("metadata_only"))));
+        assert!(downloads(&config("uas", Some("download"))));
+        assert!(downloads(&config("fab", Some("manifests_only"))));
+    }
+
+    #[test]
+    fn downloads_over_their_limit_wait_while_others_start() {
+        let mut state = QueueState {
+            concurrency: 3,
+            running: 0,
+            running_downloads: 0,
+            pending: VecDeque::from([
+                pending("job-1", "uas", Some("download")),
+                pending("job-2", "uas", Some("extract")),
+                pending("job-3", "filesystem", None),
+                pending("job-4", "filesystem", None),
+            ]),
+        };
+        let started: Vec<String> = std::iter::from_fn(|| take_next(&mut state, 1))
+            .map(|job| job.id)
+            .collect();
+        assert_eq!(started, ["job-1", "job-3", "job-4"]);
+        assert_eq!((state.running, state.running_downloads), (3, 1));
+        assert_eq!(state.pending.len(), 1);
+
+        state.running -= 2;
+        state.running_downloads -= 1;
+        assert_eq!(take_next(&mut state, 1).unwrap().id, "job-2");
+        assert!(take_next(&mut state, 1).is_none());
+    }
+}
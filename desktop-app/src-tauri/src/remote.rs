@@ -0,0 +1,414 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::jobs::{self, now_millis};
+use crate::{access, support};
+
+const DEFAULT_MINUTES: u64 = 30;
+const MAX_MINUTES: u64 = 240;
+const POLL: Duration = Duration::from_millis(200);
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+// A whole request must arrive within this, however slowly it trickles in.
+const REQUEST_DEADLINE: Duration = Duration::from_secs(10);
+const WORKERS: usize = 4;
+// Connections beyond this wait list are closed unanswered.
+const BACKLOG: usize = 16;
+const TOKEN_BYTES: usize = 32;
+const MAX_HEADER_LINES: usize = 64;
+// Request line and headers together; there is no body to read.
+const MAX_REQUEST_BYTES: u64 = 16 * 1024;
+
+struct Session {
+    port: u16,
+    lan: bool,
+    expires_at: u64,
+    stop: Arc<AtomicBool>,
+}
+
+// At most one diagnostics endpoint runs at a time. It serves read-only
+// reports and closes on its own once the session expires.
+#[derive(Default)]
+pub struct RemoteAssist {
+    session: Mutex<Option<Session>>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct RemoteAssistOptions {
+    // Minutes until the endpoint closes itself.
+    minutes: Option<u64>,
+    // Listen on every interface so another machine can connect; otherwise
+    // only this machine can. Traffic is plain HTTP, so this is meant for a
+    // trusted studio network.
+    lan: bool,
+    port: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RemoteAssistStatus {
+    active: bool,
+    port: Option<u16>,
+    lan: bool,
+    expires_at: Option<u64>,
+    // Only returned when the session starts; send it to whoever is helping.
+    token: Option<String>,
+}
+
+impl RemoteAssistStatus {
+    fn inactive() -> Self {
+        RemoteAssistStatus {
+            active: false,
+            port: None,
+            lan: false,
+            expires_at: None,
+            token: None,
+        }
+    }
+}
+
+// 256 bits from the OS random source.
+fn new_token() -> Result<String, String> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| format!("Failed to generate remote assist token: {}", e))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+// Compares every byte so the time taken does not reveal how much of a guess
+// was right.
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    token: Option<String>,
+}
+
+// Reads from the socket until `deadline`, waiting at most the time left (and
+// never more than `READ_TIMEOUT`) for each read, so a client that trickles
+// bytes cannot hold a worker past the deadline.
+struct TimedReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for TimedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "Request took too long",
+            ));
+        }
+        self.stream
+            .set_read_timeout(Some(remaining.min(READ_TIMEOUT)))?;
+        let mut stream = self.stream;
+        stream.read(buf)
+    }
+}
+
+// A line cut off by the size limit or a closed connection is refused
+// rather than parsed.
+fn read_line(reader: &mut impl BufRead, line: &mut String) -> io::Result<usize> {
+    line.clear();
+    let read = reader.read_line(line)?;
+    if read > 0 && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Request line too long or incomplete",
+        ));
+    }
+    Ok(read)
+}
+
+fn read_request(stream: &TcpStream, deadline: Instant) -> io::Result<Request> {
+    let mut reader = BufReader::new(TimedReader { stream, deadline }.take(MAX_REQUEST_BYTES));
+    let mut line = String::new();
+    read_line(&mut reader, &mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let mut token = None;
+    for _ in 0..MAX_HEADER_LINES {
+        if read_line(&mut reader, &mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") {
+                token = value
+                    .trim()
+                    .strip_prefix("Bearer ")
+                    .map(|token| token.trim().to_string());
+            }
+        }
+    }
+    Ok(Request {
+        method,
+        path,
+        token,
+    })
+}
+
+fn respond(stream: &mut TcpStream, status: &str, body: &Value) -> io::Result<()> {
+    let body = serde_json::to_string_pretty(body).unwrap_or_default();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+fn route(app: &AppHandle, path: &str) -> Option<Value> {
+    match path.trim_end_matches('/') {
+        "/health" => Some(support::environment(app)),
+        "/jobs" => Some(support::jobs(app)),
+        path => {
+            let id = path.strip_prefix("/jobs/")?;
+            let registry = jobs::registry(app);
+            let mut job = json!(registry.get(id)?);
+            job["recent_output"] = json!(registry.recent_output(id));
+            Some(job)
+        }
+    }
+}
+
+fn serve(app: &AppHandle, token: &str, mut stream: TcpStream) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(READ_TIMEOUT))?;
+    let request = read_request(&stream, Instant::now() + REQUEST_DEADLINE)?;
+    if !request
+        .token
+        .as_deref()
+        .is_some_and(|given| token_matches(token, given))
+    {
+        return respond(
+            &mut stream,
+            "401 Unauthorized",
+            &json!({ "error": "Missing or invalid token" }),
+        );
+    }
+    if request.method != "GET" {
+        return respond(
+            &mut stream,
+            "405 Method Not Allowed",
+            &json!({ "error": "Only GET is supported" }),
+        );
+    }
+    match route(app, &request.path) {
+        Some(body) => respond(&mut stream, "200 OK", &body),
+        None => respond(
+            &mut stream,
+            "404 Not Found",
+            &json!({ "error": format!("Unknown path: {}", request.path) }),
+        ),
+    }
+}
+
+fn listen(
+    app: AppHandle,
+    listener: TcpListener,
+    token: String,
+    stop: Arc<AtomicBool>,
+    until: Instant,
+) {
+    // A fixed set of workers, so a peer opening many connections cannot
+    // exhaust threads. They exit once the sender is dropped below.
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(BACKLOG);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..WORKERS {
+        let app = app.clone();
+        let token = token.clone();
+        let receiver = receiver.clone();
+        thread::spawn(move || loop {
+            let stream = receiver.lock().unwrap().recv();
+            let Ok(stream) = stream else {
+                break;
+            };
+            let _ = serve(&app, &token, stream);
+        });
+    }
+
+    while !stop.load(Ordering::Relaxed) && Instant::now() < until {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let _ = sender.try_send(stream);
+            }
+            // Nothing waiting, or a connection that failed before it was
+            // accepted.
+            Err(_) => thread::sleep(POLL),
+        }
+    }
+    drop(sender);
+    // Clears the session unless a newer one has already replaced it.
+    let state = app.state::<RemoteAssist>();
+    let mut session = state.session.lock().unwrap();
+    if session
+        .as_ref()
+        .is_some_and(|session| Arc::ptr_eq(&session.stop, &stop))
+    {
+        *session = None;
+        let _ = app.emit("remote-assist", RemoteAssistStatus::inactive());
+    }
+}
+
+fn status(session: &Session, token: Option<String>) -> RemoteAssistStatus {
+    RemoteAssistStatus {
+        active: true,
+        port: Some(session.port),
+        lan: session.lan,
+        expires_at: Some(session.expires_at),
+        token,
+    }
+}
+
+// Serves `GET /health`, `/jobs` and `/jobs/<id>` as JSON to callers that send
+// `Authorization: Bearer <token>`. Settings are never exposed.
+#[tauri::command]
+pub fn start_remote_assist(
+    app: AppHandle,
+    state: State<'_, RemoteAssist>,
+    options: Option<RemoteAssistOptions>,
+) -> Result<RemoteAssistStatus, String> {
+    access::ensure_writable(&app)?;
+    let options = options.unwrap_or_default();
+    let minutes = options.minutes.unwrap_or(DEFAULT_MINUTES);
+    if !(1..=MAX_MINUTES).contains(&minutes) {
+        return Err(format!(
+            "Remote assist must last between 1 and {} minutes",
+            MAX_MINUTES
+        ));
+    }
+    let host = if options.lan { "0.0.0.0" } else { "127.0.0.1" };
+    let listener = TcpListener::bind((host, options.port.unwrap_or(0)))
+        .map_err(|e| format!("Failed to open remote assist port: {}", e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to open remote assist port: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to open remote assist port: {}", e))?
+        .port();
+
+    let lifetime = Duration::from_secs(minutes * 60);
+    let token = new_token()?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let session = Session {
+        port,
+        lan: options.lan,
+        expires_at: now_millis() + lifetime.as_millis() as u64,
+        stop: stop.clone(),
+    };
+    let started = status(&session, Some(token.clone()));
+    // The token is only handed to the caller, never broadcast.
+    let event = status(&session, None);
+    if let Some(previous) = state.session.lock().unwrap().replace(session) {
+        previous.stop.store(true, Ordering::Relaxed);
+    }
+    let listener_app = app.clone();
+    let until = Instant::now() + lifetime;
+    thread::spawn(move || listen(listener_app, listener, token, stop, until));
+    let _ = app.emit("remote-assist", event);
+    Ok(started)
+}
+
+#[tauri::command]
+pub fn stop_remote_assist(app: AppHandle, state: State<'_, RemoteAssist>) -> RemoteAssistStatus {
+    if let Some(session) = state.session.lock().unwrap().take() {
+        session.stop.store(true, Ordering::Relaxed);
+        let _ = app.emit("remote-assist", RemoteAssistStatus::inactive());
+    }
+    RemoteAssistStatus::inactive()
+}
+
+#[tauri::command]
+pub fn get_remote_assist_status(state: State<'_, RemoteAssist>) -> RemoteAssistStatus {
+    state
+        .session
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map_or_else(RemoteAssistStatus::inactive, |session| {
+            status(session, None)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs `client` against a fresh connection and returns what the server
+    // side parsed.
+    fn request(
+        deadline: Duration,
+        client: impl FnOnce(TcpStream) + Send + 'static,
+    ) -> io::Result<Request> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = thread::spawn(move || client(TcpStream::connect(address).unwrap()));
+        let (stream, _) = listener.accept().unwrap();
+        let parsed = read_request(&stream, Instant::now() + deadline);
+        drop(stream);
+        client.join().unwrap();
+        parsed
+    }
+
+    #[test]
+    fn reads_method_path_and_token() {
+        let parsed = request(Duration::from_secs(5), |mut client| {
+            client
+                .write_all(b"GET /jobs?x=1 HTTP/1.1\r\nAuthorization: Bearer abc\r\n\r\n")
+                .unwrap();
+        })
+        .unwrap();
+        assert_eq!(parsed.method, "GET");
+        assert_eq!(parsed.path, "/jobs");
+        assert_eq!(parsed.token.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn refuses_an_oversized_request_line() {
+        let parsed = request(Duration::from_secs(5), |mut client| {
+            let _ = client.write_all(&vec![b'A'; MAX_REQUEST_BYTES as usize + 1024]);
+        });
+        assert_eq!(parsed.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn stops_waiting_for_a_slow_request_line_at_the_deadline() {
+        let started = Instant::now();
+        let parsed = request(Duration::from_millis(300), |mut client| {
+            for _ in 0..10 {
+                if client.write_all(b"G").is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+        assert!(parsed.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::jobs::{self, now_millis};
+use crate::{access, queue, storage, IngestionConfig};
+
+const CHECKPOINT_FILE: &str = "download-checkpoints.json";
+
+// Progress of a downloading job, saved as each package starts and finishes
+// so a run cut short by a crash or a failure can pick up where it stopped.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Checkpoint {
+    checkpoint_id: String,
+    config: IngestionConfig,
+    ingestion_path: String,
+    // Package ids fully downloaded, and extracted where that was asked for.
+    completed: Vec<String>,
+    in_progress: Option<String>,
+    // Left behind by the package in progress; discarded before it is retried.
+    partial_files: Vec<String>,
+    created_at: u64,
+    updated_at: u64,
+}
+
+#[derive(Serialize)]
+struct Handoff<'a> {
+    completed: &'a [String],
+    partial_files: &'a [String],
+}
+
+// Serializes read-modify-write cycles on the checkpoint file and maps
+// running jobs to the checkpoint they update.
+#[derive(Default)]
+pub struct ResumeState {
+    lock: Mutex<()>,
+    active: Mutex<HashMap<String, String>>,
+}
+
+// Only the Unity helper downloads packages one at a time; other runs either
+// fetch metadata or finish in one step.
+fn resumable(config: &IngestionConfig) -> bool {
+    config.source == "uas"
+        && matches!(
+            config.download_strategy.as_deref(),
+            Some("download") | Some("extract")
+        )
+}
+
+fn handoff_file(checkpoint_id: &str) -> String {
+    format!("resume-{}.json", checkpoint_id)
+}
+
+fn update(app: &AppHandle, checkpoint_id: &str, f: impl FnOnce(&mut Checkpoint)) {
+    let state = app.state::<ResumeState>();
+    let _guard = state.lock.lock().unwrap();
+    let mut checkpoints: Vec<Checkpoint> = storage::load_json(app, CHECKPOINT_FILE);
+    if let Some(checkpoint) = checkpoints
+        .iter_mut()
+        .find(|checkpoint| checkpoint.checkpoint_id == checkpoint_id)
+    {
+        f(checkpoint);
+        checkpoint.updated_at = now_millis();
+        let _ = storage::save_json(app, CHECKPOINT_FILE, &checkpoints);
+    }
+}
+
+// Called before the helper starts. Starts a checkpoint for a new downloading
+// job, or writes the recorded progress of a resumed one to a file and
+// returns its path for `--resume-from`.
+pub fn begin(
+    app: &AppHandle,
+    job_id: &str,
+    config: &IngestionConfig,
+    ingestion_path: &str,
+) -> Result<Option<PathBuf>, String> {
+    if !resumable(config) {
+        return Ok(None);
+    }
+    let state = app.state::<ResumeState>();
+    let mut active = state.active.lock().unwrap();
+    let _guard = state.lock.lock().unwrap();
+    let mut checkpoints: Vec<Checkpoint> = storage::load_json(app, CHECKPOINT_FILE);
+
+    if let Some(checkpoint_id) = active.get(job_id) {
+        let checkpoint = checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.checkpoint_id == *checkpoint_id)
+            .ok_or(format!("Unknown download checkpoint: {}", checkpoint_id))?;
+        let file = handoff_file(checkpoint_id);
+        storage::save_json(
+            app,
+            &file,
+            &Handoff {
+                completed: &checkpoint.completed,
+                partial_files: &checkpoint.partial_files,
+            },
+        )?;
+        return storage::data_path(app, &file).map(Some);
+    }
+
+    let started_at = jobs::registry(app)
+        .get(job_id)
+        .map_or_else(now_millis, |status| status.started_at);
+    let checkpoint_id = format!("{}-{}", started_at, job_id);
+    checkpoints.push(Checkpoint {
+        checkpoint_id: checkpoint_id.clone(),
+        config: config.clone(),
+        ingestion_path: ingestion_path.to_string(),
+        completed: Vec::new(),
+        in_progress: None,
+        partial_files: Vec::new(),
+        created_at: started_at,
+        updated_at: now_millis(),
+    });
+    storage::save_json(app, CHECKPOINT_FILE, &checkpoints)?;
+    active.insert(job_id.to_string(), checkpoint_id);
+    Ok(None)
+}
+
+pub fn item_started(app: &AppHandle, job_id: &str, item: String, partial: Vec<String>) {
+    let Some(checkpoint_id) = app
+        .state::<ResumeState>()
+        .active
+        .lock()
+        .unwrap()
+        .get(job_id)
+        .cloned()
+    else {
+        return;
+    };
+    update(app, &checkpoint_id, |checkpoint| {
+        checkpoint.in_progress = Some(item);
+        checkpoint.partial_files = partial;
+    });
+}
+
+pub fn item_completed(app: &AppHandle, job_id: &str, item: String) {
+    let Some(checkpoint_id) = app
+        .state::<ResumeState>()
+        .active
+        .lock()
+        .unwrap()
+        .get(job_id)
+        .cloned()
+    else {
+        return;
+    };
+    update(app, &checkpoint_id, |checkpoint| {
+        checkpoint.in_progress = None;
+        checkpoint.partial_files.clear();
+        if !checkpoint.completed.contains(&item) {
+            checkpoint.completed.push(item);
+        }
+    });
+}
+
+// A successful run no longer needs its checkpoint; any other outcome keeps
+// it so the job can be resumed.
+pub fn finish(app: &AppHandle, job_id: &str, success: bool) {
+    let state = app.state::<ResumeState>();
+    let Some(checkpoint_id) = state.active.lock().unwrap().remove(job_id) else {
+        return;
+    };
+    if let Ok(path) = storage::data_path(app, &handoff_file(&checkpoint_id)) {
+        let _ = fs::remove_file(path);
+    }
+    if success {
+        let _guard = state.lock.lock().unwrap();
+        let mut checkpoints: Vec<Checkpoint> = storage::load_json(app, CHECKPOINT_FILE);
+        checkpoints.retain(|checkpoint| checkpoint.checkpoint_id != checkpoint_id);
+        let _ = storage::save_json(app, CHECKPOINT_FILE, &checkpoints);
+    }
+}
+
+// Checkpoints of runs that are not currently going, newest first.
+#[tauri::command]
+pub fn list_resumable_jobs(app: AppHandle, state: State<'_, ResumeState>) -> Vec<Checkpoint> {
+    let active = state.active.lock().unwrap();
+    let _guard = state.lock.lock().unwrap();
+    let checkpoints: Vec<Checkpoint> = storage::load_json(&app, CHECKPOINT_FILE);
+    checkpoints
+        .into_iter()
+        .rev()
+        .filter(|checkpoint| !active.values().any(|id| *id == checkpoint.checkpoint_id))
+        .collect()
+}
+
+// Queues the checkpointed run again, skipping packages it already finished,
+// and returns the new job id.
+#[tauri::command]
+pub fn resume_job(
+    app: AppHandle,
+    state: State<'_, ResumeState>,
+    checkpoint_id: String,
+) -> Result<String, String> {
+    access::ensure_writable(&app)?;
+    // Held across enqueueing so the job finds its checkpoint when it starts.
+    let mut active = state.active.lock().unwrap();
+    if active.values().any(|id| *id == checkpoint_id) {
+        return Err(format!("Download is already running: {}", checkpoint_id));
+    }
+    let checkpoint = {
+        let _guard = state.lock.lock().unwrap();
+        let checkpoints: Vec<Checkpoint> = storage::load_json(&app, CHECKPOINT_FILE);
+        checkpoints
+            .into_iter()
+            .find(|checkpoint| checkpoint.checkpoint_id == checkpoint_id)
+            .ok_or(format!("Unknown download checkpoint: {}", checkpoint_id))?
+    };
+    let job_id = queue::enqueue(&app, checkpoint.config, checkpoint.ingestion_path);
+    active.insert(job_id.clone(), checkpoint_id);
+    Ok(job_id)
+}
+
+#[tauri::command]
+pub fn discard_download_checkpoint(
+    app: AppHandle,
+    state: State<'_, ResumeState>,
+    checkpoint_id: String,
+) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    let active = state.active.lock().unwrap();
+    if active.values().any(|id| *id == checkpoint_id) {
+        return Err(format!("Download is still running: {}", checkpoint_id));
+    }
+    let _guard = state.lock.lock().unwrap();
+    let mut checkpoints: Vec<Checkpoint> = storage::load_json(&app, CHECKPOINT_FILE);
+    let before = checkpoints.len();
+    checkpoints.retain(|checkpoint| checkpoint.checkpoint_id != checkpoint_id);
+    if checkpoints.len() == before {
+        return Err(format!("Unknown download checkpoint: {}", checkpoint_id));
+    }
+    storage::save_json(&app, CHECKPOINT_FILE, &checkpoints)
+}
@@ -0,0 +1,125 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::catalog::{self, with_connection, AssetRecord};
+use crate::jobs::now_millis;
+use crate::{access, storage};
+
+const REVIEW_FILE: &str = "review-gate.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ReviewStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReviewStatus::Pending => "pending",
+            ReviewStatus::Approved => "approved",
+            ReviewStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "pending" => ReviewStatus::Pending,
+            "rejected" => ReviewStatus::Rejected,
+            _ => ReviewStatus::Approved,
+        }
+    }
+}
+
+// When enabled, newly ingested assets stay out of browsing and search until
+// a lead approves them. Assets already in the catalog keep their state.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ReviewGate {
+    enabled: bool,
+}
+
+pub fn initial_status(app: &AppHandle) -> ReviewStatus {
+    let gate: ReviewGate = storage::load_json(app, REVIEW_FILE);
+    if gate.enabled {
+        ReviewStatus::Pending
+    } else {
+        ReviewStatus::Approved
+    }
+}
+
+// Catalogs created before the review gate existed get the columns added, with
+// every existing asset treated as approved.
+pub fn migrate(conn: &Connection) -> Result<(), String> {
+    let has_column = conn
+        .prepare("SELECT 1 FROM pragma_table_info('assets') WHERE name = 'review_status'")
+        .and_then(|mut stmt| stmt.exists([]))
+        .map_err(|e| format!("Failed to inspect catalog: {}", e))?;
+    if has_column {
+        return Ok(());
+    }
+    conn.execute_batch(
+        "ALTER TABLE assets ADD COLUMN review_status TEXT NOT NULL DEFAULT 'approved';
+         ALTER TABLE assets ADD COLUMN review_reason TEXT;
+         ALTER TABLE assets ADD COLUMN reviewed_at INTEGER;",
+    )
+    .map_err(|e| format!("Failed to migrate catalog: {}", e))
+}
+
+fn review(
+    app: &AppHandle,
+    id: i64,
+    status: ReviewStatus,
+    reason: Option<String>,
+) -> Result<AssetRecord, String> {
+    access::ensure_writable(app)?;
+    let reason = reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if status == ReviewStatus::Rejected && reason.is_none() {
+        return Err("A reason is required to reject an asset".to_string());
+    }
+    let updated = with_connection(app, |conn| {
+        conn.execute(
+            "UPDATE assets SET review_status = ?1, review_reason = ?2, reviewed_at = ?3
+             WHERE id = ?4",
+            params![status.as_str(), reason, now_millis() as i64, id],
+        )
+        .map_err(|e| format!("Failed to review asset {}: {}", id, e))
+    })?;
+    if updated == 0 {
+        return Err(format!("Unknown asset: {}", id));
+    }
+    let asset = catalog::get_asset(app.clone(), id)?;
+    let _ = app.emit("asset-reviewed", &asset);
+    Ok(asset)
+}
+
+#[tauri::command]
+pub fn get_review_gate(app: AppHandle) -> ReviewGate {
+    storage::load_json(&app, REVIEW_FILE)
+}
+
+#[tauri::command]
+pub fn set_review_gate(app: AppHandle, enabled: bool) -> Result<ReviewGate, String> {
+    access::ensure_writable(&app)?;
+    let gate = ReviewGate { enabled };
+    storage::save_json(&app, REVIEW_FILE, &gate)?;
+    Ok(gate)
+}
+
+#[tauri::command]
+pub fn approve_asset(
+    app: AppHandle,
+    id: i64,
+    reason: Option<String>,
+) -> Result<AssetRecord, String> {
+    review(&app, id, ReviewStatus::Approved, reason)
+}
+
+#[tauri::command]
+pub fn reject_asset(app: AppHandle, id: i64, reason: String) -> Result<AssetRecord, String> {
+    review(&app, id, ReviewStatus::Rejected, Some(reason))
+}
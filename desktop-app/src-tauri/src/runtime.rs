@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use tauri::{AppHandle, Manager};
+
+use crate::{access, queue, storage};
+
+const RUNTIME_LIMITS_FILE: &str = "runtime-limits.json";
+const MAX_THREADS: usize = 256;
+const MAX_PREVIEW_WORKERS: usize = 32;
+const MAX_DOWNLOAD_CONNECTIONS: usize = 16;
+const MAX_DB_BATCH_SIZE: usize = 1_000_000;
+
+// Unset limits are detected from the machine each time they are read.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct RuntimeLimits {
+    // Threads reading and analysing asset files after a scan.
+    analysis_threads: Option<usize>,
+    // Audio previews decoded at the same time.
+    preview_workers: Option<usize>,
+    // Marketplace ingestions that download packages at the same time.
+    download_connections: Option<usize>,
+    // Asset rows written per catalog transaction.
+    db_batch_size: Option<usize>,
+}
+
+impl RuntimeLimits {
+    pub fn new(
+        analysis_threads: usize,
+        preview_workers: usize,
+        download_connections: usize,
+        db_batch_size: usize,
+    ) -> Self {
+        RuntimeLimits {
+            analysis_threads: Some(analysis_threads),
+            preview_workers: Some(preview_workers),
+            download_connections: Some(download_connections),
+            db_batch_size: Some(db_batch_size),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EffectiveLimits {
+    pub analysis_threads: usize,
+    pub preview_workers: usize,
+    pub download_connections: usize,
+    pub db_batch_size: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RuntimeLimitsInfo {
+    cores: usize,
+    configured: RuntimeLimits,
+    effective: EffectiveLimits,
+}
+
+fn cores() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// One core is left for the UI; previews and downloads are bounded by disk
+// and marketplace rate limits rather than cores, so they grow slowly.
+fn detected(cores: usize) -> EffectiveLimits {
+    EffectiveLimits {
+        analysis_threads: cores.saturating_sub(1).clamp(1, 32),
+        preview_workers: (cores / 4).clamp(1, 4),
+        download_connections: 2,
+        db_batch_size: 5_000,
+    }
+}
+
+fn resolve(limits: &RuntimeLimits) -> EffectiveLimits {
+    let auto = detected(cores());
+    EffectiveLimits {
+        analysis_threads: limits.analysis_threads.unwrap_or(auto.analysis_threads),
+        preview_workers: limits.preview_workers.unwrap_or(auto.preview_workers),
+        download_connections: limits
+            .download_connections
+            .unwrap_or(auto.download_connections),
+        db_batch_size: limits.db_batch_size.unwrap_or(auto.db_batch_size),
+    }
+}
+
+// Holds the configured limits, which are read on every scan, preview and
+// catalog write.
+#[derive(Default)]
+pub struct RuntimeState {
+    // Loaded on first use and replaced when the settings are saved.
+    configured: Mutex<Option<RuntimeLimits>>,
+}
+
+fn configured(app: &AppHandle) -> RuntimeLimits {
+    app.state::<RuntimeState>()
+        .configured
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| storage::load_json(app, RUNTIME_LIMITS_FILE))
+        .clone()
+}
+
+pub fn limits(app: &AppHandle) -> EffectiveLimits {
+    resolve(&configured(app))
+}
+
+// Applies `f` to every item on up to `analysis_threads` threads, keeping the
+// results in input order.
+pub fn parallel_map<T, R, F>(app: &AppHandle, items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    parallel_map_on(limits(app).analysis_threads, items, f)
+}
+
+pub fn parallel_map_on<T, R, F>(threads: usize, items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = threads.min(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[tauri::command]
+pub fn get_runtime_limits(app: AppHandle) -> RuntimeLimitsInfo {
+    let configured = configured(&app);
+    RuntimeLimitsInfo {
+        cores: cores(),
+        effective: resolve(&configured),
+        configured,
+    }
+}
+
+fn check_range(name: &str, value: Option<usize>, max: usize) -> Result<(), String> {
+    match value {
+        Some(value) if !(1..=max).contains(&value) => {
+            Err(format!("{} must be between 1 and {}", name, max))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Pass `null` for a limit to go back to autodetection.
+#[tauri::command]
+pub fn set_runtime_limits(
+    app: AppHandle,
+    limits: RuntimeLimits,
+) -> Result<RuntimeLimitsInfo, String> {
+    access::ensure_writable(&app)?;
+    check_range("Analysis threads", limits.analysis_threads, MAX_THREADS)?;
+    check_range(
+        "Preview workers",
+        limits.preview_workers,
+        MAX_PREVIEW_WORKERS,
+    )?;
+    check_range(
+        "Download connections",
+        limits.download_connections,
+        MAX_DOWNLOAD_CONNECTIONS,
+    )?;
+    check_range("DB batch size", limits.db_batch_size, MAX_DB_BATCH_SIZE)?;
+    storage::save_json(&app, RUNTIME_LIMITS_FILE, &limits)?;
+    *app.state::<RuntimeState>().configured.lock().unwrap() = Some(limits);
+    // A higher download limit may let queued jobs start.
+    queue::pump(&app);
+    Ok(get_runtime_limits(app))
+}
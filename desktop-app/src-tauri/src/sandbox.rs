@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::{access, storage};
+
+const SANDBOX_FILE: &str = "sandbox.json";
+const STAGING_DIR: &str = "sandbox";
+
+// Marketplace sources whose ingestions run sandboxed. A job's own `sandbox`
+// flag still overrides the setting for that run.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SandboxSettings {
+    sources: BTreeMap<String, bool>,
+}
+
+pub fn enabled_for(app: &AppHandle, source: &str, requested: Option<bool>) -> bool {
+    requested.unwrap_or_else(|| {
+        let settings: SandboxSettings = storage::load_json(app, SANDBOX_FILE);
+        settings.sources.get(source).copied().unwrap_or(false)
+    })
+}
+
+// Restricts what a spawned ingestion process can see: the tool is readable,
+// and writes are confined to a per-job staging directory, the output
+// directory, the tool's virtualenv and uv's cache. Marketplace sources
+// always need the network, so it is left available.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct SandboxPolicy {
+    read_only: Vec<PathBuf>,
+    writable: Vec<PathBuf>,
+    staging: PathBuf,
+    cache: Option<PathBuf>,
+}
+
+impl SandboxPolicy {
+    pub fn for_ingestion(
+        app: &AppHandle,
+        job_id: &str,
+        tool_dir: &str,
+        output_dir: Option<&str>,
+    ) -> Result<Self, String> {
+        let staging = storage::data_path(app, &format!("{}/{}", STAGING_DIR, job_id))?;
+        std::fs::create_dir_all(&staging)
+            .map_err(|e| format!("Failed to create {}: {}", staging.display(), e))?;
+        let tool_dir = canonical(PathBuf::from(tool_dir));
+        let cache = uv_cache_dir().map(canonical);
+
+        let mut read_only = vec![tool_dir.clone()];
+        read_only.extend(uv_python_dir().map(canonical));
+        let mut writable = vec![canonical(staging.clone()), tool_dir.join(".venv")];
+        writable.extend(output_dir.map(|dir| canonical(PathBuf::from(dir))));
+        writable.extend(cache.clone());
+
+        Ok(SandboxPolicy {
+            read_only,
+            writable,
+            staging: canonical(staging),
+            cache,
+        })
+    }
+
+    // Points temporary files and the home directory at the staging directory,
+    // since nothing else outside the writable set exists inside the sandbox.
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        let staging = self.staging.to_string_lossy().to_string();
+        let mut env = vec![
+            ("TMPDIR", staging.clone()),
+            ("TEMP", staging.clone()),
+            ("TMP", staging.clone()),
+            ("HOME", staging),
+        ];
+        if let Some(cache) = &self.cache {
+            env.push(("UV_CACHE_DIR", cache.to_string_lossy().to_string()));
+        }
+        env
+    }
+
+    pub fn discard(&self) {
+        let _ = std::fs::remove_dir_all(&self.staging);
+    }
+}
+
+fn canonical(path: PathBuf) -> PathBuf {
+    std::fs::canonicalize(&path).unwrap_or(path)
+}
+
+fn uv_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("UV_CACHE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    if cfg!(target_os = "macos") {
+        Some(home.join("Library/Caches/uv"))
+    } else {
+        Some(
+            std::env::var_os("XDG_CACHE_HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| home.join(".cache"))
+                .join("uv"),
+        )
+    }
+}
+
+// Interpreters installed by `uv python install`, which the tool's virtualenv
+// links back to.
+fn uv_python_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("UV_PYTHON_INSTALL_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let home = PathBuf::from(std::env::var_os("HOME")?);
+    Some(
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".local/share"))
+            .join("uv/python"),
+    )
+}
+
+#[tauri::command]
+pub fn get_sandbox_settings(app: AppHandle) -> SandboxSettings {
+    storage::load_json(&app, SANDBOX_FILE)
+}
+
+#[tauri::command]
+pub fn set_sandbox_settings(app: AppHandle, settings: SandboxSettings) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    storage::save_json(&app, SANDBOX_FILE, &settings)
+}
+
+// Returns the program and arguments that run `program args` under the policy.
+#[cfg(target_os = "macos")]
+pub fn wrap(
+    program: &str,
+    args: Vec<String>,
+    policy: &SandboxPolicy,
+) -> Result<(String, Vec<String>), String> {
+    let mut profile = String::from(
+        "(version 1)\n(allow default)\n(deny file-write*)\n\
+         (allow file-write* (literal \"/dev/null\") (regex #\"^/dev/tty\") \
+         (subpath \"/private/var/folders\"))\n",
+    );
+    for dir in &policy.writable {
+        // Writable directories must exist before the profile can match them.
+        if std::fs::create_dir_all(dir).is_err() {
+            continue;
+        }
+        profile.push_str(&format!(
+            "(allow file-write* (subpath \"{}\"))\n",
+            escape_sbpl(dir)
+        ));
+    }
+
+    let mut wrapped = vec!["-p".to_string(), profile, program.to_string()];
+    wrapped.extend(args);
+    Ok(("/usr/bin/sandbox-exec".to_string(), wrapped))
+}
+
+#[cfg(target_os = "macos")]
+fn escape_sbpl(path: &std::path::Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+}
+
+// System directories the interpreter and its shared libraries load from.
+#[cfg(target_os = "linux")]
+const SYSTEM_DIRS: &[&str] = &["/usr", "/bin", "/sbin", "/lib", "/lib64", "/etc"];
+
+#[cfg(target_os = "linux")]
+pub fn wrap(
+    program: &str,
+    args: Vec<String>,
+    policy: &SandboxPolicy,
+) -> Result<(String, Vec<String>), String> {
+    let bwrap = find_on_path("bwrap")
+        .ok_or("Sandboxing on Linux requires bubblewrap (bwrap) to be installed")?;
+    let program_path = if program.contains('/') {
+        PathBuf::from(program)
+    } else {
+        find_on_path(program).ok_or_else(|| format!("{} was not found on PATH", program))?
+    };
+
+    let mut wrapped = Vec::new();
+    let mut bind = |flag: &str, dir: &std::path::Path| {
+        let dir = dir.to_string_lossy().to_string();
+        wrapped.extend([flag.to_string(), dir.clone(), dir]);
+    };
+    for dir in SYSTEM_DIRS {
+        bind("--ro-bind-try", std::path::Path::new(dir));
+    }
+    // The binary may be a symlink into another directory, e.g. ~/.cargo/bin.
+    for path in [program_path.clone(), canonical(program_path)] {
+        if let Some(parent) = path.parent() {
+            bind("--ro-bind", parent);
+        }
+    }
+    for dir in &policy.read_only {
+        bind("--ro-bind-try", dir);
+    }
+    // Writable directories come last so they are mounted over read-only
+    // parents such as the tool directory. bwrap refuses to bind paths that
+    // do not exist yet.
+    for dir in &policy.writable {
+        if std::fs::create_dir_all(dir).is_ok() {
+            bind("--bind", dir);
+        }
+    }
+    wrapped.extend(
+        [
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--unshare-all",
+            "--share-net",
+            "--die-with-parent",
+            "--",
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    wrapped.push(program.to_string());
+    wrapped.extend(args);
+    Ok((bwrap.to_string_lossy().to_string(), wrapped))
+}
+
+#[cfg(target_os = "linux")]
+fn find_on_path(binary: &str) -> Option<PathBuf> {
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(binary))
+        .find(|candidate| candidate.is_file())
+}
+
+// AppContainer/job-object isolation is not implemented; refuse rather than
+// silently running a job the user asked to sandbox without one.
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn wrap(
+    _program: &str,
+    _args: Vec<String>,
+    _policy: &SandboxPolicy,
+) -> Result<(String, Vec<String>), String> {
+    Err("Sandboxed ingestion is not supported on this platform".to_string())
+}
@@ -0,0 +1,181 @@
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::catalog::{asset_from_row, with_connection, AssetRecord, ASSET_COLUMNS};
+use crate::variant_groups;
+
+const DEFAULT_LIMIT: u32 = 100;
+const MAX_QUERY_TERMS: usize = 16;
+// Extra rows fetched when variants are collapsed, so a page still fills up.
+const VARIANT_OVERFETCH: u32 = 4;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchHit {
+    asset: AssetRecord,
+    // bm25 score, lower is better; exposed so the UI can show relevance.
+    score: f64,
+    // Other LOD and resolution versions of the same asset that also matched.
+    variants: Vec<AssetRecord>,
+}
+
+// Keeps the best-scoring file of each variant group and nests the rest
+// under it.
+fn collapse_variants(hits: Vec<SearchHit>) -> Vec<SearchHit> {
+    let keys: Vec<(String, variant_groups::Variant)> = hits
+        .iter()
+        .map(|hit| {
+            (
+                hit.asset.pack_id().to_string(),
+                variant_groups::identify(hit.asset.relative_path()),
+            )
+        })
+        .collect();
+    let grouped: Vec<(&str, &str)> = {
+        let mut by_pack: HashMap<&str, Vec<&variant_groups::Variant>> = HashMap::new();
+        for (pack_id, variant) in &keys {
+            by_pack.entry(pack_id).or_default().push(variant);
+        }
+        by_pack
+            .into_iter()
+            .flat_map(|(pack_id, variants)| {
+                variant_groups::variant_groups(variants)
+                    .into_iter()
+                    .map(move |group| (pack_id, group))
+            })
+            .collect()
+    };
+
+    let mut collapsed: Vec<SearchHit> = Vec::new();
+    let mut leaders: HashMap<(&str, &str), usize> = HashMap::new();
+    for (hit, (pack_id, variant)) in hits.into_iter().zip(&keys) {
+        let key = (pack_id.as_str(), variant.group.as_str());
+        if !grouped.contains(&key) {
+            collapsed.push(hit);
+            continue;
+        }
+        match leaders.get(&key) {
+            Some(&leader) => collapsed[leader].variants.push(hit.asset),
+            None => {
+                leaders.insert(key, collapsed.len());
+                collapsed.push(hit);
+            }
+        }
+    }
+    collapsed
+}
+
+// Free text is reduced to word tokens and each one is matched as a quoted
+// prefix, so user input can never be interpreted as FTS5 query syntax.
+pub fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(MAX_QUERY_TERMS)
+        .map(|term| format!("\"{}\"*", term.to_lowercase()))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+// Approved assets matching `fts`, best first.
+fn ranked(conn: &Connection, fts: &str, limit: u32) -> Result<Vec<SearchHit>, String> {
+    // Column weights follow the FTS table order: pack_name, path, tags,
+    // source, license.
+    let sql = format!(
+        "SELECT {}, bm25(asset_search, 4.0, 6.0, 5.0, 1.0, 1.0) AS score
+         FROM asset_search
+         JOIN assets a ON a.id = asset_search.rowid
+         JOIN packs p ON p.pack_id = a.pack_id
+         WHERE asset_search MATCH ?1 AND a.review_status = 'approved'
+         ORDER BY score
+         LIMIT ?2",
+        ASSET_COLUMNS
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Failed to search assets: {}", e))?;
+    let rows = stmt
+        .query_map(params![fts, limit], |row| {
+            Ok(SearchHit {
+                asset: asset_from_row(row)?,
+                score: row.get("score")?,
+                variants: Vec::new(),
+            })
+        })
+        .map_err(|e| format!("Failed to search assets: {}", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to read search results: {}", e))
+}
+
+#[tauri::command]
+pub fn search_assets(
+    app: AppHandle,
+    query: String,
+    limit: Option<u32>,
+    group_variants: Option<bool>,
+) -> Result<Vec<SearchHit>, String> {
+    let Some(fts) = fts_query(&query) else {
+        return Ok(Vec::new());
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let group_variants = group_variants.unwrap_or(true);
+    let fetch = if group_variants {
+        limit.saturating_mul(VARIANT_OVERFETCH)
+    } else {
+        limit
+    };
+    let hits = with_connection(&app, |conn| ranked(conn, &fts, fetch))?;
+    if !group_variants {
+        return Ok(hits);
+    }
+    let mut hits = collapse_variants(hits);
+    hits.truncate(limit as usize);
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::tests::{catalog_with, manifest};
+
+    fn search(conn: &Connection, query: &str) -> Vec<String> {
+        ranked(conn, &fts_query(query).unwrap(), 50)
+            .unwrap()
+            .into_iter()
+            .map(|hit| hit.asset.relative_path().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn fts_query_strips_syntax() {
+        assert_eq!(
+            fts_query("rock* OR \"tree\" -grass").as_deref(),
+            Some("\"rock\"* \"or\"* \"tree\"* \"grass\"*")
+        );
+        assert_eq!(fts_query("  ()*:  "), None);
+    }
+
+    #[test]
+    fn matches_in_paths_rank_above_pack_names() {
+        let conn = catalog_with(&[
+            manifest("props", "Rock Props", &["Barrel.fbx"]),
+            manifest("nature", "Nature", &["Models/Rock.fbx", "Models/Tree.fbx"]),
+        ]);
+        assert_eq!(search(&conn, "rock"), ["Models/Rock.fbx", "Barrel.fbx"]);
+        assert_eq!(search(&conn, "roc"), ["Models/Rock.fbx", "Barrel.fbx"]);
+        assert_eq!(search(&conn, "tree fbx"), ["Models/Tree.fbx"]);
+        assert!(search(&conn, "castle").is_empty());
+    }
+
+    #[test]
+    fn unapproved_assets_are_not_found() {
+        let conn = catalog_with(&[manifest("nature", "Nature", &["Rock.fbx", "Rocks.fbx"])]);
+        conn.execute(
+            "UPDATE assets SET review_status = 'pending' WHERE relative_path = 'Rock.fbx'",
+            [],
+        )
+        .unwrap();
+        assert_eq!(search(&conn, "rock"), ["Rocks.fbx"]);
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::jobs::now_millis;
+use crate::{access, storage};
+
+const SESSION_FILE: &str = "session.json";
+
+// State needed to put the user back where they were after a relaunch. Wizard
+// state is opaque to the backend and keyed by wizard id.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SessionState {
+    ingestion_path: Option<String>,
+    active_collection: Option<String>,
+    wizards: BTreeMap<String, Value>,
+    updated_at: u64,
+}
+
+// Serializes read-modify-write cycles on the session file.
+#[derive(Default)]
+pub struct SessionStore {
+    lock: Mutex<()>,
+}
+
+fn modify(app: &AppHandle, f: impl FnOnce(&mut SessionState)) -> Result<SessionState, String> {
+    let store = app.state::<SessionStore>();
+    let _guard = store.lock.lock().unwrap();
+    let mut session: SessionState = storage::load_json(app, SESSION_FILE);
+    f(&mut session);
+    session.updated_at = now_millis();
+    storage::save_json(app, SESSION_FILE, &session)?;
+    Ok(session)
+}
+
+// Called when a job starts so the last used ingestion checkout is restored
+// even if the frontend never saved it explicitly.
+pub fn remember_ingestion_path(app: &AppHandle, ingestion_path: &str) {
+    let _ = modify(app, |session| {
+        session.ingestion_path = Some(ingestion_path.to_string())
+    });
+}
+
+#[tauri::command]
+pub fn get_session_state(app: AppHandle, store: State<'_, SessionStore>) -> SessionState {
+    let _guard = store.lock.lock().unwrap();
+    storage::load_json(&app, SESSION_FILE)
+}
+
+#[tauri::command]
+pub fn set_active_collection(
+    app: AppHandle,
+    collection: Option<String>,
+) -> Result<SessionState, String> {
+    access::ensure_writable(&app)?;
+    modify(&app, |session| session.active_collection = collection)
+}
+
+// Passing `None` discards the wizard once it is completed or cancelled.
+#[tauri::command]
+pub fn save_wizard_state(
+    app: AppHandle,
+    wizard: String,
+    state: Option<Value>,
+) -> Result<SessionState, String> {
+    access::ensure_writable(&app)?;
+    modify(&app, |session| match state {
+        Some(state) => {
+            session.wizards.insert(wizard, state);
+        }
+        None => {
+            session.wizards.remove(&wizard);
+        }
+    })
+}
@@ -0,0 +1,63 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::catalog;
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Readiness {
+    Starting,
+    Ready,
+    Failed { error: String },
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct SubsystemEvent {
+    subsystem: &'static str,
+    #[serde(flatten)]
+    readiness: Readiness,
+}
+
+// Subsystems that are slow to open are started after the window is shown.
+// Commands still work before then; they wait for the subsystem instead of the
+// whole app.
+#[derive(Default)]
+pub struct StartupState {
+    subsystems: Mutex<BTreeMap<&'static str, Readiness>>,
+}
+
+fn set(app: &AppHandle, subsystem: &'static str, readiness: Readiness) {
+    app.state::<StartupState>()
+        .subsystems
+        .lock()
+        .unwrap()
+        .insert(subsystem, readiness.clone());
+    let _ = app.emit(
+        "subsystem-status",
+        SubsystemEvent {
+            subsystem,
+            readiness,
+        },
+    );
+}
+
+// Opening the catalog runs migrations and, on first launch after an upgrade,
+// backfills the search index, which can take seconds on large libraries.
+pub fn start(app: &AppHandle) {
+    set(app, "catalog", Readiness::Starting);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let readiness = match catalog::with_connection(&app, |_| Ok(())) {
+            Ok(()) => Readiness::Ready,
+            Err(error) => Readiness::Failed { error },
+        };
+        set(&app, "catalog", readiness);
+    });
+}
+
+#[tauri::command]
+pub fn get_startup_status(state: State<'_, StartupState>) -> BTreeMap<&'static str, Readiness> {
+    state.subsystems.lock().unwrap().clone()
+}
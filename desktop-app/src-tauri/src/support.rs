@@ -0,0 +1,225 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::{access, environment, jobs, memory, preflight, runtime, startup, uv_binary};
+
+const REDACTED: &str = "[redacted]";
+const SECRET_KEYS: &[&str] = &[
+    "token",
+    "secret",
+    "password",
+    "cookie",
+    "credential",
+    "auth",
+    "api_key",
+    "apikey",
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BundleFile {
+    name: String,
+    size_bytes: u64,
+    // Exactly what will be written to the zip, so it can be reviewed first.
+    content: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BundleEntry {
+    name: String,
+    size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SupportBundle {
+    path: String,
+    files: Vec<BundleEntry>,
+}
+
+fn is_secret(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SECRET_KEYS.iter().any(|secret| key.contains(secret))
+}
+
+// Values under `env` are passed to uv and often hold index tokens, so they
+// are hidden whatever their names.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret(key) {
+                    *value = json!(REDACTED);
+                } else if key == "env" {
+                    if let Value::Object(env) = value {
+                        for value in env.values_mut() {
+                            *value = json!(REDACTED);
+                        }
+                    }
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn file(name: impl Into<String>, value: &impl Serialize) -> Result<BundleFile, String> {
+    let name = name.into();
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    Ok(BundleFile {
+        name,
+        size_bytes: content.len() as u64,
+        content,
+    })
+}
+
+// Settings stored by the app. Credentials live in the system keyring and are
+// never read here.
+fn settings(app: &AppHandle) -> Result<Vec<BundleFile>, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data directory: {}", e))?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    let mut files = Vec::new();
+    for path in paths {
+        let Some(mut value) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        else {
+            continue;
+        };
+        redact(&mut value);
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        files.push(file(format!("settings/{}", name), &value)?);
+    }
+    Ok(files)
+}
+
+pub fn jobs(app: &AppHandle) -> Value {
+    let registry = jobs::registry(app);
+    let jobs: Vec<Value> = registry
+        .list()
+        .into_iter()
+        .map(|status| {
+            let mut job = json!(status);
+            job["recent_output"] = json!(registry.recent_output(&status.id));
+            job
+        })
+        .collect();
+    json!(jobs)
+}
+
+pub fn environment(app: &AppHandle) -> Value {
+    let package = app.package_info();
+    json!({
+        "app": { "name": package.name, "version": package.version.to_string() },
+        "os": std::env::consts::OS,
+        "family": std::env::consts::FAMILY,
+        "arch": std::env::consts::ARCH,
+        "runtime_limits": runtime::get_runtime_limits(app.clone()),
+        "memory": memory::get_memory_report(app.clone()),
+        "startup": startup::get_startup_status(app.state()),
+        "access": access::get_access_mode(app.state()),
+        "uv_program": uv_binary::program(app),
+    })
+}
+
+// Diagnostics run the same checks as the setup screen; failures are kept in
+// the bundle since they are usually what the report is about.
+async fn diagnostics(app: &AppHandle, ingestion_path: &str) -> Value {
+    let environment = environment::get_environment_info(app.clone(), ingestion_path.to_string())
+        .await
+        .map_or_else(|e| json!({ "error": e }), |info| json!(info));
+    let mut preflight = serde_json::Map::new();
+    for source in ["filesystem", "fab", "uas"] {
+        let report = preflight::preflight_check(
+            app.clone(),
+            source.to_string(),
+            ingestion_path.to_string(),
+            None,
+        )
+        .await
+        .map_or_else(|e| json!({ "error": e }), |report| json!(report));
+        preflight.insert(source.to_string(), report);
+    }
+    json!({ "environment": environment, "preflight": preflight })
+}
+
+async fn collect(app: &AppHandle, ingestion_path: Option<&str>) -> Result<Vec<BundleFile>, String> {
+    let mut files = vec![
+        file("environment.json", &environment(app))?,
+        file("jobs.json", &jobs(app))?,
+    ];
+    if let Some(path) = ingestion_path.filter(|path| !path.trim().is_empty()) {
+        files.push(file("diagnostics.json", &diagnostics(app, path).await)?);
+    }
+    files.extend(settings(app)?);
+    Ok(files)
+}
+
+// Lists every file `create_support_bundle` would write, with its contents,
+// so the user can see what leaves the machine.
+#[tauri::command]
+pub async fn preview_support_bundle(
+    app: AppHandle,
+    ingestion_path: Option<String>,
+) -> Result<Vec<BundleFile>, String> {
+    collect(&app, ingestion_path.as_deref()).await
+}
+
+fn write_zip(destination: &Path, files: &[BundleFile]) -> Result<(), String> {
+    let write_err = |e: String| format!("Failed to write {}: {}", destination.display(), e);
+    let out = File::create(destination).map_err(|e| write_err(e.to_string()))?;
+    let mut zip = ZipWriter::new(out);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for file in files {
+        zip.start_file(file.name.as_str(), options)
+            .map_err(|e| write_err(e.to_string()))?;
+        zip.write_all(file.content.as_bytes())
+            .map_err(|e| write_err(e.to_string()))?;
+    }
+    zip.finish().map_err(|e| write_err(e.to_string()))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn create_support_bundle(
+    app: AppHandle,
+    destination: String,
+    ingestion_path: Option<String>,
+) -> Result<SupportBundle, String> {
+    access::ensure_writable(&app)?;
+    let files = collect(&app, ingestion_path.as_deref()).await?;
+    let entries = files
+        .iter()
+        .map(|file| BundleEntry {
+            name: file.name.clone(),
+            size_bytes: file.size_bytes,
+        })
+        .collect();
+    let path = destination.clone();
+    tauri::async_runtime::spawn_blocking(move || write_zip(Path::new(&path), &files))
+        .await
+        .map_err(|e| format!("Failed to write support bundle: {}", e))??;
+    Ok(SupportBundle {
+        path: destination,
+        files: entries,
+    })
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::access;
+use crate::storage;
+
+const CACHE_FILE: &str = "uv-sync-cache.json";
+const FINGERPRINT_FILES: &[&str] = &["pyproject.toml", "uv.lock"];
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct SyncCache {
+    entries: HashMap<String, String>,
+}
+
+fn cache_key(working_dir: &str, extra: &str) -> String {
+    let dir = fs::canonicalize(working_dir)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| working_dir.to_string());
+    format!("{}::{}", dir, extra)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Hash of the files uv resolves from; a change to either means the
+// environment may no longer match what was last synced.
+pub fn fingerprint(working_dir: &Path) -> Option<String> {
+    let mut hasher = Sha256::new();
+    for name in FINGERPRINT_FILES {
+        match fs::read(working_dir.join(name)) {
+            Ok(bytes) => {
+                hasher.update(name.as_bytes());
+                hasher.update((bytes.len() as u64).to_le_bytes());
+                hasher.update(&bytes);
+            }
+            Err(_) if *name == "uv.lock" => hasher.update(b"no-lockfile"),
+            Err(_) => return None,
+        }
+    }
+    Some(to_hex(&hasher.finalize()))
+}
+
+pub fn is_fresh(app: &AppHandle, working_dir: &str, extra: &str) -> bool {
+    if !Path::new(working_dir).join(".venv").is_dir() {
+        return false;
+    }
+    let Some(current) = fingerprint(Path::new(working_dir)) else {
+        return false;
+    };
+    let cache: SyncCache = storage::load_json(app, CACHE_FILE);
+    cache.entries.get(&cache_key(working_dir, extra)) == Some(&current)
+}
+
+pub fn mark_synced(app: &AppHandle, working_dir: &str, extra: &str) {
+    let Some(current) = fingerprint(Path::new(working_dir)) else {
+        return;
+    };
+    let mut cache: SyncCache = storage::load_json(app, CACHE_FILE);
+    cache.entries.insert(cache_key(working_dir, extra), current);
+    let _ = storage::save_json(app, CACHE_FILE, &cache);
+}
+
+#[tauri::command]
+pub fn clear_uv_sync_cache(app: AppHandle) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    storage::save_json(&app, CACHE_FILE, &SyncCache::default())
+}
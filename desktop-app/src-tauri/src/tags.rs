@@ -0,0 +1,226 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub const SEPARATOR: char = '/';
+const MAX_TAG_LENGTH: usize = 100;
+
+// Tags are hierarchical paths such as `audio/sfx/footsteps`. Segments are
+// trimmed and lowercased so `Audio / SFX` and `audio/sfx` name the same node.
+pub fn normalize(raw: &str) -> Result<String, String> {
+    let tag = raw
+        .split(SEPARATOR)
+        .map(|segment| segment.trim().to_lowercase())
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    if tag.is_empty() {
+        return Err(format!("Invalid tag: '{}'", raw));
+    }
+    if tag.len() > MAX_TAG_LENGTH {
+        return Err(format!(
+            "Tag '{}' exceeds {} characters",
+            tag, MAX_TAG_LENGTH
+        ));
+    }
+    Ok(tag)
+}
+
+pub fn normalize_all(raw: &[String]) -> Result<Vec<String>, String> {
+    let mut tags = Vec::with_capacity(raw.len());
+    for tag in raw {
+        let tag = normalize(tag)?;
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    Ok(tags)
+}
+
+// A query matches the tag itself and every descendant, so `audio` finds
+// assets tagged `audio/sfx/footsteps`.
+pub fn matches(tag: &str, query: &str) -> bool {
+    tag == query
+        || (tag.len() > query.len()
+            && tag.starts_with(query)
+            && tag[query.len()..].starts_with(SEPARATOR))
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TagNode {
+    name: String,
+    path: String,
+    // Number of times this exact tag is used, excluding descendants.
+    count: u64,
+    total: u64,
+    children: Vec<TagNode>,
+}
+
+#[derive(Default)]
+struct TreeBuilder {
+    count: u64,
+    children: BTreeMap<String, TreeBuilder>,
+}
+
+impl TreeBuilder {
+    fn insert(&mut self, tag: &str) {
+        let mut node = self;
+        for segment in tag.split(SEPARATOR) {
+            node = node.children.entry(segment.to_string()).or_default();
+        }
+        node.count += 1;
+    }
+
+    fn finish(self, prefix: &str) -> Vec<TagNode> {
+        self.children
+            .into_iter()
+            .map(|(name, builder)| {
+                let path = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}{}{}", prefix, SEPARATOR, name)
+                };
+                let count = builder.count;
+                let children = builder.finish(&path);
+                let total = count + children.iter().map(|child| child.total).sum::<u64>();
+                TagNode {
+                    name,
+                    path,
+                    count,
+                    total,
+                    children,
+                }
+            })
+            .collect()
+    }
+}
+
+pub fn build_tree<'a>(tags: impl IntoIterator<Item = &'a str>) -> Vec<TagNode> {
+    let mut root = TreeBuilder::default();
+    for tag in tags {
+        if let Ok(tag) = normalize(tag) {
+            root.insert(&tag);
+        }
+    }
+    root.finish("")
+}
+
+fn string_array(value: Option<&Value>) -> Vec<&str> {
+    value
+        .and_then(Value::as_array)
+        .map(|tags| tags.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn manifest_tags(manifest: &Value) -> (Vec<&str>, Vec<(&str, Vec<&str>)>) {
+    let global = string_array(manifest.get("global_tags"));
+    let assets = manifest
+        .get("assets")
+        .and_then(Value::as_array)
+        .map(|assets| {
+            assets
+                .iter()
+                .map(|asset| {
+                    (
+                        asset["relative_path"].as_str().unwrap_or_default(),
+                        string_array(asset.get("local_tags")),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (global, assets)
+}
+
+fn parse_manifests(manifest_json: &str) -> Result<Vec<Value>, String> {
+    serde_json::Deserializer::from_str(manifest_json)
+        .into_iter::<Value>()
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Invalid manifest JSON: {}", e))
+}
+
+#[tauri::command]
+pub fn get_tag_tree(manifest_json: String) -> Result<Vec<TagNode>, String> {
+    let manifests = parse_manifests(&manifest_json)?;
+    let mut tags = Vec::new();
+    for manifest in &manifests {
+        let (global, assets) = manifest_tags(manifest);
+        tags.extend(global);
+        for (_, local) in assets {
+            tags.extend(local);
+        }
+    }
+    Ok(build_tree(tags))
+}
+
+#[tauri::command]
+pub fn find_assets_by_tag(manifest_json: String, tag: String) -> Result<Vec<String>, String> {
+    let query = normalize(&tag)?;
+    let manifests = parse_manifests(&manifest_json)?;
+    let is_match = |tags: &[&str]| {
+        tags.iter()
+            .filter_map(|tag| normalize(tag).ok())
+            .any(|tag| matches(&tag, &query))
+    };
+
+    let mut paths = Vec::new();
+    for manifest in &manifests {
+        let (global, assets) = manifest_tags(manifest);
+        let pack_matches = is_match(&global);
+        paths.extend(
+            assets
+                .into_iter()
+                .filter(|(_, local)| pack_matches || is_match(local))
+                .map(|(path, _)| path.to_string()),
+        );
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_trims_and_lowercases_segments() {
+        assert_eq!(
+            normalize(" Audio / SFX //Footsteps ").unwrap(),
+            "audio/sfx/footsteps"
+        );
+        assert!(normalize(" / ").is_err());
+        assert!(normalize(&"a".repeat(MAX_TAG_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn normalize_all_drops_duplicates() {
+        let raw = ["Audio".to_string(), "audio ".to_string(), "vfx".to_string()];
+        assert_eq!(normalize_all(&raw).unwrap(), ["audio", "vfx"]);
+    }
+
+    #[test]
+    fn matches_descendants_only_at_segment_boundaries() {
+        assert!(matches("audio", "audio"));
+        assert!(matches("audio/sfx", "audio"));
+        assert!(!matches("audiobooks", "audio"));
+        assert!(!matches("audio", "audio/sfx"));
+    }
+
+    #[test]
+    fn build_tree_counts_exact_and_total_uses() {
+        let tree = build_tree(["audio/sfx", "Audio", "audio/sfx/steps", "vfx", ""]);
+        assert_eq!(tree.len(), 2);
+        let audio = &tree[0];
+        assert_eq!(
+            (audio.path.as_str(), audio.count, audio.total),
+            ("audio", 1, 3)
+        );
+        let sfx = &audio.children[0];
+        assert_eq!(
+            (sfx.path.as_str(), sfx.count, sfx.total),
+            ("audio/sfx", 1, 2)
+        );
+        assert_eq!(sfx.children[0].path, "audio/sfx/steps");
+        assert_eq!(tree[1].total, 1);
+    }
+}
@@ -0,0 +1,324 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+const MAX_SCAN_DEPTH: usize = 8;
+const MAX_TEXTURES: usize = 5000;
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MapRole {
+    BaseColor,
+    Normal,
+    // Occlusion/roughness/metallic packed into RGB (ORM, ARM, MRA, ...).
+    PackedRgb,
+    // Unity HDRP style mask map, which also uses the alpha channel.
+    PackedRgba,
+    Data,
+}
+
+impl MapRole {
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        let role = match suffix {
+            "basecolor" | "base_color" | "albedo" | "diffuse" | "color" | "col" | "bc" | "d" => {
+                MapRole::BaseColor
+            }
+            "normal" | "normalmap" | "nrm" | "nor" | "n" | "normal_gl" | "normal_dx" => {
+                MapRole::Normal
+            }
+            "orm" | "arm" | "mra" | "rma" | "orme" => MapRole::PackedRgb,
+            "mask" | "maskmap" | "mask_map" => MapRole::PackedRgba,
+            "roughness" | "rough" | "metallic" | "metalness" | "ao" | "occlusion" | "height"
+            | "displacement" | "emissive" | "emission" | "opacity" | "r" | "m" => MapRole::Data,
+            _ => return None,
+        };
+        Some(role)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            MapRole::BaseColor => "Base colour",
+            MapRole::Normal => "Normal",
+            MapRole::PackedRgb | MapRole::PackedRgba => "Packed",
+            MapRole::Data => "Data",
+        }
+    }
+
+    fn min_channels(self) -> u8 {
+        match self {
+            MapRole::PackedRgba => 4,
+            MapRole::Normal | MapRole::PackedRgb => 3,
+            MapRole::BaseColor | MapRole::Data => 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TextureMap {
+    relative_path: String,
+    role: MapRole,
+    width: u32,
+    height: u32,
+    channels: u8,
+    // Only PNG records a colour space; `None` means the format cannot say.
+    srgb: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextureIssueKind {
+    ChannelPacking,
+    SrgbNormal,
+    ResolutionMismatch,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TextureIssue {
+    kind: TextureIssueKind,
+    relative_path: Option<String>,
+    message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TextureSet {
+    name: String,
+    maps: Vec<TextureMap>,
+    issues: Vec<TextureIssue>,
+}
+
+struct Header {
+    width: u32,
+    height: u32,
+    channels: u8,
+    srgb: Option<bool>,
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+// Walks the chunks ahead of the image data, skipping their payloads, to find
+// the dimensions, colour type and any colour-space tag.
+fn read_png(file: &mut File) -> Option<Header> {
+    let mut signature = [0u8; 8];
+    file.read_exact(&mut signature).ok()?;
+    if &signature != PNG_SIGNATURE {
+        return None;
+    }
+    let mut header: Option<Header> = None;
+    loop {
+        let mut chunk = [0u8; 8];
+        if file.read_exact(&mut chunk).is_err() {
+            break;
+        }
+        let length = be_u32(&chunk[..4]);
+        match &chunk[4..] {
+            b"IHDR" => {
+                let mut data = [0u8; 13];
+                file.read_exact(&mut data).ok()?;
+                let channels = match data[9] {
+                    0 | 3 => 1,
+                    4 => 2,
+                    2 => 3,
+                    6 => 4,
+                    _ => return None,
+                };
+                header = Some(Header {
+                    width: be_u32(&data[..4]),
+                    height: be_u32(&data[4..8]),
+                    channels,
+                    srgb: Some(false),
+                });
+                file.seek(SeekFrom::Current(4)).ok()?;
+                continue;
+            }
+            // An sRGB chunk or embedded ICC profile marks the image as colour
+            // data rather than linear.
+            b"sRGB" | b"iCCP" => {
+                if let Some(header) = header.as_mut() {
+                    header.srgb = Some(true);
+                }
+            }
+            b"IDAT" | b"IEND" => break,
+            _ => {}
+        }
+        file.seek(SeekFrom::Current(i64::from(length) + 4)).ok()?;
+    }
+    header
+}
+
+fn read_tga(file: &mut File) -> Option<Header> {
+    let mut data = [0u8; 18];
+    file.read_exact(&mut data).ok()?;
+    let channels = match (data[2], data[16]) {
+        (3 | 11, _) => 1,
+        (_, 32) => 4,
+        (_, 24) => 3,
+        (_, 8) => 1,
+        _ => return None,
+    };
+    Some(Header {
+        width: u32::from(u16::from_le_bytes([data[12], data[13]])),
+        height: u32::from(u16::from_le_bytes([data[14], data[15]])),
+        channels,
+        srgb: None,
+    })
+}
+
+fn read_header(path: &Path, extension: &str) -> Option<Header> {
+    let mut file = File::open(path).ok()?;
+    match extension {
+        "png" => read_png(&mut file),
+        "tga" => read_tga(&mut file),
+        _ => None,
+    }
+}
+
+// "Rock_01_Normal_GL" is set "Rock_01" with role normal.
+fn split_role(stem: &str) -> Option<(String, MapRole)> {
+    let lower = stem.to_ascii_lowercase();
+    let tokens: Vec<&str> = lower.split(['_', '-', ' ', '.']).collect();
+    for taken in [2, 1] {
+        if tokens.len() <= taken {
+            continue;
+        }
+        let suffix = tokens[tokens.len() - taken..].join("_");
+        if let Some(role) = MapRole::from_suffix(&suffix) {
+            let base_len = tokens[..tokens.len() - taken].join("_").len();
+            return Some((stem[..base_len].to_string(), role));
+        }
+    }
+    None
+}
+
+fn collect_maps(
+    root: &Path,
+    dir: &Path,
+    depth: usize,
+    out: &mut BTreeMap<String, Vec<TextureMap>>,
+    count: &mut usize,
+) {
+    if depth > MAX_SCAN_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        if *count >= MAX_TEXTURES {
+            return;
+        }
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if !entry.file_name().to_string_lossy().starts_with('.') {
+                collect_maps(root, &path, depth + 1, out, count);
+            }
+            continue;
+        }
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let Some((base, role)) = split_role(&stem) else {
+            continue;
+        };
+        let Some(header) = read_header(&path, &extension) else {
+            continue;
+        };
+        *count += 1;
+
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let relative_path = relative.to_string_lossy().replace('\\', "/");
+        let set_name = match relative.parent().filter(|p| !p.as_os_str().is_empty()) {
+            Some(parent) => format!("{}/{}", parent.to_string_lossy().replace('\\', "/"), base),
+            None => base,
+        };
+        out.entry(set_name).or_default().push(TextureMap {
+            relative_path,
+            role,
+            width: header.width,
+            height: header.height,
+            channels: header.channels,
+            srgb: header.srgb,
+        });
+    }
+}
+
+fn check_set(maps: &[TextureMap]) -> Vec<TextureIssue> {
+    let mut issues = Vec::new();
+    for map in maps {
+        let required = map.role.min_channels();
+        if map.channels < required {
+            issues.push(TextureIssue {
+                kind: TextureIssueKind::ChannelPacking,
+                relative_path: Some(map.relative_path.clone()),
+                message: format!(
+                    "{} map has {} channel(s), expected at least {}",
+                    map.role.label(),
+                    map.channels,
+                    required
+                ),
+            });
+        }
+        if map.role == MapRole::Normal && map.srgb == Some(true) {
+            issues.push(TextureIssue {
+                kind: TextureIssueKind::SrgbNormal,
+                relative_path: Some(map.relative_path.clone()),
+                message: "Normal map is tagged as sRGB and will be imported as colour data"
+                    .to_string(),
+            });
+        }
+    }
+
+    let mut sizes: Vec<(u32, u32)> = maps.iter().map(|map| (map.width, map.height)).collect();
+    sizes.sort();
+    sizes.dedup();
+    if sizes.len() > 1 {
+        let listed = sizes
+            .iter()
+            .map(|(w, h)| format!("{}x{}", w, h))
+            .collect::<Vec<_>>()
+            .join(", ");
+        issues.push(TextureIssue {
+            kind: TextureIssueKind::ResolutionMismatch,
+            relative_path: None,
+            message: format!("Maps in this set have different resolutions: {}", listed),
+        });
+    }
+    issues
+}
+
+// Groups PNG and TGA maps into PBR sets by folder and base name, and reports
+// the packing, colour-space and resolution problems that otherwise only show
+// up on engine import. Sets of a single, valid map are omitted.
+#[tauri::command]
+pub fn validate_texture_sets(path: String) -> Result<Vec<TextureSet>, String> {
+    let root = Path::new(&path);
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", path));
+    }
+    let mut groups = BTreeMap::new();
+    collect_maps(root, root, 0, &mut groups, &mut 0);
+
+    Ok(groups
+        .into_iter()
+        .map(|(name, maps)| TextureSet {
+            issues: check_set(&maps),
+            name,
+            maps,
+        })
+        .filter(|set| set.maps.len() > 1 || !set.issues.is_empty())
+        .collect())
+}
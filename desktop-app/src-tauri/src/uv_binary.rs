@@ -0,0 +1,339 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_shell::process::{Command, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+use crate::{access, storage};
+
+const UV_CONFIG_FILE: &str = "uv.json";
+const INSTALL_DIR: &str = "uv-bin";
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[cfg(windows)]
+const UV_EXE: &str = "uv.exe";
+#[cfg(not(windows))]
+const UV_EXE: &str = "uv";
+
+#[cfg(windows)]
+const INSTALLER_URL: &str = "https://astral.sh/uv/install.ps1";
+#[cfg(not(windows))]
+const INSTALLER_URL: &str = "https://astral.sh/uv/install.sh";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct UvSettings {
+    path: Option<String>,
+    // Prepended to PATH for uv and everything it launches.
+    extra_path: Vec<String>,
+    env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum UvOrigin {
+    Configured,
+    Path,
+    Sidecar,
+    Installed,
+    InstallDir,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UvLocation {
+    path: String,
+    origin: UvOrigin,
+    version: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+enum InstallProgress {
+    Download { downloaded: u64, total: Option<u64> },
+    Install { line: String },
+    Done { path: String },
+}
+
+// Every uv invocation resolves the binary here, so one found outside PATH or
+// installed by the app is used without the user editing their environment.
+pub fn program(app: &AppHandle) -> String {
+    let settings: UvSettings = storage::load_json(app, UV_CONFIG_FILE);
+    let on_extra_path = settings
+        .extra_path
+        .iter()
+        .map(|dir| Path::new(dir).join(UV_EXE))
+        .find(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string());
+    settings
+        .path
+        .filter(|path| Path::new(path).is_file())
+        .or(on_extra_path)
+        .unwrap_or_else(|| "uv".to_string())
+}
+
+// Applies the configured PATH entries and environment variables, which also
+// reach the sandbox wrapper and the Python processes uv starts.
+pub fn with_env(app: &AppHandle, mut command: Command) -> Command {
+    let settings: UvSettings = storage::load_json(app, UV_CONFIG_FILE);
+    if !settings.extra_path.is_empty() {
+        let inherited = std::env::var_os("PATH").unwrap_or_default();
+        let dirs = settings
+            .extra_path
+            .iter()
+            .map(PathBuf::from)
+            .chain(std::env::split_paths(&inherited));
+        if let Ok(path) = std::env::join_paths(dirs) {
+            command = command.env("PATH", path);
+        }
+    }
+    command.envs(settings.env)
+}
+
+pub fn command(app: &AppHandle) -> Command {
+    with_env(app, app.shell().command(program(app)))
+}
+
+fn install_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_path(app, INSTALL_DIR)
+}
+
+fn home_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    if let Some(home) = home.map(PathBuf::from) {
+        dirs.push(home.join(".local").join("bin"));
+        dirs.push(home.join(".cargo").join("bin"));
+    }
+    if cfg!(unix) {
+        dirs.push(PathBuf::from("/opt/homebrew/bin"));
+        dirs.push(PathBuf::from("/usr/local/bin"));
+    }
+    dirs
+}
+
+fn candidates(app: &AppHandle) -> Vec<(PathBuf, UvOrigin)> {
+    let mut found = Vec::new();
+    let settings: UvSettings = storage::load_json(app, UV_CONFIG_FILE);
+    if let Some(path) = settings.path {
+        found.push((PathBuf::from(path), UvOrigin::Configured));
+    }
+    found.extend(
+        settings
+            .extra_path
+            .iter()
+            .map(|dir| (Path::new(dir).join(UV_EXE), UvOrigin::Configured)),
+    );
+    if let Some(path) = std::env::var_os("PATH") {
+        found.extend(std::env::split_paths(&path).map(|dir| (dir.join(UV_EXE), UvOrigin::Path)));
+    }
+    // Bundled as a Tauri sidecar, which is placed next to the executable.
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        found.push((dir.join(UV_EXE), UvOrigin::Sidecar));
+    }
+    if let Ok(dir) = install_dir(app) {
+        found.push((dir.join(UV_EXE), UvOrigin::Installed));
+    }
+    found.extend(
+        home_dirs()
+            .into_iter()
+            .map(|dir| (dir.join(UV_EXE), UvOrigin::InstallDir)),
+    );
+    found
+}
+
+async fn version(app: &AppHandle, path: &Path) -> Option<String> {
+    with_env(app, app.shell().command(path.to_string_lossy().to_string()))
+        .args(["--version"])
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn remember(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let mut settings: UvSettings = storage::load_json(app, UV_CONFIG_FILE);
+    settings.path = Some(path.to_string_lossy().to_string());
+    storage::save_json(app, UV_CONFIG_FILE, &settings)
+}
+
+// Returns the first working uv and remembers it, or `None` when uv has to be
+// installed.
+#[tauri::command]
+pub async fn detect_uv(app: AppHandle) -> Result<Option<UvLocation>, String> {
+    for (path, origin) in candidates(&app) {
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(version) = version(&app, &path).await {
+            remember(&app, &path)?;
+            return Ok(Some(UvLocation {
+                path: path.to_string_lossy().to_string(),
+                origin,
+                version,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+async fn download_installer(app: &AppHandle) -> Result<Vec<u8>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut response = client
+        .get(INSTALLER_URL)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to download the uv installer: {}", e))?;
+
+    let total = response.content_length();
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to download the uv installer: {}", e))?
+    {
+        body.extend_from_slice(&chunk);
+        let _ = app.emit(
+            "uv-install-progress",
+            InstallProgress::Download {
+                downloaded: body.len() as u64,
+                total,
+            },
+        );
+    }
+    Ok(body)
+}
+
+// Runs the official installer into the app data directory without touching
+// the user's shell profile, then records the installed binary.
+#[tauri::command]
+pub async fn install_uv(app: AppHandle) -> Result<UvLocation, String> {
+    access::ensure_writable(&app)?;
+    let dir = install_dir(&app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let script = download_installer(&app).await?;
+    let script_path = dir.join(if cfg!(windows) {
+        "install.ps1"
+    } else {
+        "install.sh"
+    });
+    fs::write(&script_path, script)
+        .map_err(|e| format!("Failed to write {}: {}", script_path.display(), e))?;
+    let script_arg = script_path.to_string_lossy().to_string();
+
+    let command = if cfg!(windows) {
+        app.shell().command("powershell").args([
+            "-NoProfile",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-File",
+            &script_arg,
+        ])
+    } else {
+        app.shell().command("sh").args([&script_arg])
+    };
+    let (mut rx, _child) = command
+        .env("UV_INSTALL_DIR", &dir)
+        .env("UV_NO_MODIFY_PATH", "1")
+        .env("INSTALLER_NO_MODIFY_PATH", "1")
+        .spawn()
+        .map_err(|e| format!("Failed to run the uv installer: {}", e))?;
+
+    let mut outcome = Err("The uv installer ended unexpectedly".to_string());
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                let line = String::from_utf8_lossy(&line).trim_end().to_string();
+                if !line.is_empty() {
+                    let _ = app.emit("uv-install-progress", InstallProgress::Install { line });
+                }
+            }
+            CommandEvent::Terminated(payload) => {
+                outcome = match payload.code {
+                    Some(0) => Ok(()),
+                    code => Err(format!("The uv installer exited with {:?}", code)),
+                };
+                break;
+            }
+            CommandEvent::Error(err) => {
+                outcome = Err(format!("Failed to run the uv installer: {}", err));
+                break;
+            }
+            _ => {}
+        }
+    }
+    let _ = fs::remove_file(&script_path);
+    outcome?;
+
+    // Older installers put the binary in a `bin` subdirectory.
+    let path = [dir.join(UV_EXE), dir.join("bin").join(UV_EXE)]
+        .into_iter()
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| dir.join(UV_EXE));
+    let version = version(&app, &path)
+        .await
+        .ok_or_else(|| format!("uv was not found at {} after installing", path.display()))?;
+    remember(&app, &path)?;
+    let path = path.to_string_lossy().to_string();
+    let _ = app.emit(
+        "uv-install-progress",
+        InstallProgress::Done { path: path.clone() },
+    );
+    Ok(UvLocation {
+        path,
+        origin: UvOrigin::Installed,
+        version,
+    })
+}
+
+#[tauri::command]
+pub fn get_uv_settings(app: AppHandle) -> UvSettings {
+    storage::load_json(&app, UV_CONFIG_FILE)
+}
+
+// PATH itself is managed through `extra_path` so the inherited entries are
+// kept.
+#[tauri::command]
+pub fn set_uv_settings(
+    app: AppHandle,
+    path: Option<String>,
+    extra_path: Vec<String>,
+    env: BTreeMap<String, String>,
+) -> Result<UvSettings, String> {
+    access::ensure_writable(&app)?;
+    let path = path.filter(|path| !path.trim().is_empty());
+    if let Some(path) = &path {
+        if !Path::new(path).is_file() {
+            return Err(format!("uv was not found at {}", path));
+        }
+    }
+    for key in env.keys() {
+        if key.is_empty() || key.contains('=') || key.contains('\0') {
+            return Err(format!("Invalid environment variable name: \"{}\"", key));
+        }
+        if key.eq_ignore_ascii_case("PATH") {
+            return Err("Use extra PATH entries instead of overriding PATH".to_string());
+        }
+    }
+    let settings = UvSettings {
+        path,
+        extra_path: extra_path
+            .into_iter()
+            .filter(|dir| !dir.trim().is_empty())
+            .collect(),
+        env,
+    };
+    storage::save_json(&app, UV_CONFIG_FILE, &settings)?;
+    Ok(settings)
+}
@@ -0,0 +1,413 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::credentials;
+use crate::error::Error;
+
+const MARKETPLACE_SOURCES: &[&str] = &["fab", "uas"];
+const FAB_STRATEGIES: &[&str] = &["metadata_only", "manifests_only"];
+const UAS_STRATEGIES: &[&str] = &["metadata_only", "manifests_only", "download", "extract"];
+const MAX_VALUE_LENGTH: usize = 2048;
+
+// A uv invocation assembled only through the constructors below, so every
+// argv shape the app can produce is enumerated here and user-supplied values
+// are validated before they reach the child process.
+#[derive(Debug, Clone)]
+pub struct UvCommand {
+    args: Vec<String>,
+    working_dir: PathBuf,
+    env: SecretEnv,
+}
+
+// Environment holding credentials; the values are kept out of debug output.
+#[derive(Clone, Default)]
+struct SecretEnv(Vec<(&'static str, String)>);
+
+impl fmt::Debug for SecretEnv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+// Files dropped when a downloaded package is extracted. Packages arrive
+// whole, so these save disk rather than bandwidth.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DownloadFilters {
+    skip_examples: bool,
+    skip_8k: bool,
+    source_art_only: bool,
+}
+
+impl DownloadFilters {
+    fn is_empty(&self) -> bool {
+        !(self.skip_examples || self.skip_8k || self.source_art_only)
+    }
+}
+
+pub struct MarketplaceArgs<'a> {
+    pub source: &'a str,
+    pub download_strategy: Option<&'a str>,
+    pub output_dir: Option<&'a str>,
+    pub asset_id: Option<&'a str>,
+    pub filters: Option<&'a DownloadFilters>,
+    pub resume_from: Option<&'a Path>,
+}
+
+impl UvCommand {
+    pub fn gui_helper(
+        ingestion_path: &str,
+        market_args: MarketplaceArgs<'_>,
+    ) -> Result<Self, Error> {
+        let working_dir = project_dir(ingestion_path)?;
+        let source = marketplace_source(market_args.source)?;
+
+        let mut args = vec![
+            "run".to_string(),
+            "python".to_string(),
+            "-m".to_string(),
+            "game_asset_tracker_ingestion.gui_helper".to_string(),
+            source.to_string(),
+        ];
+
+        if let Some(strategy) = market_args.download_strategy {
+            let allowed = if source == "fab" {
+                FAB_STRATEGIES
+            } else {
+                UAS_STRATEGIES
+            };
+            if !allowed.contains(&strategy) {
+                return Err(Error::InvalidConfig(format!(
+                    "Unsupported download strategy for {}: {}",
+                    source, strategy
+                )));
+            }
+            args.push("--download-strategy".to_string());
+            args.push(strategy.to_string());
+        }
+
+        if let Some(output_dir) = market_args.output_dir {
+            args.push("--output-dir".to_string());
+            args.push(output_path(output_dir)?.to_string_lossy().to_string());
+        }
+
+        // Only the UAS helper can restrict a run to a single library item.
+        if let Some(asset_id) = market_args.asset_id {
+            if source != "uas" || !asset_id.chars().all(|c| c.is_ascii_digit()) {
+                return Err(Error::InvalidConfig(format!(
+                    "Unsupported asset id for {}: {}",
+                    source, asset_id
+                )));
+            }
+            args.push("--asset-id".to_string());
+            args.push(value(asset_id, "Asset id")?);
+        }
+
+        // Only extraction writes individual package files to disk.
+        if let Some(filters) = market_args.filters.filter(|filters| !filters.is_empty()) {
+            if source != "uas" || market_args.download_strategy != Some("extract") {
+                return Err(Error::InvalidConfig(
+                    "Download filters need the Unity Asset Store extract strategy".to_string(),
+                ));
+            }
+            let spec = serde_json::to_string(filters).map_err(|e| {
+                Error::Internal(format!("Failed to encode download filters: {}", e))
+            })?;
+            args.push("--filters".to_string());
+            args.push(value(&spec, "Download filters")?);
+        }
+
+        // Resuming skips packages a previous run already fetched, so it only
+        // applies to strategies that download them one by one.
+        if let Some(resume_from) = market_args.resume_from {
+            if source != "uas"
+                || !matches!(market_args.download_strategy, Some("download" | "extract"))
+            {
+                return Err(Error::InvalidConfig(
+                    "Only Unity Asset Store downloads can be resumed".to_string(),
+                ));
+            }
+            args.push("--resume-from".to_string());
+            args.push(value(&resume_from.to_string_lossy(), "Resume state")?);
+        }
+
+        Ok(UvCommand {
+            args,
+            working_dir,
+            env: SecretEnv(credentials::environment(source)),
+        })
+    }
+
+    pub fn sync(ingestion_path: &str, extra: &str) -> Result<Self, Error> {
+        Ok(UvCommand {
+            args: vec![
+                "sync".to_string(),
+                "--extra".to_string(),
+                marketplace_source(extra)?.to_string(),
+            ],
+            working_dir: project_dir(ingestion_path)?,
+            env: SecretEnv::default(),
+        })
+    }
+
+    pub fn lock_check(ingestion_path: &str) -> Result<Self, Error> {
+        Ok(UvCommand {
+            args: vec!["lock".to_string(), "--check".to_string()],
+            working_dir: project_dir(ingestion_path)?,
+            env: SecretEnv::default(),
+        })
+    }
+
+    pub fn python_script(ingestion_path: &str, script: &'static str) -> Result<Self, Error> {
+        Ok(UvCommand {
+            args: vec![
+                "run".to_string(),
+                "--no-sync".to_string(),
+                "python".to_string(),
+                "-c".to_string(),
+                script.to_string(),
+            ],
+            working_dir: project_dir(ingestion_path)?,
+            env: SecretEnv::default(),
+        })
+    }
+
+    // Variables the child needs beyond the configured uv environment.
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        self.env.0.clone()
+    }
+
+    pub fn into_parts(self) -> (Vec<String>, PathBuf) {
+        (self.args, self.working_dir)
+    }
+}
+
+fn project_dir(ingestion_path: &str) -> Result<PathBuf, Error> {
+    let dir = existing_dir(ingestion_path, "Ingestion path")?;
+    if !dir.join("pyproject.toml").is_file() {
+        return Err(Error::InvalidPath(format!(
+            "No pyproject.toml found in {}",
+            dir.display()
+        )));
+    }
+    Ok(dir)
+}
+
+fn existing_dir(path: &str, label: &str) -> Result<PathBuf, Error> {
+    let dir = fs::canonicalize(value(path, label)?)
+        .map_err(|e| Error::InvalidPath(format!("{} {} is not accessible: {}", label, path, e)))?;
+    if !dir.is_dir() {
+        return Err(Error::InvalidPath(format!(
+            "{} {} is not a directory",
+            label, path
+        )));
+    }
+    Ok(dir)
+}
+
+// Output directories may not exist yet, so only their parent is required to
+// resolve; the result is always absolute.
+fn output_path(path: &str) -> Result<PathBuf, Error> {
+    let path = PathBuf::from(value(path, "Output directory")?);
+    if !path.is_absolute() {
+        return Err(Error::InvalidPath(format!(
+            "Output directory must be absolute: {}",
+            path.display()
+        )));
+    }
+    if let Ok(resolved) = fs::canonicalize(&path) {
+        return Ok(resolved);
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => fs::canonicalize(parent)
+            .map(|parent| parent.join(name))
+            .map_err(|e| {
+                Error::InvalidPath(format!(
+                    "Output directory {} is not accessible: {}",
+                    path.display(),
+                    e
+                ))
+            }),
+        _ => Err(Error::InvalidPath(format!(
+            "Invalid output directory: {}",
+            path.display()
+        ))),
+    }
+}
+
+fn marketplace_source(source: &str) -> Result<&str, Error> {
+    MARKETPLACE_SOURCES
+        .iter()
+        .find(|allowed| **allowed == source)
+        .copied()
+        .ok_or(Error::InvalidConfig(format!(
+            "Unknown marketplace source: {}",
+            source
+        )))
+}
+
+// Rejects values the ingestion CLI could mistake for options or that cannot
+// be passed through argv intact.
+fn value(raw: &str, label: &str) -> Result<String, Error> {
+    let invalid = |reason: String| Err(Error::InvalidConfig(reason));
+    if raw.trim().is_empty() {
+        return invalid(format!("{} must not be empty", label));
+    }
+    if raw.starts_with('-') {
+        return invalid(format!("{} must not start with '-': {}", label, raw));
+    }
+    if raw.chars().any(char::is_control) {
+        return invalid(format!("{} contains control characters", label));
+    }
+    if raw.len() > MAX_VALUE_LENGTH {
+        return invalid(format!("{} exceeds {} characters", label, MAX_VALUE_LENGTH));
+    }
+    Ok(raw.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("uv-command-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("pyproject.toml"), "[project]\n").unwrap();
+        dir
+    }
+
+    fn market_args<'a>(source: &'a str) -> MarketplaceArgs<'a> {
+        MarketplaceArgs {
+            source,
+            download_strategy: None,
+            output_dir: None,
+            asset_id: None,
+            filters: None,
+            resume_from: None,
+        }
+    }
+
+    fn is_invalid_config(result: Result<UvCommand, Error>) -> bool {
+        matches!(result, Err(Error::InvalidConfig(_)))
+    }
+
+    #[test]
+    fn value_rejects_option_like_and_control_characters() {
+        assert!(value("-rf", "Asset id").is_err());
+        assert!(value("--output-dir=/", "Asset id").is_err());
+        assert!(value("a\nb", "Asset id").is_err());
+        assert!(value("a\0b", "Asset id").is_err());
+        assert!(value("a\u{1b}[31m", "Asset id").is_err());
+        assert!(value("  ", "Asset id").is_err());
+        assert_eq!(value("a-b c", "Asset id").unwrap(), "a-b c");
+    }
+
+    #[test]
+    fn value_enforces_the_length_limit() {
+        assert!(value(&"a".repeat(MAX_VALUE_LENGTH), "Filters").is_ok());
+        assert!(value(&"a".repeat(MAX_VALUE_LENGTH + 1), "Filters").is_err());
+    }
+
+    #[test]
+    fn output_path_must_be_absolute() {
+        assert!(matches!(
+            output_path("relative/out"),
+            Err(Error::InvalidPath(_))
+        ));
+        assert!(matches!(output_path("./out"), Err(Error::InvalidPath(_))));
+    }
+
+    #[test]
+    fn output_path_is_canonicalized() {
+        let dir = project("canonical");
+        let existing = dir.join("out");
+        fs::create_dir_all(&existing).unwrap();
+        let canonical = fs::canonicalize(&dir).unwrap();
+
+        let through_parent = dir.join("out").join("..").join("out");
+        let resolved = output_path(&through_parent.to_string_lossy()).unwrap();
+        assert_eq!(resolved, canonical.join("out"));
+
+        // A missing leaf is allowed as long as its parent resolves.
+        let missing = dir.join("out").join("..").join("new");
+        let resolved = output_path(&missing.to_string_lossy()).unwrap();
+        assert_eq!(resolved, canonical.join("new"));
+
+        let orphan = dir.join("missing").join("new");
+        let result = output_path(&orphan.to_string_lossy());
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(result, Err(Error::InvalidPath(_))));
+    }
+
+    #[test]
+    fn gui_helper_rejects_unknown_sources_and_strategies() {
+        let dir = project("strategies");
+        let path = dir.to_string_lossy().to_string();
+
+        assert!(is_invalid_config(UvCommand::gui_helper(
+            &path,
+            market_args("filesystem")
+        )));
+        for (source, strategy) in [
+            ("fab", "download"),
+            ("fab", "extract"),
+            ("uas", "everything"),
+            ("uas", "--download"),
+        ] {
+            let args = MarketplaceArgs {
+                download_strategy: Some(strategy),
+                ..market_args(source)
+            };
+            assert!(
+                is_invalid_config(UvCommand::gui_helper(&path, args)),
+                "{} accepted {}",
+                source,
+                strategy
+            );
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn gui_helper_rejects_bad_asset_ids() {
+        let dir = project("asset-ids");
+        let path = dir.to_string_lossy().to_string();
+
+        for (source, asset_id) in [
+            ("fab", "12345"),
+            ("uas", "12a45"),
+            ("uas", "-1"),
+            ("uas", "1 2"),
+            ("uas", ""),
+        ] {
+            let args = MarketplaceArgs {
+                asset_id: Some(asset_id),
+                ..market_args(source)
+            };
+            assert!(
+                is_invalid_config(UvCommand::gui_helper(&path, args)),
+                "{} accepted asset id {:?}",
+                source,
+                asset_id
+            );
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn gui_helper_requires_a_project_directory() {
+        let dir = std::env::temp_dir().join(format!("uv-command-empty-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let result = UvCommand::gui_helper(&dir.to_string_lossy(), market_args("uas"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(result, Err(Error::InvalidPath(_))));
+        assert!(matches!(
+            UvCommand::gui_helper("-project", market_args("uas")),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
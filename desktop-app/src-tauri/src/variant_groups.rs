@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use crate::asset_types;
+use crate::manifest::AssetManifest;
+
+const GROUP_KEY: &str = "variant_group";
+const LOD_KEY: &str = "lod";
+const RESOLUTION_KEY: &str = "resolution";
+
+const RESOLUTION_MULTIPLIERS: &[u32] = &[1, 2, 4, 8, 16];
+const RESOLUTION_PIXELS: &[u32] = &[256, 512, 1024, 2048, 4096, 8192];
+
+// One file's place among the level-of-detail and resolution versions of the
+// same logical asset. `Rock_LOD0.fbx`, `Rock_LOD1.fbx` and `Rock.fbx` share a
+// group, as do `Rock_2K_Albedo.png` and `Rock_4K_Albedo.png`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub group: String,
+    // The file stem without its LOD and resolution tokens, as written.
+    pub name: String,
+    lod: Option<u32>,
+    resolution: Option<String>,
+}
+
+impl Variant {
+    fn is_tagged(&self) -> bool {
+        self.lod.is_some() || self.resolution.is_some()
+    }
+}
+
+fn resolution(token: &str) -> Option<String> {
+    if let Some(multiplier) = token.strip_suffix('k').and_then(|n| n.parse::<u32>().ok()) {
+        return RESOLUTION_MULTIPLIERS
+            .contains(&multiplier)
+            .then(|| format!("{}K", multiplier));
+    }
+    token
+        .parse::<u32>()
+        .ok()
+        .filter(|pixels| RESOLUTION_PIXELS.contains(pixels))
+        .map(|pixels| pixels.to_string())
+}
+
+pub fn identify(relative_path: &str) -> Variant {
+    let normalized = relative_path.replace('\\', "/");
+    let (dir, file) = normalized.rsplit_once('/').unwrap_or(("", &normalized));
+    let (stem, extension) = file
+        .rsplit_once('.')
+        .filter(|(stem, _)| !stem.is_empty())
+        .unwrap_or((file, ""));
+
+    let tokens: Vec<&str> = stem
+        .split(['_', '-', ' '])
+        .filter(|token| !token.is_empty())
+        .collect();
+    let mut kept = Vec::new();
+    let mut lod = None;
+    let mut resolution_label = None;
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        let lower = token.to_lowercase();
+        index += 1;
+        // `LOD2` or `LOD_2`.
+        if let Some(rest) = lower.strip_prefix("lod") {
+            let (digits, consumed) = if rest.is_empty() {
+                (tokens.get(index).copied(), 1)
+            } else {
+                (Some(rest), 0)
+            };
+            if let Some(level) = digits.and_then(|digits| digits.parse().ok()) {
+                index += consumed;
+                lod = Some(level);
+                continue;
+            }
+        }
+        if let Some(label) = resolution(&lower) {
+            resolution_label = Some(label);
+            continue;
+        }
+        kept.push(token);
+    }
+
+    // The asset type keeps a mesh and its textures from sharing a group.
+    let asset_type = asset_types::classify(relative_path, extension);
+    let name = kept.join("_");
+    Variant {
+        group: format!(
+            "{}{}{}:{}",
+            dir.to_lowercase(),
+            if dir.is_empty() { "" } else { "/" },
+            name.to_lowercase(),
+            asset_type.as_str()
+        ),
+        name,
+        lod,
+        resolution: resolution_label,
+    }
+}
+
+// Groups that hold a tagged file, so an untagged file is only treated as a
+// variant when its LOD or resolution siblings are present.
+pub fn variant_groups<'a>(variants: impl IntoIterator<Item = &'a Variant>) -> Vec<&'a str> {
+    let mut counts: HashMap<&str, (usize, bool)> = HashMap::new();
+    for variant in variants {
+        let entry = counts.entry(variant.group.as_str()).or_default();
+        entry.0 += 1;
+        entry.1 |= variant.is_tagged();
+    }
+    counts
+        .into_iter()
+        .filter(|(_, (count, tagged))| *count > 1 && *tagged)
+        .map(|(group, _)| group)
+        .collect()
+}
+
+// Records the shared group of each LOD and resolution variant in its metadata,
+// with the level or resolution it carries.
+pub fn annotate(manifests: &mut [AssetManifest]) {
+    for manifest in manifests.iter_mut() {
+        let variants: Vec<Variant> = manifest
+            .assets
+            .iter()
+            .map(|asset| identify(&asset.relative_path))
+            .collect();
+        let groups = variant_groups(&variants);
+        for (asset, variant) in manifest.assets.iter_mut().zip(&variants) {
+            if !groups.contains(&variant.group.as_str()) {
+                continue;
+            }
+            asset
+                .metadata
+                .insert(GROUP_KEY.to_string(), variant.group.clone());
+            if let Some(lod) = variant.lod {
+                asset.metadata.insert(LOD_KEY.to_string(), lod.to_string());
+            }
+            if let Some(resolution) = &variant.resolution {
+                asset
+                    .metadata
+                    .insert(RESOLUTION_KEY.to_string(), resolution.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifies_lod_and_resolution_tokens() {
+        let lod = identify("Meshes/Rock_LOD_2.fbx");
+        assert_eq!((lod.name.as_str(), lod.lod), ("Rock", Some(2)));
+        assert_eq!(lod.group, "meshes/rock:mesh");
+
+        let texture = identify("Textures\\Rock_4k_Albedo.png");
+        assert_eq!(texture.name, "Rock_Albedo");
+        assert_eq!(texture.resolution.as_deref(), Some("4K"));
+        assert_eq!(
+            identify("Rock_2048.png").resolution.as_deref(),
+            Some("2048")
+        );
+        assert_eq!(identify("Rock_3k.png").resolution, None);
+    }
+
+    #[test]
+    fn groups_need_a_tagged_sibling() {
+        let variants: Vec<Variant> = ["Rock.fbx", "Rock_LOD1.fbx", "Rock.png", "Tree.fbx"]
+            .iter()
+            .map(|path| identify(path))
+            .collect();
+        assert_eq!(variant_groups(&variants), ["rock:mesh"]);
+
+        let untagged = [identify("A/Rock.fbx"), identify("B/Rock.fbx")];
+        assert!(variant_groups(&untagged).is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+use rusqlite::params;
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::catalog::{self, with_connection, AssetRecord, ASSET_COLUMNS};
+use crate::manifest::AssetManifest;
+
+const VARIANT_KEY: &str = "variant";
+const EXPORTED_FROM_KEY: &str = "exported_from";
+
+// Authoring formats that engines do not load directly.
+const SOURCE_ONLY: &[&str] = &[
+    "blend", "max", "ma", "mb", "c4d", "hip", "ztl", "zpr", "spp", "sbs", "kra", "xcf", "psb",
+];
+// Unity imports these as-is, so they only count as sources when an export of
+// them is in the pack.
+const LAYERED: &[&str] = &["psd", "tif", "tiff"];
+const EXPORTS: &[&str] = &[
+    "fbx", "obj", "gltf", "glb", "dae", "png", "tga", "jpg", "jpeg", "dds", "exr", "bmp", "webp",
+    "sbsar",
+];
+// Suffixes that mark the high- or low-poly version of the same model.
+const DETAIL_SUFFIXES: &[&str] = &[
+    "_highpoly",
+    "_lowpoly",
+    "_high",
+    "_low",
+    "_hp",
+    "_lp",
+    "_source",
+    "_src",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Source,
+    // Index of the file it was exported from.
+    Export(usize),
+    Standalone,
+}
+
+fn split(relative_path: &str) -> (&str, &str, String) {
+    let (dir, file) = relative_path
+        .rfind(['/', '\\'])
+        .map_or(("", relative_path), |index| {
+            (&relative_path[..index], &relative_path[index + 1..])
+        });
+    let (stem, extension) = file
+        .rsplit_once('.')
+        .filter(|(stem, _)| !stem.is_empty())
+        .unwrap_or((file, ""));
+    (dir, stem, extension.to_lowercase())
+}
+
+fn normalize(stem: &str) -> String {
+    let stem = stem.to_lowercase().replace([' ', '-'], "_");
+    DETAIL_SUFFIXES
+        .iter()
+        .find_map(|suffix| stem.strip_suffix(suffix))
+        .unwrap_or(&stem)
+        .to_string()
+}
+
+// Links exports to the source they came from by file name: `Rock.ztl` and
+// `Rock_high.blend` both match `Rock.fbx`, and `Rock.spp` matches
+// `Rock_BaseColor.png`. A source in the same folder wins over one elsewhere.
+fn link(paths: &[&str]) -> Vec<Role> {
+    let parts: Vec<_> = paths.iter().map(|path| split(path)).collect();
+    let mut sources: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, (_, stem, extension)) in parts.iter().enumerate() {
+        if SOURCE_ONLY.contains(&extension.as_str()) || LAYERED.contains(&extension.as_str()) {
+            sources.entry(normalize(stem)).or_default().push(index);
+        }
+    }
+
+    let mut roles: Vec<Role> = parts
+        .iter()
+        .map(|(_, _, extension)| {
+            if SOURCE_ONLY.contains(&extension.as_str()) {
+                Role::Source
+            } else {
+                Role::Standalone
+            }
+        })
+        .collect();
+    for (index, (dir, stem, extension)) in parts.iter().enumerate() {
+        if !EXPORTS.contains(&extension.as_str()) {
+            continue;
+        }
+        // Drops trailing `_Token` parts until a source matches.
+        let mut key = normalize(stem);
+        let candidates = loop {
+            if let Some(candidates) = sources.get(&key) {
+                break Some(candidates);
+            }
+            match key.rfind('_') {
+                Some(cut) if cut > 0 => key.truncate(cut),
+                _ => break None,
+            }
+        };
+        let Some(candidates) = candidates else {
+            continue;
+        };
+        let source = candidates
+            .iter()
+            .copied()
+            .find(|candidate| parts[*candidate].0 == *dir)
+            .unwrap_or(candidates[0]);
+        roles[index] = Role::Export(source);
+        roles[source] = Role::Source;
+    }
+    roles
+}
+
+// Marks linked files in each manifest printed by the ingestion tool:
+// `variant` is `source` or `game_ready`, and exports name their source in
+// `exported_from`.
+pub fn annotate(manifests: &mut [AssetManifest]) {
+    for manifest in manifests.iter_mut() {
+        let paths: Vec<&str> = manifest
+            .assets
+            .iter()
+            .map(|asset| asset.relative_path.as_str())
+            .collect();
+        let roles = link(&paths);
+        let sources: Vec<String> = roles
+            .iter()
+            .map(|role| match role {
+                Role::Export(source) => manifest.assets[*source].relative_path.clone(),
+                _ => String::new(),
+            })
+            .collect();
+        for ((asset, role), source) in manifest.assets.iter_mut().zip(roles).zip(sources) {
+            match role {
+                Role::Source => {
+                    asset
+                        .metadata
+                        .insert(VARIANT_KEY.to_string(), "source".to_string());
+                }
+                Role::Export(_) => {
+                    asset
+                        .metadata
+                        .insert(VARIANT_KEY.to_string(), "game_ready".to_string());
+                    asset.metadata.insert(EXPORTED_FROM_KEY.to_string(), source);
+                }
+                Role::Standalone => {}
+            }
+        }
+    }
+}
+
+// Links are worked out again from the catalog rather than read from
+// metadata, so packs ingested before linking existed are covered too.
+fn pack_roles(app: &AppHandle, pack_id: &str) -> Result<Vec<(AssetRecord, Role)>, String> {
+    let sql = format!(
+        "SELECT {} FROM assets a JOIN packs p ON p.pack_id = a.pack_id
+         WHERE a.pack_id = ?1
+         ORDER BY a.relative_path",
+        ASSET_COLUMNS
+    );
+    let assets: Vec<AssetRecord> = with_connection(app, |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        let rows = stmt
+            .query_map(params![pack_id], catalog::asset_from_row)
+            .map_err(|e| format!("Failed to query assets: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read assets: {}", e))
+    })?;
+    if assets.is_empty() {
+        return Err(format!("Unknown pack: {}", pack_id));
+    }
+    let paths: Vec<&str> = assets.iter().map(|asset| asset.relative_path()).collect();
+    let roles = link(&paths);
+    Ok(assets.into_iter().zip(roles).collect())
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct VariantGroup {
+    source: AssetRecord,
+    // Empty for source files with no export in the pack.
+    exports: Vec<AssetRecord>,
+}
+
+#[tauri::command]
+pub fn get_pack_variants(app: AppHandle, pack_id: String) -> Result<Vec<VariantGroup>, String> {
+    let roles = pack_roles(&app, &pack_id)?;
+    let mut exports: HashMap<usize, Vec<AssetRecord>> = HashMap::new();
+    let mut sources = Vec::new();
+    for (index, (asset, role)) in roles.into_iter().enumerate() {
+        match role {
+            Role::Source => sources.push((index, asset)),
+            Role::Export(source) => exports.entry(source).or_default().push(asset),
+            Role::Standalone => {}
+        }
+    }
+    Ok(sources
+        .into_iter()
+        .map(|(index, source)| VariantGroup {
+            source,
+            exports: exports.remove(&index).unwrap_or_default(),
+        })
+        .collect())
+}
+
+// The pack without its source files: what a "game-ready only" install copies.
+#[tauri::command]
+pub fn list_game_ready_assets(app: AppHandle, pack_id: String) -> Result<Vec<AssetRecord>, String> {
+    Ok(pack_roles(&app, &pack_id)?
+        .into_iter()
+        .filter(|(_, role)| *role != Role::Source)
+        .map(|(asset, _)| asset)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_exports_to_their_sources() {
+        let roles = link(&[
+            "Source/Rock_high.blend",
+            "Meshes/Rock.fbx",
+            "Textures/Rock.spp",
+            "Textures/Rock_BaseColor.png",
+            "Textures/Tree.png",
+        ]);
+        assert_eq!(
+            roles,
+            [
+                Role::Source,
+                Role::Export(0),
+                Role::Source,
+                Role::Export(2),
+                Role::Standalone,
+            ]
+        );
+    }
+
+    #[test]
+    fn layered_files_are_sources_only_when_exported() {
+        assert_eq!(link(&["Rock.psd"]), [Role::Standalone]);
+        assert_eq!(
+            link(&["Rock.psd", "Rock.png"]),
+            [Role::Source, Role::Export(0)]
+        );
+    }
+
+    #[test]
+    fn prefers_a_source_in_the_same_folder() {
+        let roles = link(&["A/Rock.blend", "B/Rock.blend", "B/Rock.fbx"]);
+        assert_eq!(roles[2], Role::Export(1));
+        assert_eq!(roles[0], Role::Source);
+    }
+}
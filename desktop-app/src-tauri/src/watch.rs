@@ -0,0 +1,322 @@
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::archives::{self, ArchiveFormat};
+use crate::jobs::now_millis;
+use crate::{access, inference, queue, storage, tags, IngestionConfig};
+
+const WATCH_FILE: &str = "watch-folders.json";
+const POLL: Duration = Duration::from_secs(2);
+// New entries are usually still being copied or downloaded when they first
+// appear, so they are queued only once they stop changing.
+const SETTLE: Duration = Duration::from_secs(10);
+
+// Packs that appear in `path` are ingested with these defaults.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchFolder {
+    path: String,
+    tags: Vec<String>,
+    license: Option<String>,
+    output_dir: Option<String>,
+    added_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct WatchSettings {
+    folders: Vec<WatchFolder>,
+}
+
+// File count, total size and newest modification time of an entry.
+type Signature = (u64, u64, Option<SystemTime>);
+
+struct Pending {
+    signature: Option<Signature>,
+    changed_at: Instant,
+}
+
+#[derive(Default)]
+pub struct WatchState {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    pending: Mutex<HashMap<PathBuf, Pending>>,
+    // Entries already queued, so later events for them are ignored until
+    // they are removed.
+    queued: Mutex<HashSet<PathBuf>>,
+    // Serializes read-modify-write cycles on the settings file.
+    lock: Mutex<()>,
+}
+
+// Emitted as `watch-ingestion` when a watched folder gains a pack.
+#[derive(Debug, Serialize, Clone)]
+pub struct WatchNotice {
+    folder: String,
+    path: String,
+    name: String,
+    job_id: String,
+}
+
+fn load(app: &AppHandle) -> WatchSettings {
+    storage::load_json(app, WATCH_FILE)
+}
+
+fn signature(path: &Path) -> Option<Signature> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return Some((1, metadata.len(), metadata.modified().ok()));
+    }
+    let mut signature = (0, 0, metadata.modified().ok());
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+                continue;
+            }
+            signature.0 += 1;
+            signature.1 += metadata.len();
+            signature.2 = signature.2.max(metadata.modified().ok());
+        }
+    }
+    Some(signature)
+}
+
+// Folders and zip or 7z archives are packs; loose files are ignored.
+fn is_pack(path: &Path) -> bool {
+    if path
+        .file_name()
+        .is_none_or(|name| name.to_string_lossy().starts_with('.'))
+    {
+        return false;
+    }
+    path.is_dir() || archives::detect(path).is_some_and(|format| format != ArchiveFormat::Rar)
+}
+
+fn watched_folder(app: &AppHandle, path: &Path) -> Option<WatchFolder> {
+    let parent = path.parent()?;
+    let canonical = fs::canonicalize(parent).ok();
+    load(app).folders.into_iter().find(|folder| {
+        let folder = Path::new(&folder.path);
+        folder == parent || canonical.as_deref() == Some(folder)
+    })
+}
+
+fn on_event(app: &AppHandle, event: Event) {
+    let state = app.state::<WatchState>();
+    match event.kind {
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_)) => {
+            for path in event.paths {
+                if !path.exists() || state.queued.lock().unwrap().contains(&path) {
+                    continue;
+                }
+                state
+                    .pending
+                    .lock()
+                    .unwrap()
+                    .entry(path)
+                    .or_insert(Pending {
+                        signature: None,
+                        changed_at: Instant::now(),
+                    });
+            }
+        }
+        EventKind::Remove(_) => {
+            for path in &event.paths {
+                state.pending.lock().unwrap().remove(path);
+                state.queued.lock().unwrap().remove(path);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn queue_pack(app: &AppHandle, folder: &WatchFolder, path: &Path) {
+    let name = inference::infer(&inference::stem_for(path))
+        .name()
+        .to_string();
+    let config = IngestionConfig {
+        path: Some(path.to_string_lossy().to_string()),
+        name: Some(name.clone()),
+        source: "filesystem".to_string(),
+        tags: folder.tags.clone(),
+        license: folder.license.clone(),
+        download_strategy: None,
+        download_filters: None,
+        output_dir: folder.output_dir.clone(),
+        priority: None,
+        force_sync: None,
+        sandbox: None,
+        asset_id: None,
+        timeout_secs: None,
+    };
+    let job_id = queue::enqueue(app, config, String::new());
+    let _ = app.emit(
+        "watch-ingestion",
+        WatchNotice {
+            folder: folder.path.clone(),
+            path: path.to_string_lossy().to_string(),
+            name,
+            job_id,
+        },
+    );
+}
+
+// Queues pending entries whose contents have not changed for `SETTLE`.
+fn settle(app: &AppHandle) {
+    let state = app.state::<WatchState>();
+    let paths: Vec<PathBuf> = state.pending.lock().unwrap().keys().cloned().collect();
+    for path in paths {
+        let current = signature(&path);
+        let ready = {
+            let mut pending = state.pending.lock().unwrap();
+            let Some(entry) = pending.get_mut(&path) else {
+                continue;
+            };
+            if current.is_none() {
+                pending.remove(&path);
+                continue;
+            }
+            if entry.signature != current {
+                entry.signature = current;
+                entry.changed_at = Instant::now();
+                false
+            } else {
+                entry.changed_at.elapsed() >= SETTLE
+            }
+        };
+        if !ready {
+            continue;
+        }
+        state.pending.lock().unwrap().remove(&path);
+        if !is_pack(&path) {
+            continue;
+        }
+        let Some(folder) = watched_folder(app, &path) else {
+            continue;
+        };
+        state.queued.lock().unwrap().insert(path.clone());
+        if access::ensure_writable(app).is_err() {
+            continue;
+        }
+        queue_pack(app, &folder, &path);
+    }
+}
+
+// Starts watching the saved folders. Only entries that appear from now on
+// are ingested; what is already in a folder is left alone.
+pub fn start(app: &AppHandle) {
+    let handler_app = app.clone();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event {
+            on_event(&handler_app, event);
+        }
+    });
+    // Without a watcher, adding a folder reports the problem instead.
+    let Ok(mut watcher) = watcher else {
+        return;
+    };
+    for folder in load(app).folders {
+        let _ = watcher.watch(Path::new(&folder.path), RecursiveMode::NonRecursive);
+    }
+    *app.state::<WatchState>().watcher.lock().unwrap() = Some(watcher);
+
+    let app = app.clone();
+    thread::spawn(move || loop {
+        thread::sleep(POLL);
+        settle(&app);
+    });
+}
+
+#[tauri::command]
+pub fn list_watch_folders(app: AppHandle) -> Vec<WatchFolder> {
+    load(&app).folders
+}
+
+#[tauri::command]
+pub fn add_watch_folder(
+    app: AppHandle,
+    state: State<'_, WatchState>,
+    path: String,
+    tags: Vec<String>,
+    license: Option<String>,
+    output_dir: Option<String>,
+) -> Result<Vec<WatchFolder>, String> {
+    access::ensure_writable(&app)?;
+    let canonical =
+        fs::canonicalize(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if !canonical.is_dir() {
+        return Err(format!("{} is not a directory", path));
+    }
+    let output_dir = output_dir.filter(|dir| !dir.trim().is_empty());
+    // Archives would be unpacked into the folder and picked up again.
+    if output_dir
+        .as_deref()
+        .and_then(|dir| fs::canonicalize(dir).ok())
+        .is_some_and(|dir| dir == canonical)
+    {
+        return Err("The output directory cannot be the watched folder".to_string());
+    }
+    let tags = tags::normalize_all(&tags)?;
+    let path = canonical.to_string_lossy().to_string();
+
+    let _guard = state.lock.lock().unwrap();
+    let mut settings = load(&app);
+    if settings.folders.iter().any(|folder| folder.path == path) {
+        return Err(format!("{} is already watched", path));
+    }
+    state
+        .watcher
+        .lock()
+        .unwrap()
+        .as_mut()
+        .ok_or("Folder watching is not available on this system")?
+        .watch(&canonical, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", path, e))?;
+    settings.folders.push(WatchFolder {
+        path,
+        tags,
+        license: license.filter(|license| !license.trim().is_empty()),
+        output_dir,
+        added_at: now_millis(),
+    });
+    storage::save_json(&app, WATCH_FILE, &settings)?;
+    Ok(settings.folders)
+}
+
+#[tauri::command]
+pub fn remove_watch_folder(
+    app: AppHandle,
+    state: State<'_, WatchState>,
+    path: String,
+) -> Result<Vec<WatchFolder>, String> {
+    access::ensure_writable(&app)?;
+    let _guard = state.lock.lock().unwrap();
+    let mut settings = load(&app);
+    let before = settings.folders.len();
+    settings.folders.retain(|folder| folder.path != path);
+    if settings.folders.len() == before {
+        return Err(format!("{} is not watched", path));
+    }
+    if let Some(watcher) = state.watcher.lock().unwrap().as_mut() {
+        let _ = watcher.unwatch(Path::new(&path));
+    }
+    state
+        .pending
+        .lock()
+        .unwrap()
+        .retain(|pending, _| !pending.starts_with(&path));
+    storage::save_json(&app, WATCH_FILE, &settings)?;
+    Ok(settings.folders)
+}
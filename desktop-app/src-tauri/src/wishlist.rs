@@ -0,0 +1,196 @@
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::access;
+use crate::catalog::with_connection;
+use crate::jobs::now_millis;
+use crate::marketplace::{self, Marketplace};
+
+// A marketplace listing someone on the team wants. Each listing appears once;
+// adding it again counts as a vote from the new person.
+#[derive(Debug, Serialize, Clone)]
+pub struct WishlistItem {
+    id: i64,
+    url: String,
+    marketplace: Marketplace,
+    item_id: String,
+    name: Option<String>,
+    added_by: String,
+    added_at: u64,
+    voters: Vec<String>,
+    // Set once the listing shows up in the owned library.
+    external_asset_id: Option<i64>,
+}
+
+const COLUMNS: &str = "w.id, w.url, w.marketplace, w.item_id, w.name, w.added_by, w.added_at,
+     e.id, (SELECT json_group_array(voter) FROM
+         (SELECT voter FROM wishlist_votes v WHERE v.wishlist_id = w.id ORDER BY voted_at))";
+
+const FROM: &str = "wishlist w
+     LEFT JOIN external_assets e ON e.marketplace = w.marketplace AND e.item_id = w.item_id";
+
+fn from_row(row: &Row) -> rusqlite::Result<WishlistItem> {
+    let marketplace: String = row.get(2)?;
+    let voters: String = row.get(8)?;
+    Ok(WishlistItem {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        marketplace: Marketplace::parse(&marketplace).unwrap_or(Marketplace::Fab),
+        item_id: row.get(3)?,
+        name: row.get(4)?,
+        added_by: row.get(5)?,
+        added_at: row.get::<_, i64>(6)?.max(0) as u64,
+        external_asset_id: row.get(7)?,
+        voters: serde_json::from_str(&voters).unwrap_or_default(),
+    })
+}
+
+fn get(conn: &Connection, id: i64) -> Result<WishlistItem, String> {
+    let sql = format!("SELECT {} FROM {} WHERE w.id = ?1", COLUMNS, FROM);
+    conn.query_row(&sql, params![id], from_row)
+        .optional()
+        .map_err(|e| format!("Failed to read wishlist item {}: {}", id, e))?
+        .ok_or(format!("Unknown wishlist item: {}", id))
+}
+
+fn notify(app: &AppHandle, item: &WishlistItem) {
+    let _ = app.emit("wishlist-changed", item);
+}
+
+fn voter_name(voter: String) -> Result<String, String> {
+    let voter = voter.trim().to_string();
+    if voter.is_empty() {
+        return Err("Wishlist votes need a name".to_string());
+    }
+    Ok(voter)
+}
+
+// Listings the studio already owns are refused rather than added.
+#[tauri::command]
+pub fn add_to_wishlist(
+    app: AppHandle,
+    url: String,
+    voter: String,
+    name: Option<String>,
+) -> Result<WishlistItem, String> {
+    access::ensure_writable(&app)?;
+    let (marketplace, item_id) = marketplace::listing_id(&url)?;
+    let voter = voter_name(voter)?;
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    let item = with_connection(&app, |conn| {
+        let owned = conn
+            .query_row(
+                "SELECT name FROM external_assets WHERE marketplace = ?1 AND item_id = ?2",
+                params![marketplace.as_str(), item_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to query external assets: {}", e))?;
+        if let Some(owned) = owned {
+            return Err(format!("{} is already in the library", owned));
+        }
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to save wishlist item: {}", e))?;
+        let now = now_millis() as i64;
+        tx.execute(
+            "INSERT INTO wishlist (url, marketplace, item_id, name, added_by, added_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(marketplace, item_id) DO UPDATE SET
+                 name = COALESCE(name, excluded.name)",
+            params![url.trim(), marketplace.as_str(), item_id, name, voter, now],
+        )
+        .map_err(|e| format!("Failed to save wishlist item: {}", e))?;
+        let id: i64 = tx
+            .query_row(
+                "SELECT id FROM wishlist WHERE marketplace = ?1 AND item_id = ?2",
+                params![marketplace.as_str(), item_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to save wishlist item: {}", e))?;
+        tx.execute(
+            "INSERT OR IGNORE INTO wishlist_votes (wishlist_id, voter, voted_at)
+             VALUES (?1, ?2, ?3)",
+            params![id, voter, now],
+        )
+        .map_err(|e| format!("Failed to save wishlist vote: {}", e))?;
+        tx.commit()
+            .map_err(|e| format!("Failed to save wishlist item: {}", e))?;
+        get(conn, id)
+    })?;
+    notify(&app, &item);
+    Ok(item)
+}
+
+#[tauri::command]
+pub fn set_wishlist_vote(
+    app: AppHandle,
+    id: i64,
+    voter: String,
+    vote: bool,
+) -> Result<WishlistItem, String> {
+    access::ensure_writable(&app)?;
+    let voter = voter_name(voter)?;
+    let item = with_connection(&app, |conn| {
+        get(conn, id)?;
+        let result = if vote {
+            conn.execute(
+                "INSERT OR IGNORE INTO wishlist_votes (wishlist_id, voter, voted_at)
+                 VALUES (?1, ?2, ?3)",
+                params![id, voter, now_millis() as i64],
+            )
+        } else {
+            conn.execute(
+                "DELETE FROM wishlist_votes WHERE wishlist_id = ?1 AND voter = ?2",
+                params![id, voter],
+            )
+        };
+        result.map_err(|e| format!("Failed to save wishlist vote: {}", e))?;
+        get(conn, id)
+    })?;
+    notify(&app, &item);
+    Ok(item)
+}
+
+// Most-wanted first. Owned listings are hidden unless asked for, so the list
+// shows what is still worth buying.
+#[tauri::command]
+pub fn list_wishlist(
+    app: AppHandle,
+    include_owned: Option<bool>,
+) -> Result<Vec<WishlistItem>, String> {
+    let sql = format!(
+        "SELECT {} FROM {}
+         WHERE ?1 OR e.id IS NULL
+         ORDER BY (SELECT COUNT(*) FROM wishlist_votes v WHERE v.wishlist_id = w.id) DESC,
+                  w.added_at",
+        COLUMNS, FROM
+    );
+    with_connection(&app, |conn| {
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Failed to query wishlist: {}", e))?;
+        let rows = stmt
+            .query_map(params![include_owned.unwrap_or(false)], from_row)
+            .map_err(|e| format!("Failed to query wishlist: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read wishlist: {}", e))
+    })
+}
+
+#[tauri::command]
+pub fn remove_from_wishlist(app: AppHandle, id: i64) -> Result<(), String> {
+    access::ensure_writable(&app)?;
+    with_connection(&app, |conn| {
+        get(conn, id)?;
+        conn.execute("DELETE FROM wishlist WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to remove wishlist item {}: {}", id, e))
+    })?;
+    let _ = app.emit("wishlist-item-removed", id);
+    Ok(())
+}
@@ -6,7 +6,8 @@ import { IngestionForm } from './components/IngestionForm';
 import { LogViewer } from './components/LogViewer';
 import { ResultView } from './components/ResultView';
 import { Settings, loadSettings } from './components/Settings';
-import type { IngestionConfig, LogEntry, IngestionResult, AppSettings } from './types';
+import { isCommandError, logLevel } from './types';
+import type { IngestionConfig, LogBatch, LogEntry, IngestionResult, AppSettings } from './types';
 
 type AppState = 'idle' | 'running' | 'complete' | 'error';
 
@@ -18,12 +19,21 @@ function App() {
   const [settings, setSettings] = useState<AppSettings>(loadSettings);
 
   useEffect(() => {
-    const unlistenLog = listen<{ message: string; level: string }>('ingestion-log', (event) => {
-      setLogs((prev) => [...prev, {
-        timestamp: new Date().toISOString(),
-        message: event.payload.message,
-        level: event.payload.level as LogEntry['level'],
-      }]);
+    const unlistenLog = listen<LogBatch>('ingestion-log-batch', (event) => {
+      const timestamp = new Date().toISOString();
+      const entries: LogEntry[] = event.payload.entries.map((entry) => ({
+        timestamp,
+        message: entry.message,
+        level: logLevel(entry.type),
+      }));
+      if (event.payload.dropped > 0) {
+        entries.unshift({
+          timestamp,
+          message: `${event.payload.dropped} log lines omitted`,
+          level: 'warn',
+        });
+      }
+      setLogs((prev) => [...prev, ...entries]);
     });
 
     return () => {
@@ -99,7 +109,7 @@ function App() {
     } catch (err) {
       const errorResult: IngestionResult = {
         success: false,
-        error: err instanceof Error ? err.message : String(err),
+        error: err instanceof Error || isCommandError(err) ? err.message : String(err),
         manifest: null,
         assetCount: 0,
         totalSize: 0,
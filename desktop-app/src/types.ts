@@ -30,6 +30,33 @@ export interface LogEntry {
   message: string;
 }
 
+export interface BackendLogEntry {
+  type: string;
+  message: string;
+  code: string;
+  params: Record<string, string>;
+}
+
+// Log lines arrive in batches of up to ~100ms; `dropped` counts lines
+// discarded when the backend buffer overflowed.
+export interface LogBatch {
+  entries: BackendLogEntry[];
+  dropped: number;
+}
+
+export function logLevel(type: string): LogEntry['level'] {
+  switch (type) {
+    case 'error':
+      return 'error';
+    case 'warn':
+    case 'timeout':
+    case 'cancelled':
+      return 'warn';
+    default:
+      return 'info';
+  }
+}
+
 export interface IngestionResult {
   success: boolean;
   manifest: string | null;
@@ -39,6 +66,18 @@ export interface IngestionResult {
   manifestCount?: number;
 }
 
+export type ErrorCategory = 'access' | 'environment' | 'input' | 'auth' | 'process' | 'internal';
+
+export interface CommandError {
+  code: string;
+  category: ErrorCategory;
+  message: string;
+}
+
+export function isCommandError(err: unknown): err is CommandError {
+  return typeof err === 'object' && err !== null && 'code' in err && 'message' in err;
+}
+
 export interface AppSettings {
   ingestionPath: string;
   outputDirectory: string;
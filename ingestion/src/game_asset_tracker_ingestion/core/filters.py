@@ -0,0 +1,106 @@
+"""File-level filters for marketplace downloads.
+
+Marketplace packages arrive as a single archive, so filters cannot avoid the
+download itself; they keep unwanted files from being written out when a
+package is extracted.
+"""
+
+import json
+import re
+from dataclasses import dataclass
+from pathlib import Path, PurePosixPath
+
+# Folder names that hold demo content rather than the assets themselves
+EXAMPLE_DIRECTORIES = {
+    "demo",
+    "demos",
+    "demoscene",
+    "demoscenes",
+    "example",
+    "examples",
+    "sample",
+    "samples",
+    "showcase",
+}
+
+# Source art and audio, as opposed to engine-specific files such as prefabs,
+# materials, scenes and .meta files
+SOURCE_EXTENSIONS = set(
+    "psd psb kra xcf tif tiff exr hdr png tga jpg jpeg "
+    "blend fbx obj dae gltf glb max ma mb c4d 3ds ztl zpr spp sbs sbsar "
+    "wav flac aif aiff ogg mp3".split()
+)
+
+# "8K" as its own token, e.g. Rock_8K_Albedo.png or textures/8k/
+EIGHT_K_PATTERN = re.compile(r"(?:^|[^a-z0-9])8k(?:[^a-z0-9]|$)", re.IGNORECASE)
+
+
+@dataclass(frozen=True)
+class DownloadFilters:
+    """Which files to drop from an extracted package."""
+
+    skip_examples: bool = False
+    skip_8k: bool = False
+    source_art_only: bool = False
+
+    @classmethod
+    def from_json(cls, spec: str) -> "DownloadFilters":
+        """Parse the filter spec passed by the desktop app.
+
+        Raises:
+            ValueError: If the spec is not an object or has unknown keys
+        """
+        data = json.loads(spec)
+        if not isinstance(data, dict):
+            raise ValueError("Download filters must be a JSON object")
+        unknown = set(data) - {"skip_examples", "skip_8k", "source_art_only"}
+        if unknown:
+            raise ValueError(f"Unknown download filters: {', '.join(sorted(unknown))}")
+        return cls(**{key: bool(value) for key, value in data.items()})
+
+    def is_empty(self) -> bool:
+        return not (self.skip_examples or self.skip_8k or self.source_art_only)
+
+    def exclusion_reason(self, relative_path: str) -> str | None:
+        """Return why a package file is filtered out, or None to keep it."""
+        path = PurePosixPath(relative_path.replace("\\", "/"))
+        folders = [part.lower().replace(" ", "").replace("_", "") for part in path.parts[:-1]]
+        if self.skip_examples and any(folder in EXAMPLE_DIRECTORIES for folder in folders):
+            return "example"
+        if self.skip_8k and any(EIGHT_K_PATTERN.search(part) for part in path.parts):
+            return "8k"
+        if self.source_art_only and path.suffix.lower().lstrip(".") not in SOURCE_EXTENSIONS:
+            return "not_source_art"
+        return None
+
+
+@dataclass
+class PruneSummary:
+    files_removed: int = 0
+    bytes_removed: int = 0
+
+
+def prune(directory: Path, filters: DownloadFilters) -> PruneSummary:
+    """Delete files under directory that the filters exclude.
+
+    Folders left empty are removed as well.
+    """
+    summary = PruneSummary()
+    if filters.is_empty():
+        return summary
+
+    for path in sorted(directory.rglob("*")):
+        if not path.is_file() or path.is_symlink():
+            continue
+        relative = path.relative_to(directory).as_posix()
+        if filters.exclusion_reason(relative) is None:
+            continue
+        summary.bytes_removed += path.stat().st_size
+        summary.files_removed += 1
+        path.unlink()
+
+    # Deepest first, so parents are empty by the time they are checked
+    for folder in sorted(directory.rglob("*"), key=lambda p: len(p.parts), reverse=True):
+        if folder.is_dir() and not any(folder.iterdir()):
+            folder.rmdir()
+    return summary
@@ -0,0 +1,64 @@
+"""Resuming interrupted marketplace downloads.
+
+The desktop app keeps the checkpoint: the helper reports each package as it
+starts and finishes, and a resumed run receives the state the app recorded so
+far as a JSON file passed with --resume-from.
+"""
+
+import json
+import shutil
+from dataclasses import dataclass, field
+from pathlib import Path
+
+
+@dataclass(frozen=True)
+class ResumeState:
+    """Packages already done and files left behind by the one in progress."""
+
+    completed: frozenset[str] = frozenset()
+    partial_files: tuple[str, ...] = field(default_factory=tuple)
+
+    @classmethod
+    def load(cls, path: Path) -> "ResumeState":
+        """Read the handoff file written by the desktop app.
+
+        Raises:
+            ValueError: If the file is not a valid resume state
+        """
+        try:
+            data = json.loads(path.read_text())
+        except (OSError, json.JSONDecodeError) as e:
+            raise ValueError(f"Cannot read resume state {path}: {e}") from e
+        if not isinstance(data, dict):
+            raise ValueError("Resume state must be a JSON object")
+        completed = data.get("completed", [])
+        partial_files = data.get("partial_files", [])
+        if not all(isinstance(item, str) for item in [*completed, *partial_files]):
+            raise ValueError("Resume state lists must hold strings")
+        return cls(completed=frozenset(completed), partial_files=tuple(partial_files))
+
+
+def discard_partial_files(paths: tuple[str, ...]) -> int:
+    """Delete leftovers of an interrupted package so it is fetched cleanly.
+
+    Returns the number of paths removed.
+    """
+    removed = 0
+    for raw in paths:
+        path = Path(raw)
+        if path.is_symlink() or path.is_file():
+            path.unlink()
+        elif path.is_dir():
+            shutil.rmtree(path)
+        else:
+            continue
+        removed += 1
+    return removed
+
+
+def checkpoint_event(event: str, item: str, partial: list[str] | None = None) -> str:
+    """Format a JSON-lines event the desktop app records in its checkpoint."""
+    payload: dict[str, object] = {"event": event, "item": item}
+    if partial is not None:
+        payload["partial"] = partial
+    return json.dumps(payload)
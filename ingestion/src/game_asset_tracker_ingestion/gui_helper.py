@@ -1,8 +1,23 @@
 #!/usr/bin/env python3
 import argparse
 import json
+import os
 import sys
 from pathlib import Path
+from typing import Any
+
+
+# The desktop app passes keychain credentials through the environment; they are
+# removed so subprocesses started here do not inherit them.
+def stored_credentials(variable: str) -> Any:
+    value = os.environ.pop(variable, None)
+    if not value:
+        return None
+    try:
+        return json.loads(value)
+    except json.JSONDecodeError:
+        print(f"Ignoring malformed {variable}; capturing a new login", file=sys.stderr)
+        return None
 
 
 def run_filesystem(args: argparse.Namespace) -> None:
@@ -31,9 +46,13 @@ def run_fab(args: argparse.Namespace) -> None:
     from game_asset_tracker_ingestion.registry import SourceRegistry
 
     print("Initializing FAB authentication...", file=sys.stderr)
-    print("This will open Epic Games Launcher to capture auth cookies.", file=sys.stderr)
-    extractor = MitmproxyExtractor()
-    cookies = extractor.capture_cookies(auto_install_cert=True)
+    cookies = stored_credentials("GAT_FAB_COOKIES")
+    if cookies is None:
+        print("This will open Epic Games Launcher to capture auth cookies.", file=sys.stderr)
+        extractor = MitmproxyExtractor()
+        cookies = extractor.capture_cookies(auto_install_cert=True)
+    else:
+        print("Using stored FAB credentials.", file=sys.stderr)
     auth = EpicGamesLauncherAuth(cookies=cookies)
     client = FabClient(auth=auth)
 
@@ -60,6 +79,16 @@ def run_fab(args: argparse.Namespace) -> None:
     print(f"Completed: {manifest_count} manifests generated", file=sys.stderr)
 
 
+def select_library_items(items, asset_id: str | None):
+    if not asset_id:
+        return items
+    selected = [item for item in items if str(item.package_id) == asset_id]
+    if not selected:
+        print(f"Asset {asset_id} is not in the UAS library", file=sys.stderr)
+        sys.exit(1)
+    return selected
+
+
 def run_uas(args: argparse.Namespace) -> None:
     try:
         from uas_adapter import UnityHubAuth, AssetDownloader
@@ -73,8 +102,12 @@ def run_uas(args: argparse.Namespace) -> None:
     from game_asset_tracker_ingestion.registry import SourceRegistry
 
     print("Initializing UAS authentication...", file=sys.stderr)
-    extractor = ElectronExtractor()
-    tokens = extractor.extract_tokens()
+    tokens = stored_credentials("GAT_UAS_TOKENS")
+    if tokens is None:
+        extractor = ElectronExtractor()
+        tokens = extractor.extract_tokens()
+    else:
+        print("Using stored UAS credentials.", file=sys.stderr)
     auth = UnityHubAuth(
         access_token=tokens["accessToken"],
         access_token_expiration=tokens["accessTokenExpiration"],
@@ -87,7 +120,42 @@ def run_uas(args: argparse.Namespace) -> None:
 
     strategy = args.download_strategy
 
+    filters = None
+    if args.filters:
+        from game_asset_tracker_ingestion.core.filters import DownloadFilters
+
+        try:
+            filters = DownloadFilters.from_json(args.filters)
+        except ValueError as e:
+            print(f"Invalid download filters: {e}", file=sys.stderr)
+            sys.exit(1)
+        if strategy != "extract" and not filters.is_empty():
+            print("Download filters need the extract strategy", file=sys.stderr)
+            sys.exit(1)
+
+    completed: frozenset[str] = frozenset()
+    if args.resume_from:
+        from game_asset_tracker_ingestion.core.resume import ResumeState, discard_partial_files
+
+        if strategy not in ("download", "extract"):
+            print("Resuming needs the download or extract strategy", file=sys.stderr)
+            sys.exit(1)
+        try:
+            state = ResumeState.load(Path(args.resume_from))
+        except ValueError as e:
+            print(str(e), file=sys.stderr)
+            sys.exit(1)
+        completed = state.completed
+        removed = discard_partial_files(state.partial_files)
+        print(
+            f"Resuming: {len(completed)} packages already done, "
+            f"{removed} partial files discarded",
+            file=sys.stderr,
+        )
+
     if strategy in ("download", "extract"):
+        from game_asset_tracker_ingestion.core.resume import checkpoint_event
+
         print(f"Running UAS ingestion with {strategy} strategy...", file=sys.stderr)
         downloader = AssetDownloader(auth)
         pkg_extractor = PackageExtractor() if strategy == "extract" else None
@@ -97,16 +165,28 @@ def run_uas(args: argparse.Namespace) -> None:
         library = client.get_library()
         manifest_count = 0
 
-        for item in library.results:
+        for item in select_library_items(library.results, args.asset_id):
             asset_id = str(item.package_id)
+            if asset_id in completed:
+                print(f"Skipping {item.display_name} ({asset_id}): already done", file=sys.stderr)
+                continue
+            # Each package downloads into its own folder, so a half-written
+            # file is known before the downloader names it.
+            package_dir = downloads_dir / asset_id
+            extract_dir = output_dir / "extracted" / asset_id
+            partial = [str(package_dir)]
+            if strategy == "extract":
+                partial.append(str(extract_dir))
+            print(checkpoint_event("item_started", asset_id, partial), file=sys.stderr)
             print(f"Downloading {item.display_name} ({asset_id})...", file=sys.stderr)
 
             def progress_cb(msg: str) -> None:
                 print(f"  {msg}", file=sys.stderr)
 
+            package_dir.mkdir(parents=True, exist_ok=True)
             result = downloader.download_asset(
                 asset_id=asset_id,
-                output_dir=str(downloads_dir),
+                output_dir=str(package_dir),
                 on_progress=progress_cb,
             )
             print(
@@ -114,11 +194,20 @@ def run_uas(args: argparse.Namespace) -> None:
             )
 
             if strategy == "extract" and pkg_extractor:
-                extract_dir = output_dir / "extracted" / asset_id
                 extract_dir.mkdir(parents=True, exist_ok=True)
                 pkg_extractor.extract_package(result["file_path"], str(extract_dir))
                 print(f"  Extracted to: {extract_dir}", file=sys.stderr)
+                if filters is not None:
+                    from game_asset_tracker_ingestion.core.filters import prune
+
+                    pruned = prune(extract_dir, filters)
+                    print(
+                        f"  Filtered out {pruned.files_removed} files "
+                        f"({pruned.bytes_removed / (1024 * 1024):.2f} MB)",
+                        file=sys.stderr,
+                    )
 
+            print(checkpoint_event("item_completed", asset_id), file=sys.stderr)
             manifest_count += 1
 
         print(f"Completed: {manifest_count} packages processed", file=sys.stderr)
@@ -130,7 +219,7 @@ def run_uas(args: argparse.Namespace) -> None:
         library = client.get_library()
         manifest_count = 0
 
-        for item in library.results:
+        for item in select_library_items(library.results, args.asset_id):
             asset_id = str(item.package_id)
             print(f"Fetching download info for {item.display_name}...", file=sys.stderr)
             download_info = downloader.get_download_info(asset_id)
@@ -197,6 +286,17 @@ def main() -> None:
         help="Download strategy: metadata_only (API only), manifests_only (get download info), download (download+decrypt), extract (download+decrypt+extract)",
     )
     uas_parser.add_argument("--output-dir", help="Output directory for manifests")
+    uas_parser.add_argument("--asset-id", help="Only process this package id from the library")
+    uas_parser.add_argument(
+        "--filters",
+        help="JSON object of file filters applied on extraction: "
+        "skip_examples, skip_8k, source_art_only",
+    )
+    uas_parser.add_argument(
+        "--resume-from",
+        help="JSON file from the desktop app listing packages already downloaded "
+        "and partial files to discard",
+    )
 
     args = parser.parse_args()
 
@@ -0,0 +1,80 @@
+"""Tests for download filters."""
+
+import tempfile
+from pathlib import Path
+
+import pytest
+
+from game_asset_tracker_ingestion.core.filters import DownloadFilters, prune
+
+
+class TestDownloadFiltersFromJson:
+    """Test parsing of the filter spec passed by the desktop app."""
+
+    def test_parses_known_filters(self) -> None:
+        """Test that known keys are read and missing ones default to off."""
+        filters = DownloadFilters.from_json('{"skip_examples": true, "skip_8k": false}')
+        assert filters == DownloadFilters(skip_examples=True)
+
+    def test_rejects_unknown_filters(self) -> None:
+        """Test that unknown keys are reported rather than ignored."""
+        with pytest.raises(ValueError, match="Unknown download filters: skip_4k"):
+            DownloadFilters.from_json('{"skip_4k": true}')
+
+    def test_rejects_non_objects(self) -> None:
+        """Test that the spec must be an object."""
+        with pytest.raises(ValueError, match="must be a JSON object"):
+            DownloadFilters.from_json("[]")
+
+
+class TestExclusionReason:
+    """Test which package files each filter drops."""
+
+    def test_skip_examples_matches_demo_folders(self) -> None:
+        """Test that files inside example and demo folders are dropped."""
+        filters = DownloadFilters(skip_examples=True)
+        assert filters.exclusion_reason("Assets/Pack/Demo Scenes/Forest.unity") == "example"
+        assert filters.exclusion_reason("Assets/Pack/Examples/Readme.txt") == "example"
+        assert filters.exclusion_reason("Assets/Pack/Textures/Example.png") is None
+
+    def test_skip_8k_matches_token(self) -> None:
+        """Test that 8K variants are dropped by name or folder."""
+        filters = DownloadFilters(skip_8k=True)
+        assert filters.exclusion_reason("Textures/Rock_8K_Albedo.png") == "8k"
+        assert filters.exclusion_reason("Textures/8k/Rock.png") == "8k"
+        assert filters.exclusion_reason("Textures/Rock_4K_Albedo.png") is None
+        assert filters.exclusion_reason("Textures/Rock18k.png") is None
+
+    def test_source_art_only_keeps_source_formats(self) -> None:
+        """Test that engine-specific files are dropped."""
+        filters = DownloadFilters(source_art_only=True)
+        assert filters.exclusion_reason("Models/Tree.fbx") is None
+        assert filters.exclusion_reason("Source/Tree.PSD") is None
+        assert filters.exclusion_reason("Prefabs/Tree.prefab") == "not_source_art"
+        assert filters.exclusion_reason("Models/Tree.fbx.meta") == "not_source_art"
+
+    def test_no_filters_keep_everything(self) -> None:
+        """Test that an empty filter set excludes nothing."""
+        filters = DownloadFilters()
+        assert filters.is_empty()
+        assert filters.exclusion_reason("Demo/Rock_8K.prefab") is None
+
+
+class TestPrune:
+    """Test removal of filtered files from an extracted package."""
+
+    def test_removes_excluded_files_and_empty_folders(self) -> None:
+        """Test that excluded files are deleted and counted."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "Demo").mkdir()
+            (root / "Demo" / "Scene.unity").write_bytes(b"x" * 10)
+            (root / "Textures").mkdir()
+            (root / "Textures" / "Rock.png").write_bytes(b"x" * 5)
+
+            summary = prune(root, DownloadFilters(skip_examples=True))
+
+            assert summary.files_removed == 1
+            assert summary.bytes_removed == 10
+            assert not (root / "Demo").exists()
+            assert (root / "Textures" / "Rock.png").exists()
@@ -0,0 +1,86 @@
+"""Tests for resuming interrupted downloads."""
+
+import json
+import tempfile
+from pathlib import Path
+
+import pytest
+
+from game_asset_tracker_ingestion.core.resume import (
+    ResumeState,
+    checkpoint_event,
+    discard_partial_files,
+)
+
+
+class TestResumeStateLoad:
+    """Test reading the handoff file written by the desktop app."""
+
+    def test_reads_completed_and_partial_files(self) -> None:
+        """Test that both lists are read."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "resume.json"
+            path.write_text(json.dumps({"completed": ["1", "2"], "partial_files": ["/x"]}))
+
+            state = ResumeState.load(path)
+
+            assert state.completed == frozenset({"1", "2"})
+            assert state.partial_files == ("/x",)
+
+    def test_missing_lists_default_to_empty(self) -> None:
+        """Test that an empty object means nothing to skip."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "resume.json"
+            path.write_text("{}")
+
+            assert ResumeState.load(path) == ResumeState()
+
+    def test_rejects_unreadable_files(self) -> None:
+        """Test that a missing or malformed file is reported."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "resume.json"
+            with pytest.raises(ValueError, match="Cannot read resume state"):
+                ResumeState.load(path)
+            path.write_text('{"completed": [1]}')
+            with pytest.raises(ValueError, match="must hold strings"):
+                ResumeState.load(path)
+
+
+class TestDiscardPartialFiles:
+    """Test cleanup of an interrupted package."""
+
+    def test_removes_files_and_folders(self) -> None:
+        """Test that files and folders are removed and missing paths skipped."""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "extracted" / "1").mkdir(parents=True)
+            (root / "extracted" / "1" / "Rock.png").write_bytes(b"x")
+            (root / "1.unitypackage").write_bytes(b"x")
+
+            removed = discard_partial_files(
+                (
+                    str(root / "extracted" / "1"),
+                    str(root / "1.unitypackage"),
+                    str(root / "missing"),
+                )
+            )
+
+            assert removed == 2
+            assert not (root / "extracted" / "1").exists()
+            assert not (root / "1.unitypackage").exists()
+
+
+class TestCheckpointEvent:
+    """Test the JSON-lines events read by the desktop app."""
+
+    def test_formats_events(self) -> None:
+        """Test that partial paths are only included when given."""
+        assert json.loads(checkpoint_event("item_completed", "7")) == {
+            "event": "item_completed",
+            "item": "7",
+        }
+        assert json.loads(checkpoint_event("item_started", "7", ["/out/7"])) == {
+            "event": "item_started",
+            "item": "7",
+            "partial": ["/out/7"],
+        }